@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use anyhow::{bail, Result};
-use syspulse_core::ipc::client::IpcClient;
+use syspulse_core::ipc::client::{IpcClient, ResponseStream};
 use syspulse_core::ipc::protocol::{Request, Response};
 
 pub struct CliClient {
@@ -22,6 +22,13 @@ impl CliClient {
         Ok(self.inner.send(request).await?)
     }
 
+    pub async fn stream(&self, request: Request) -> Result<ResponseStream> {
+        if !self.inner.is_manager_running().await {
+            bail!("syspulse daemon is not running. Start it with: syspulse daemon");
+        }
+        Ok(self.inner.stream(request).await?)
+    }
+
     pub fn ensure_success(response: &Response) -> Result<()> {
         if let Response::Error { code, message } = response {
             bail!("Error ({}): {}", code, message);
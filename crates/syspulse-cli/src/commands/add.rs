@@ -4,7 +4,7 @@ use std::path::Path;
 
 use anyhow::{bail, Result};
 use syspulse_core::config::parse_config_file;
-use syspulse_core::daemon::DaemonSpec;
+use syspulse_core::daemon::{CatchUp, DaemonSpec, ReadyMode, ScheduleOverlap};
 use syspulse_core::ipc::protocol::{Request, Response};
 use syspulse_core::restart::RestartPolicy;
 
@@ -55,7 +55,7 @@ fn discover_config_file_in_dir(dir: &Path) -> Result<Option<std::path::PathBuf>>
     }
 }
 
-fn discover_config_file() -> Result<Option<std::path::PathBuf>> {
+pub(crate) fn discover_config_file() -> Result<Option<std::path::PathBuf>> {
     let cwd = std::env::current_dir()?;
     discover_config_file_in_dir(&cwd)
 }
@@ -95,11 +95,27 @@ pub async fn run(
                 restart_policy: RestartPolicy::default(),
                 resource_limits: None,
                 schedule: None,
+                schedule_timezone: None,
+                every: None,
+                jitter: None,
+                catch_up: CatchUp::None,
+                schedule_overlap: ScheduleOverlap::Skip,
+                max_runtime_secs: None,
                 tags: Vec::new(),
                 stop_timeout_secs: 30,
                 log_config: None,
                 description: None,
                 user: None,
+                group: None,
+                depends_on: Vec::new(),
+                replicas: 1,
+                sockets: Vec::new(),
+                lazy_start: false,
+                ready_mode: ReadyMode::Immediate,
+                start_timeout_secs: 90,
+                watchdog_secs: None,
+                heartbeat_file: None,
+                watchdog_signal: "SIGABRT".to_string(),
             }]
         }
     };
@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use syspulse_core::config::parse_config_file;
+use syspulse_core::ipc::protocol::{Request, Response};
+
+use crate::client::CliClient;
+use crate::commands::add::discover_config_file;
+use crate::commands::OutputFormat;
+use crate::output;
+
+pub async fn run(
+    socket_path: &Path,
+    file: Option<&Path>,
+    dry_run: bool,
+    prune: bool,
+    format: &OutputFormat,
+) -> Result<()> {
+    let path = match file {
+        Some(path) => path.to_path_buf(),
+        None => discover_config_file()?
+            .context("No config file found in the current directory. Use --file to choose one")?,
+    };
+    let specs = parse_config_file(&path)?;
+    let client = CliClient::new(socket_path);
+
+    let response = client
+        .send(Request::Apply {
+            specs,
+            dry_run,
+            prune,
+        })
+        .await?;
+
+    CliClient::ensure_success(&response)?;
+
+    match response {
+        Response::Plan { plan } => {
+            println!("{}", output::format_plan(&plan, dry_run, format));
+        }
+        _ => {
+            println!("Unexpected response");
+        }
+    }
+
+    Ok(())
+}
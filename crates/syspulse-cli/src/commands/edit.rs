@@ -0,0 +1,189 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use syspulse_core::config::parse_config;
+use syspulse_core::daemon::DaemonSpec;
+use syspulse_core::ipc::protocol::{Request, Response};
+use tempfile::TempPath;
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+use crate::output;
+
+#[derive(Serialize)]
+struct EditFile<'a> {
+    daemon: &'a DaemonSpec,
+}
+
+/// Open the daemon's spec in `$VISUAL`/`$EDITOR` and submit the result as
+/// an update based on the revision that was opened.
+pub async fn run(socket_path: &Path, name: &str, format: &OutputFormat) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
+    let response = client
+        .send(Request::GetSpec {
+            name: name.to_string(),
+            revision: None,
+        })
+        .await?;
+    CliClient::ensure_success(&response)?;
+    let Response::Spec { spec, revision } = response else {
+        bail!("Unexpected response");
+    };
+
+    let original = render_spec(&spec, revision)?;
+    // Created exclusively with an unpredictable name, readable only by the
+    // user, and removed when dropped unless kept.
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("syspulse-edit-{}-", name))
+        .suffix(".sys")
+        .tempfile()
+        .context("Failed to create a file to edit")?;
+    file.write_all(original.as_bytes())?;
+    let path = file.into_temp_path();
+
+    let edited = edit_file(&path)?;
+    if edited == original {
+        println!("No changes");
+        return Ok(());
+    }
+
+    let spec = match parse_edited(&edited, name) {
+        Ok(spec) => spec,
+        Err(e) => bail!(
+            "{:#}\nYour changes were kept in {}",
+            e,
+            keep(path)?.display()
+        ),
+    };
+
+    let response = client
+        .send(Request::Update {
+            spec,
+            revision: Some(revision),
+        })
+        .await?;
+    if let Err(e) = CliClient::ensure_success(&response) {
+        bail!(
+            "{:#}\nYour changes were kept in {}",
+            e,
+            keep(path)?.display()
+        );
+    }
+
+    match response {
+        Response::Updated {
+            revision,
+            fields,
+            restarted,
+        } => println!(
+            "{}",
+            output::format_update(name, revision, &fields, restarted, format)
+        ),
+        _ => println!("Unexpected response"),
+    }
+
+    Ok(())
+}
+
+/// The spec as a config file that `parse_config` reads back unchanged.
+fn render_spec(spec: &DaemonSpec, revision: u64) -> Result<String> {
+    let body = toml::to_string(&EditFile { daemon: spec })
+        .context("Failed to render daemon spec as TOML")?;
+    Ok(format!(
+        "# Daemon '{}', revision {}. Save and close the editor to apply.\n\n{}",
+        spec.name, revision, body
+    ))
+}
+
+fn parse_edited(content: &str, name: &str) -> Result<DaemonSpec> {
+    let mut specs = parse_config(content)?;
+    if specs.len() != 1 {
+        bail!("Expected exactly one [daemon], found {}", specs.len());
+    }
+    let spec = specs.remove(0);
+    if spec.name != name {
+        bail!(
+            "Renaming a daemon is not supported (name changed from '{}' to '{}')",
+            name,
+            spec.name
+        );
+    }
+    Ok(spec)
+}
+
+/// Keep the edited file past the end of the command.
+fn keep(path: TempPath) -> Result<PathBuf> {
+    path.keep().context("Failed to keep the edited file")
+}
+
+fn edit_file(path: &Path) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("The configured editor is empty")?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run editor '{}'", editor))?;
+    if !status.success() {
+        bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendered_spec_parses_back_unchanged() {
+        let spec = parse_config(
+            r#"
+[daemon]
+name = "api"
+command = ["python", "-m", "http.server"]
+working_dir = "/srv/api"
+tags = ["web"]
+schedule = "0 0 * * * *"
+depends_on = ["db", { name = "cache", condition = "healthy" }]
+
+[daemon.env]
+PORT = "8000"
+
+[daemon.health_check]
+type = "http"
+target = "http://127.0.0.1:8000/"
+interval_secs = 5
+
+[daemon.restart_policy]
+policy = "on_failure"
+max_retries = 3
+"#,
+        )
+        .unwrap()
+        .remove(0);
+
+        let rendered = render_spec(&spec, 4).unwrap();
+        assert!(rendered.starts_with("# Daemon 'api', revision 4."));
+        let parsed = parse_edited(&rendered, "api").unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&spec).unwrap()
+        );
+
+        assert!(parse_edited(&rendered, "web").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use syspulse_core::ipc::protocol::{Request, Response};
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+use crate::output;
+
+pub async fn run(
+    socket_path: &Path,
+    name: Option<&str>,
+    since: Option<&str>,
+    limit: usize,
+    format: &OutputFormat,
+) -> Result<()> {
+    let since = since.map(|s| parse_since(s, Utc::now())).transpose()?;
+    let client = CliClient::new(socket_path);
+
+    let response = client
+        .send(Request::Events {
+            name: name.map(str::to_string),
+            since,
+            limit: Some(limit),
+        })
+        .await?;
+
+    CliClient::ensure_success(&response)?;
+
+    match response {
+        Response::Events { events } => {
+            println!("{}", output::format_events(&events, format));
+        }
+        _ => {
+            println!("Unexpected response");
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `--since` as an RFC 3339 timestamp or a duration ago such as
+/// `90s`, `10m`, `2h` or `1d`.
+fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let unit_at = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_at);
+    let amount: i64 = amount
+        .parse()
+        .with_context(|| format!("Invalid --since value '{}'", value))?;
+    let ago = match unit {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => bail!(
+            "Invalid --since value '{}': expected a timestamp or a duration like 10m, 2h, 1d",
+            value
+        ),
+    };
+    Ok(now - ago)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_relative_and_absolute_since() {
+        let now = Utc::now();
+        assert_eq!(
+            parse_since("10m", now).unwrap(),
+            now - Duration::minutes(10)
+        );
+        assert_eq!(parse_since("1d", now).unwrap(), now - Duration::days(1));
+        assert_eq!(
+            parse_since("2026-01-02T03:04:05Z", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-02T03:04:05+00:00"
+        );
+        assert!(parse_since("10", now).is_err());
+        assert!(parse_since("soon", now).is_err());
+    }
+}
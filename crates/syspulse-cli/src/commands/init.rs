@@ -14,6 +14,7 @@ command = ["node", "server.js"]
 # working_dir = "/opt/my-app"
 # description = "My application server"
 # user = "www-data"
+# group = "www-data"
 
 # Environment variables
 # [daemon.env]
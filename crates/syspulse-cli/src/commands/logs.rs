@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use syspulse_core::ipc::protocol::{Request, Response};
 
 use crate::client::CliClient;
@@ -14,17 +14,18 @@ pub async fn run(
     follow: bool,
     format: &OutputFormat,
 ) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
     if follow {
-        bail!("--follow is not yet implemented in v0.1");
+        return follow_logs(&client, name, lines, stderr, format).await;
     }
 
-    let client = CliClient::new(socket_path);
-
     let response = client
         .send(Request::Logs {
             name: name.to_string(),
             lines,
             stderr,
+            follow: false,
         })
         .await?;
 
@@ -46,3 +47,44 @@ pub async fn run(
 
     Ok(())
 }
+
+/// Print log lines as the manager streams them. In JSON mode each line is
+/// emitted as its own JSON string so the output can be consumed incrementally.
+async fn follow_logs(
+    client: &CliClient,
+    name: &str,
+    lines: usize,
+    stderr: bool,
+    format: &OutputFormat,
+) -> Result<()> {
+    let mut stream = client
+        .stream(Request::Logs {
+            name: name.to_string(),
+            lines,
+            stderr,
+            follow: true,
+        })
+        .await?;
+
+    while let Some(response) = stream.next().await? {
+        CliClient::ensure_success(&response)?;
+
+        match (&response, format) {
+            (Response::Logs { lines }, OutputFormat::Table) => {
+                for line in lines {
+                    println!("{}", line);
+                }
+            }
+            (Response::Logs { lines }, OutputFormat::Json) => {
+                for line in lines {
+                    println!("{}", serde_json::to_string(line)?);
+                }
+            }
+            _ => {
+                println!("Unexpected response");
+            }
+        }
+    }
+
+    Ok(())
+}
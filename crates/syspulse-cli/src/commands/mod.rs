@@ -1,13 +1,23 @@
 pub mod add;
+pub mod apply;
 pub mod daemon_cmd;
+pub mod edit;
+pub mod events;
 pub mod init;
 pub mod list;
 pub mod logs;
 pub mod remove;
+pub mod reset_failed;
 pub mod restart;
+pub mod rollback;
+pub mod runs;
+pub mod scale;
 pub mod start;
 pub mod status;
 pub mod stop;
+pub mod top;
+pub mod trigger;
+pub mod watch;
 
 use clap::{Parser, Subcommand, ValueEnum};
 
@@ -56,12 +66,15 @@ pub enum Commands {
     Start {
         /// Daemon name
         name: String,
-        /// Wait for daemon to be running
+        /// Wait for daemon to be running and healthy
         #[arg(long)]
         wait: bool,
-        /// Timeout in seconds (with --wait)
+        /// Timeout in seconds (with --wait, default 60)
         #[arg(long)]
         timeout: Option<u64>,
+        /// Start the daemons it depends on first
+        #[arg(long)]
+        with_deps: bool,
     },
     /// Stop a daemon
     Stop {
@@ -70,7 +83,7 @@ pub enum Commands {
         /// Force kill immediately
         #[arg(long)]
         force: bool,
-        /// Timeout in seconds
+        /// Seconds to wait for graceful shutdown (overrides stop_timeout_secs)
         #[arg(long)]
         timeout: Option<u64>,
     },
@@ -81,9 +94,26 @@ pub enum Commands {
         /// Force kill before restart
         #[arg(long)]
         force: bool,
-        /// Wait for daemon to be running after restart
+        /// Wait for daemon to be running and healthy after restart
         #[arg(long)]
         wait: bool,
+        /// Timeout in seconds (with --wait or --rolling, default 60)
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Start each new process before stopping the old one, and keep the
+        /// old one if the new one doesn't become healthy
+        #[arg(long, conflicts_with = "force")]
+        rolling: bool,
+    },
+    /// Run a scheduled daemon now, as if its schedule had fired
+    Trigger {
+        /// Daemon name
+        name: String,
+    },
+    /// Clear a failed or crash-looping daemon's state and restart count
+    ResetFailed {
+        /// Daemon name, or `name:index` for one replica
+        name: String,
     },
     /// Show daemon status
     Status {
@@ -102,10 +132,45 @@ pub enum Commands {
         /// Show stderr instead of stdout
         #[arg(long)]
         stderr: bool,
-        /// Follow log output (not implemented in v0.1)
+        /// Follow log output
         #[arg(short, long)]
         follow: bool,
     },
+    /// Show live CPU and memory usage of running daemons
+    Top {
+        /// Daemon name (omit for all running daemons)
+        name: Option<String>,
+        /// Refresh interval in seconds
+        #[arg(short = 'i', long, default_value = "2")]
+        interval: u64,
+    },
+    /// Show recent lifecycle events (starts, exits, restarts, health changes)
+    Events {
+        /// Daemon name (omit for all)
+        name: Option<String>,
+        /// Only events since a time (RFC 3339) or a duration ago (e.g. 10m, 2h, 1d)
+        #[arg(long)]
+        since: Option<String>,
+        /// Number of events to show
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+    },
+    /// Show the run history of a scheduled daemon
+    Runs {
+        /// Daemon name
+        name: String,
+        /// Number of runs to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+    },
+    /// Stream lifecycle, health and resource events as they happen
+    Watch {
+        /// Daemon names (omit for all)
+        names: Vec<String>,
+        /// Only these event kinds (repeatable), e.g. exited, health, resources
+        #[arg(long = "kind")]
+        kinds: Vec<String>,
+    },
     /// Add a new daemon
     Add {
         /// Load from config file (.sys)
@@ -118,6 +183,38 @@ pub enum Commands {
         #[arg(long, num_args = 1..)]
         command: Option<Vec<String>>,
     },
+    /// Make the registered daemons match a config file
+    Apply {
+        /// Config file (.sys); discovered in the current directory if omitted
+        #[arg(short, long)]
+        file: Option<std::path::PathBuf>,
+        /// Only show what would change
+        #[arg(long)]
+        dry_run: bool,
+        /// Also remove registered daemons that are not in the file
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Edit a daemon's spec in $VISUAL/$EDITOR
+    Edit {
+        /// Daemon name
+        name: String,
+    },
+    /// Restore an earlier revision of a daemon's spec (omit --to to list revisions)
+    Rollback {
+        /// Daemon name
+        name: String,
+        /// Revision to restore
+        #[arg(long)]
+        to: Option<u64>,
+    },
+    /// Set how many replicas of a daemon run
+    Scale {
+        /// Daemon name
+        name: String,
+        /// Number of replicas
+        replicas: u32,
+    },
     /// Remove a daemon
     Remove {
         /// Daemon name
@@ -11,6 +11,8 @@ pub async fn run(
     name: &str,
     force: bool,
     wait: bool,
+    timeout: Option<u64>,
+    rolling: bool,
     format: &OutputFormat,
 ) -> Result<()> {
     let client = CliClient::new(socket_path);
@@ -20,6 +22,8 @@ pub async fn run(
             name: name.to_string(),
             force,
             wait,
+            timeout_secs: timeout,
+            rolling,
         })
         .await?;
 
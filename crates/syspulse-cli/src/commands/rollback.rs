@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use syspulse_core::ipc::protocol::{Request, Response};
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+use crate::output;
+
+/// Restore an earlier revision of a daemon's spec as a new revision, or list
+/// the revisions when `to` is omitted.
+pub async fn run(
+    socket_path: &Path,
+    name: &str,
+    to: Option<u64>,
+    format: &OutputFormat,
+) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
+    let Some(to) = to else {
+        let response = client
+            .send(Request::Revisions {
+                name: name.to_string(),
+            })
+            .await?;
+        CliClient::ensure_success(&response)?;
+        match response {
+            Response::Revisions { revisions } => {
+                println!("{}", output::format_revisions(&revisions, format))
+            }
+            _ => println!("Unexpected response"),
+        }
+        return Ok(());
+    };
+
+    let current = client
+        .send(Request::GetSpec {
+            name: name.to_string(),
+            revision: None,
+        })
+        .await?;
+    CliClient::ensure_success(&current)?;
+    let Response::Spec {
+        revision: current, ..
+    } = current
+    else {
+        bail!("Unexpected response");
+    };
+
+    let target = client
+        .send(Request::GetSpec {
+            name: name.to_string(),
+            revision: Some(to),
+        })
+        .await?;
+    CliClient::ensure_success(&target)?;
+    let Response::Spec { spec, .. } = target else {
+        bail!("Unexpected response");
+    };
+
+    let response = client
+        .send(Request::Update {
+            spec,
+            revision: Some(current),
+        })
+        .await?;
+    CliClient::ensure_success(&response)?;
+
+    match response {
+        Response::Updated {
+            revision,
+            fields,
+            restarted,
+        } => println!(
+            "{}",
+            output::format_update(name, revision, &fields, restarted, format)
+        ),
+        _ => println!("Unexpected response"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use anyhow::Result;
+use syspulse_core::ipc::protocol::{Request, Response};
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+use crate::output;
+
+pub async fn run(
+    socket_path: &Path,
+    name: &str,
+    limit: usize,
+    format: &OutputFormat,
+) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
+    let response = client
+        .send(Request::Runs {
+            name: name.to_string(),
+            limit: Some(limit),
+        })
+        .await?;
+
+    CliClient::ensure_success(&response)?;
+
+    match response {
+        Response::Runs { runs } => {
+            println!("{}", output::format_runs(&runs, format));
+        }
+        _ => {
+            println!("Unexpected response");
+        }
+    }
+
+    Ok(())
+}
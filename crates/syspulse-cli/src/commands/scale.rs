@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use anyhow::Result;
+use syspulse_core::ipc::protocol::{Request, Response};
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+
+pub async fn run(
+    socket_path: &Path,
+    name: &str,
+    replicas: u32,
+    format: &OutputFormat,
+) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
+    let response = client
+        .send(Request::Scale {
+            name: name.to_string(),
+            replicas,
+        })
+        .await?;
+
+    CliClient::ensure_success(&response)?;
+
+    match (&response, format) {
+        (Response::Updated { revision, .. }, OutputFormat::Table) => {
+            println!(
+                "Daemon '{}' scaled to {} replicas (revision {})",
+                name, replicas, revision
+            );
+        }
+        (Response::Updated { revision, .. }, OutputFormat::Json) => {
+            println!(
+                "{}",
+                serde_json::json!({ "name": name, "replicas": replicas, "revision": revision })
+            );
+        }
+        _ => {
+            println!("Unexpected response");
+        }
+    }
+
+    Ok(())
+}
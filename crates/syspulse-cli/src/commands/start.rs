@@ -11,6 +11,7 @@ pub async fn run(
     name: &str,
     wait: bool,
     timeout: Option<u64>,
+    with_deps: bool,
     format: &OutputFormat,
 ) -> Result<()> {
     let client = CliClient::new(socket_path);
@@ -20,6 +21,7 @@ pub async fn run(
             name: name.to_string(),
             wait,
             timeout_secs: timeout,
+            with_deps,
         })
         .await?;
 
@@ -0,0 +1,64 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use chrono::Local;
+use syspulse_core::ipc::protocol::{Request, Response};
+use syspulse_core::resources::DaemonStats;
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+use crate::output;
+
+pub async fn run(
+    socket_path: &Path,
+    name: Option<&str>,
+    interval: u64,
+    format: &OutputFormat,
+) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
+    // JSON output is a single snapshot including the sample history.
+    if let OutputFormat::Json = format {
+        let stats = fetch_stats(&client, name).await?;
+        println!("{}", output::format_stats(&stats, format));
+        return Ok(());
+    }
+
+    let interval = interval.max(1);
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        let stats = fetch_stats(&client, name).await?;
+
+        // Clear the screen and move the cursor home before redrawing.
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "syspulse top - {} (every {}s, Ctrl+C to quit)\n",
+            Local::now().format("%H:%M:%S"),
+            interval
+        );
+        println!("{}", output::format_stats(&stats, format));
+    }
+
+    Ok(())
+}
+
+async fn fetch_stats(client: &CliClient, name: Option<&str>) -> Result<Vec<DaemonStats>> {
+    let response = client
+        .send(Request::Stats {
+            name: name.map(str::to_string),
+        })
+        .await?;
+
+    CliClient::ensure_success(&response)?;
+
+    match response {
+        Response::Stats { stats } => Ok(stats),
+        _ => bail!("Unexpected response"),
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use anyhow::Result;
+use syspulse_core::ipc::protocol::{Request, Response};
+
+use crate::client::CliClient;
+use crate::commands::OutputFormat;
+use crate::output;
+
+/// Print events as the manager pushes them, one per line, until Ctrl+C. In
+/// JSON mode each event is emitted as its own JSON object.
+pub async fn run(
+    socket_path: &Path,
+    names: Vec<String>,
+    kinds: Vec<String>,
+    format: &OutputFormat,
+) -> Result<()> {
+    let client = CliClient::new(socket_path);
+
+    let mut stream = client.stream(Request::Subscribe { names, kinds }).await?;
+
+    loop {
+        let response = tokio::select! {
+            next = stream.next() => match next? {
+                Some(response) => response,
+                None => break,
+            },
+            _ = tokio::signal::ctrl_c() => break,
+        };
+
+        CliClient::ensure_success(&response)?;
+
+        match response {
+            Response::Event { event } => println!("{}", output::format_event(&event, format)),
+            _ => println!("Unexpected response"),
+        }
+    }
+
+    Ok(())
+}
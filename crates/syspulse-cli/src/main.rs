@@ -44,8 +44,10 @@ async fn main() -> Result<()> {
             name,
             wait,
             timeout,
+            with_deps,
         } => {
-            commands::start::run(&socket_path, &name, wait, timeout, &cli.format).await?;
+            commands::start::run(&socket_path, &name, wait, timeout, with_deps, &cli.format)
+                .await?;
         }
         Commands::Stop {
             name,
@@ -54,8 +56,23 @@ async fn main() -> Result<()> {
         } => {
             commands::stop::run(&socket_path, &name, force, timeout, &cli.format).await?;
         }
-        Commands::Restart { name, force, wait } => {
-            commands::restart::run(&socket_path, &name, force, wait, &cli.format).await?;
+        Commands::Restart {
+            name,
+            force,
+            wait,
+            timeout,
+            rolling,
+        } => {
+            commands::restart::run(
+                &socket_path,
+                &name,
+                force,
+                wait,
+                timeout,
+                rolling,
+                &cli.format,
+            )
+            .await?;
         }
         Commands::Status { name } => {
             commands::status::run(&socket_path, name.as_deref(), &cli.format).await?;
@@ -71,6 +88,25 @@ async fn main() -> Result<()> {
         } => {
             commands::logs::run(&socket_path, &name, lines, stderr, follow, &cli.format).await?;
         }
+        Commands::Top { name, interval } => {
+            commands::top::run(&socket_path, name.as_deref(), interval, &cli.format).await?;
+        }
+        Commands::Events { name, since, limit } => {
+            commands::events::run(
+                &socket_path,
+                name.as_deref(),
+                since.as_deref(),
+                limit,
+                &cli.format,
+            )
+            .await?;
+        }
+        Commands::Runs { name, limit } => {
+            commands::runs::run(&socket_path, &name, limit, &cli.format).await?;
+        }
+        Commands::Watch { names, kinds } => {
+            commands::watch::run(&socket_path, names, kinds, &cli.format).await?;
+        }
         Commands::Add {
             file,
             name,
@@ -85,6 +121,29 @@ async fn main() -> Result<()> {
             )
             .await?;
         }
+        Commands::Apply {
+            file,
+            dry_run,
+            prune,
+        } => {
+            commands::apply::run(&socket_path, file.as_deref(), dry_run, prune, &cli.format)
+                .await?;
+        }
+        Commands::Edit { name } => {
+            commands::edit::run(&socket_path, &name, &cli.format).await?;
+        }
+        Commands::Rollback { name, to } => {
+            commands::rollback::run(&socket_path, &name, to, &cli.format).await?;
+        }
+        Commands::Trigger { name } => {
+            commands::trigger::run(&socket_path, &name, &cli.format).await?;
+        }
+        Commands::ResetFailed { name } => {
+            commands::reset_failed::run(&socket_path, &name, &cli.format).await?;
+        }
+        Commands::Scale { name, replicas } => {
+            commands::scale::run(&socket_path, &name, replicas, &cli.format).await?;
+        }
         Commands::Remove { name, force } => {
             commands::remove::run(&socket_path, &name, force, &cli.format).await?;
         }
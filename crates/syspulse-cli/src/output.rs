@@ -1,8 +1,12 @@
 use chrono::Utc;
 use comfy_table::{presets::UTF8_FULL, ContentArrangement, Table};
 use owo_colors::OwoColorize;
-use syspulse_core::daemon::{DaemonInstance, HealthStatus};
+use syspulse_core::apply::ApplyPlan;
+use syspulse_core::daemon::{DaemonInstance, HealthStatus, SpecRevision};
+use syspulse_core::events::{DaemonEvent, EventKind};
 use syspulse_core::lifecycle::LifecycleState;
+use syspulse_core::resources::DaemonStats;
+use syspulse_core::runs::{DaemonRun, RunStatus};
 
 use crate::commands::OutputFormat;
 
@@ -44,7 +48,7 @@ fn format_table(instances: &[DaemonInstance]) -> String {
         let uptime = format_uptime(inst);
         let health = format_health(&inst.health_status);
         table.add_row(vec![
-            &inst.spec_name,
+            &inst.name(),
             &state_str,
             &pid,
             &uptime,
@@ -55,6 +59,357 @@ fn format_table(instances: &[DaemonInstance]) -> String {
     table.to_string()
 }
 
+pub fn format_stats(stats: &[DaemonStats], format: &OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(stats).unwrap_or_default(),
+        OutputFormat::Table => format_stats_table(stats),
+    }
+}
+
+fn format_stats_table(stats: &[DaemonStats]) -> String {
+    if stats.is_empty() {
+        return "No running daemons.".to_string();
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Name",
+            "PID",
+            "CPU %",
+            "Avg CPU %",
+            "Memory",
+            "Peak Memory",
+            "Procs",
+        ]);
+
+    for daemon in stats {
+        let pid = daemon
+            .pid
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "-".into());
+        let row = match daemon.latest() {
+            Some(latest) => {
+                let avg_cpu = daemon.samples.iter().map(|s| s.cpu_percent).sum::<f64>()
+                    / daemon.samples.len() as f64;
+                let peak_memory = daemon
+                    .samples
+                    .iter()
+                    .map(|s| s.memory_bytes)
+                    .max()
+                    .unwrap_or(0);
+                vec![
+                    daemon.name.clone(),
+                    pid,
+                    format!("{:.1}", latest.cpu_percent),
+                    format!("{:.1}", avg_cpu),
+                    format_bytes(latest.memory_bytes),
+                    format_bytes(peak_memory),
+                    latest.process_count.to_string(),
+                ]
+            }
+            None => {
+                let mut row = vec![daemon.name.clone(), pid];
+                row.extend(std::iter::repeat_n("-".to_string(), 5));
+                row
+            }
+        };
+        table.add_row(row);
+    }
+    table.to_string()
+}
+
+pub fn format_events(events: &[DaemonEvent], format: &OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(events).unwrap_or_default(),
+        OutputFormat::Table => format_events_table(events),
+    }
+}
+
+fn format_events_table(events: &[DaemonEvent]) -> String {
+    if events.is_empty() {
+        return "No events recorded.".to_string();
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Time", "Daemon", "Event", "Details"]);
+
+    for event in events {
+        table.add_row(vec![
+            format_event_time(event),
+            event.name.clone(),
+            event.kind.name().to_string(),
+            format_event_details(event),
+        ]);
+    }
+    table.to_string()
+}
+
+pub fn format_runs(runs: &[DaemonRun], format: &OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(runs).unwrap_or_default(),
+        OutputFormat::Table => format_runs_table(runs),
+    }
+}
+
+fn format_runs_table(runs: &[DaemonRun]) -> String {
+    if runs.is_empty() {
+        return "No runs recorded.".to_string();
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Started", "Daemon", "Trigger", "Status", "Duration", "Exit",
+        ]);
+
+    for run in runs {
+        let exit = match (run.exit_code, run.exit_signal) {
+            (_, Some(signal)) => format!("signal {}", signal),
+            (Some(code), None) => code.to_string(),
+            (None, None) => "-".to_string(),
+        };
+        let duration = if run.status == RunStatus::Skipped {
+            "-".to_string()
+        } else {
+            format_duration(run.duration().num_seconds())
+        };
+        table.add_row(vec![
+            run.started_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            run.name.clone(),
+            run.trigger.name().to_string(),
+            colorize_run_status(run.status),
+            duration,
+            exit,
+        ]);
+    }
+    table.to_string()
+}
+
+fn colorize_run_status(status: RunStatus) -> String {
+    let label = status.name();
+    if !colors_enabled() {
+        return label.to_string();
+    }
+    match status {
+        RunStatus::Succeeded => label.green().to_string(),
+        RunStatus::Failed | RunStatus::TimedOut => label.red().to_string(),
+        RunStatus::Running => label.yellow().to_string(),
+        RunStatus::Stopped | RunStatus::Replaced | RunStatus::Skipped => label.dimmed().to_string(),
+    }
+}
+
+/// A single event as one line, for streaming output.
+pub fn format_event(event: &DaemonEvent, format: &OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string(event).unwrap_or_default(),
+        OutputFormat::Table => format!(
+            "{}  {}  {}  {}",
+            format_event_time(event),
+            event.name,
+            event.kind.name(),
+            format_event_details(event)
+        )
+        .trim_end()
+        .to_string(),
+    }
+}
+
+pub fn format_update(
+    name: &str,
+    revision: u64,
+    fields: &[String],
+    restarted: bool,
+    format: &OutputFormat,
+) -> String {
+    match format {
+        OutputFormat::Json => serde_json::json!({
+            "name": name,
+            "revision": revision,
+            "fields": fields,
+            "restarted": restarted,
+        })
+        .to_string(),
+        OutputFormat::Table if fields.is_empty() => {
+            format!("No changes to '{}' (revision {})", name, revision)
+        }
+        OutputFormat::Table => {
+            let restarted = if restarted { ", restarted" } else { "" };
+            format!(
+                "Daemon '{}' updated to revision {} ({}{})",
+                name,
+                revision,
+                fields.join(", "),
+                restarted
+            )
+        }
+    }
+}
+
+pub fn format_revisions(revisions: &[SpecRevision], format: &OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(revisions).unwrap_or_default(),
+        OutputFormat::Table => format_revisions_table(revisions),
+    }
+}
+
+fn format_revisions_table(revisions: &[SpecRevision]) -> String {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Revision", "Created", "Command"]);
+
+    for (i, revision) in revisions.iter().enumerate() {
+        let label = if i == 0 {
+            format!("{} (current)", revision.revision)
+        } else {
+            revision.revision.to_string()
+        };
+        table.add_row(vec![
+            label,
+            revision
+                .created_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            revision.spec.command.join(" "),
+        ]);
+    }
+    table.to_string()
+}
+
+pub fn format_plan(plan: &ApplyPlan, dry_run: bool, format: &OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(plan).unwrap_or_default(),
+        OutputFormat::Table => format_plan_lines(plan, dry_run),
+    }
+}
+
+fn format_plan_lines(plan: &ApplyPlan, dry_run: bool) -> String {
+    let colors = colors_enabled();
+    let mark = |symbol: &str, color: fn(&str) -> String| {
+        if colors {
+            color(symbol)
+        } else {
+            symbol.to_string()
+        }
+    };
+
+    let mut lines = Vec::new();
+    for name in &plan.added {
+        lines.push(format!("{} {}", mark("+", |s| s.green().to_string()), name));
+    }
+    for change in &plan.changed {
+        let restart = if change.restart { " (restart)" } else { "" };
+        lines.push(format!(
+            "{} {}: {}{}",
+            mark("~", |s| s.yellow().to_string()),
+            change.name,
+            change.fields.join(", "),
+            restart
+        ));
+    }
+    for name in &plan.removed {
+        lines.push(format!("{} {}", mark("-", |s| s.red().to_string()), name));
+    }
+    for name in &plan.not_in_file {
+        lines.push(format!(
+            "{} {}: not in file, kept (use --prune to remove)",
+            mark("?", |s| s.dimmed().to_string()),
+            name
+        ));
+    }
+
+    let mut summary = format!(
+        "{} added, {} changed, {} removed, {} unchanged",
+        plan.added.len(),
+        plan.changed.len(),
+        plan.removed.len(),
+        plan.unchanged.len()
+    );
+    if !plan.not_in_file.is_empty() {
+        summary.push_str(&format!(", {} not in file", plan.not_in_file.len()));
+    }
+    if plan.is_empty() {
+        lines.push(format!("No changes ({} unchanged)", plan.unchanged.len()));
+    } else if dry_run {
+        lines.push(format!("Dry run: {}", summary));
+    } else {
+        lines.push(summary);
+    }
+    lines.join("\n")
+}
+
+fn format_event_time(event: &DaemonEvent) -> String {
+    event
+        .timestamp
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+fn format_event_details(event: &DaemonEvent) -> String {
+    let mut details = match &event.kind {
+        EventKind::Started { pid } => format!("pid {}", pid),
+        EventKind::Stopped { exit_code, signal } | EventKind::Exited { exit_code, signal } => {
+            match (exit_code, signal) {
+                (_, Some(signal)) => format!("signal {}", signal),
+                (Some(code), None) => format!("exit code {}", code),
+                (None, None) => String::new(),
+            }
+        }
+        EventKind::Restarting {
+            attempt,
+            backoff_ms,
+        } => format!("attempt {} in {}ms", attempt, backoff_ms),
+        EventKind::Health { status } => format_health(status),
+        EventKind::CronTriggered => String::new(),
+        EventKind::Resources {
+            cpu_percent,
+            memory_bytes,
+            process_count,
+        } => format!(
+            "cpu {:.1}%, memory {}, {} procs",
+            cpu_percent,
+            format_bytes(*memory_bytes),
+            process_count
+        ),
+    };
+    if let Some(message) = &event.message {
+        if !details.is_empty() {
+            details.push_str(": ");
+        }
+        details.push_str(message);
+    }
+    details
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
 fn colorize_state(state: &LifecycleState) -> String {
     let label = state.to_string();
     if !colors_enabled() {
@@ -63,7 +418,7 @@ fn colorize_state(state: &LifecycleState) -> String {
     match state {
         LifecycleState::Running => label.green().to_string(),
         LifecycleState::Stopped => label.dimmed().to_string(),
-        LifecycleState::Failed => label.red().to_string(),
+        LifecycleState::Failed | LifecycleState::CrashLooping => label.red().to_string(),
         LifecycleState::Starting => label.yellow().to_string(),
         LifecycleState::Stopping => label.yellow().to_string(),
         LifecycleState::Scheduled => label.cyan().to_string(),
@@ -99,7 +454,10 @@ fn format_uptime(instance: &DaemonInstance) -> String {
     }
 
     let duration = Utc::now().signed_duration_since(started);
-    let total_secs = duration.num_seconds();
+    format_duration(duration.num_seconds())
+}
+
+fn format_duration(total_secs: i64) -> String {
     if total_secs < 0 {
         return "-".to_string();
     }
@@ -124,9 +482,9 @@ fn format_instance_detail(instance: &DaemonInstance) -> String {
     let mut lines = Vec::new();
 
     let name_display = if colors_enabled() {
-        instance.spec_name.bold().to_string()
+        instance.name().bold().to_string()
     } else {
-        instance.spec_name.clone()
+        instance.name()
     };
 
     lines.push(format!("Name:       {}", name_display));
@@ -152,15 +510,30 @@ fn format_instance_detail(instance: &DaemonInstance) -> String {
     if let Some(ref t) = instance.stopped_at {
         lines.push(format!("Stopped:    {}", t.format("%Y-%m-%d %H:%M:%S UTC")));
     }
+    if let Some(ref t) = instance.next_run_at {
+        lines.push(format!("Next Run:   {}", t.format("%Y-%m-%d %H:%M:%S UTC")));
+    }
     if let Some(code) = instance.exit_code {
         lines.push(format!("Exit Code:  {}", code));
     }
+    if let Some(signal) = instance.exit_signal {
+        lines.push(format!("Signal:     {}", signal));
+    }
     if let Some(ref p) = instance.stdout_log {
         lines.push(format!("Stdout Log: {}", p.display()));
     }
     if let Some(ref p) = instance.stderr_log {
         lines.push(format!("Stderr Log: {}", p.display()));
     }
+    if let Some(ref message) = instance.status_message {
+        lines.push(format!("Message:    {}", message));
+    }
+    if let Some(ref reason) = instance.reason {
+        lines.push(format!("Reason:     {}", reason));
+    }
+    for warning in &instance.warnings {
+        lines.push(format!("Warning:    {}", warning.yellow()));
+    }
 
     lines.join("\n")
 }
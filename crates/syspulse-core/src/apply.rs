@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::daemon::DaemonSpec;
+use crate::error::Result;
+
+/// Spec fields that only take effect when the process is started again.
+pub const RESTART_FIELDS: &[&str] = &[
+    "command",
+    "working_dir",
+    "env",
+    "resource_limits",
+    "user",
+    "group",
+    "sockets",
+    "ready_mode",
+    "watchdog_secs",
+    "heartbeat_file",
+    "watchdog_signal",
+];
+
+/// What applying a set of specs changes in the registry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApplyPlan {
+    pub added: Vec<String>,
+    pub changed: Vec<SpecChange>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// Registered daemons missing from the applied specs that are kept,
+    /// because the apply doesn't prune.
+    #[serde(default)]
+    pub not_in_file: Vec<String>,
+}
+
+/// A registered daemon whose spec differs from the applied one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecChange {
+    pub name: String,
+    /// Top-level spec fields that differ, sorted.
+    pub fields: Vec<String>,
+    /// Whether the daemon has to be restarted for the change to take effect.
+    pub restart: bool,
+}
+
+impl ApplyPlan {
+    /// Diff `desired` against the `current` registered specs. Daemons missing
+    /// from `desired` are planned for removal with `prune`, and otherwise
+    /// only reported.
+    pub fn new(current: &[DaemonSpec], desired: &[DaemonSpec], prune: bool) -> Result<Self> {
+        let mut plan = ApplyPlan::default();
+
+        for spec in desired {
+            match current.iter().find(|c| c.name == spec.name) {
+                None => plan.added.push(spec.name.clone()),
+                Some(old) => {
+                    let change = SpecChange::between(old, spec)?;
+                    if change.is_empty() {
+                        plan.unchanged.push(spec.name.clone());
+                    } else {
+                        plan.changed.push(change);
+                    }
+                }
+            }
+        }
+
+        let missing = current
+            .iter()
+            .filter(|c| !desired.iter().any(|d| d.name == c.name))
+            .map(|c| c.name.clone())
+            .collect();
+        if prune {
+            plan.removed = missing;
+        } else {
+            plan.not_in_file = missing;
+        }
+
+        Ok(plan)
+    }
+
+    /// Whether applying would change nothing.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl SpecChange {
+    /// The difference between two specs of the same daemon.
+    pub fn between(old: &DaemonSpec, new: &DaemonSpec) -> Result<Self> {
+        let fields = changed_fields(old, new)?;
+        let restart = fields.iter().any(|f| RESTART_FIELDS.contains(&f.as_str()));
+        Ok(SpecChange {
+            name: new.name.clone(),
+            fields,
+            restart,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// Compare specs field by field through their serialized form, so that
+/// defaults spelled out in one and omitted in the other compare equal.
+fn changed_fields(old: &DaemonSpec, new: &DaemonSpec) -> Result<Vec<String>> {
+    let old: Map<String, Value> = serde_json::from_value(serde_json::to_value(old)?)?;
+    let new: Map<String, Value> = serde_json::from_value(serde_json::to_value(new)?)?;
+
+    let mut fields: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|key| old.get(*key) != new.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(toml: &str) -> DaemonSpec {
+        crate::config::parse_config(toml).unwrap().remove(0)
+    }
+
+    #[test]
+    fn classifies_added_changed_removed_and_unchanged() {
+        let current = vec![
+            spec("[daemon]\nname = 'api'\ncommand = ['api']\n"),
+            spec("[daemon]\nname = 'old'\ncommand = ['old']\n"),
+            spec("[daemon]\nname = 'same'\ncommand = ['same']\n"),
+            spec("[daemon]\nname = 'worker'\ncommand = ['worker']\n"),
+        ];
+        let desired = vec![
+            spec("[daemon]\nname = 'api'\ncommand = ['api', '--v2']\n"),
+            spec("[daemon]\nname = 'new'\ncommand = ['new']\n"),
+            spec("[daemon]\nname = 'same'\ncommand = ['same']\nstop_timeout_secs = 30\n"),
+            spec("[daemon]\nname = 'worker'\ncommand = ['worker']\ntags = ['batch']\n"),
+        ];
+
+        let plan = ApplyPlan::new(&current, &desired, true).unwrap();
+        assert_eq!(plan.added, vec!["new"]);
+        assert_eq!(plan.removed, vec!["old"]);
+        assert!(plan.not_in_file.is_empty());
+        assert_eq!(plan.unchanged, vec!["same"]);
+        assert_eq!(
+            plan.changed,
+            vec![
+                SpecChange {
+                    name: "api".to_string(),
+                    fields: vec!["command".to_string()],
+                    restart: true,
+                },
+                SpecChange {
+                    name: "worker".to_string(),
+                    fields: vec!["tags".to_string()],
+                    restart: false,
+                },
+            ]
+        );
+        assert!(!plan.is_empty());
+
+        let kept = ApplyPlan::new(&current, &desired, false).unwrap();
+        assert!(kept.removed.is_empty());
+        assert_eq!(kept.not_in_file, vec!["old"]);
+        assert_eq!(kept.changed, plan.changed);
+    }
+
+    #[test]
+    fn readiness_and_watchdog_changes_need_a_restart() {
+        let old = spec("[daemon]\nname = 'api'\ncommand = ['api']\n");
+        let new = spec("[daemon]\nname = 'api'\ncommand = ['api']\nwatchdog_secs = 10\n");
+        let change = SpecChange::between(&old, &new).unwrap();
+        assert_eq!(change.fields, vec!["watchdog_secs"]);
+        assert!(change.restart);
+
+        let signal = spec(
+            "[daemon]\nname = 'api'\ncommand = ['api']\nwatchdog_secs = 10\n\
+             watchdog_signal = 'SIGKILL'\n",
+        );
+        assert!(SpecChange::between(&new, &signal).unwrap().restart);
+
+        let notify = spec("[daemon]\nname = 'api'\ncommand = ['api']\nready_mode = 'notify'\n");
+        assert!(SpecChange::between(&old, &notify).unwrap().restart);
+    }
+
+    #[test]
+    fn identical_specs_plan_nothing() {
+        let specs = vec![spec(
+            "[daemon]\nname = 'api'\ncommand = ['api']\n[daemon.env]\nA = '1'\nB = '2'\n",
+        )];
+        let plan = ApplyPlan::new(&specs, &specs.clone(), true).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.unchanged, vec!["api"]);
+    }
+}
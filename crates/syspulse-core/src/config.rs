@@ -1,6 +1,7 @@
 use serde::Deserialize;
 
 use crate::daemon::DaemonSpec;
+use crate::dependencies::DependencyGraph;
 use crate::error::{Result, SyspulseError};
 
 #[derive(Debug, Deserialize)]
@@ -13,10 +14,15 @@ enum ConfigFile {
 pub fn parse_config(content: &str) -> Result<Vec<DaemonSpec>> {
     let config: ConfigFile =
         toml::from_str(content).map_err(|e| SyspulseError::Config(e.to_string()))?;
-    match config {
-        ConfigFile::Single { daemon } => Ok(vec![daemon]),
-        ConfigFile::Multi { daemon } => Ok(daemon),
+    let specs = match config {
+        ConfigFile::Single { daemon } => vec![daemon],
+        ConfigFile::Multi { daemon } => daemon,
+    };
+    for spec in &specs {
+        spec.validate()?;
     }
+    DependencyGraph::new(&specs).check_cycles()?;
+    Ok(specs)
 }
 
 pub fn parse_config_file(path: &std::path::Path) -> Result<Vec<DaemonSpec>> {
@@ -27,6 +33,8 @@ pub fn parse_config_file(path: &std::path::Path) -> Result<Vec<DaemonSpec>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::daemon::{CatchUp, DependencyCondition, UnhealthyAction};
+    use std::path::PathBuf;
 
     #[test]
     fn parse_single_daemon_config() {
@@ -68,8 +76,9 @@ working_dir = "/opt/app"
 description = "A full daemon example"
 stop_timeout_secs = 60
 tags = ["web", "production"]
-schedule = "0 0 * * *"
+schedule = "0 0 0 * * *"
 user = "www-data"
+group = "www-data"
 
 [daemon.env]
 NODE_ENV = "production"
@@ -82,6 +91,7 @@ interval_secs = 15
 timeout_secs = 3
 retries = 5
 start_period_secs = 10
+on_unhealthy = "restart"
 
 [daemon.restart_policy]
 policy = "on_failure"
@@ -105,8 +115,10 @@ compress_rotated = true
         assert_eq!(spec.command, vec!["node", "server.js"]);
         assert_eq!(spec.stop_timeout_secs, 60);
         assert_eq!(spec.tags, vec!["web", "production"]);
+        assert_eq!(spec.schedule.as_deref(), Some("0 0 0 * * *"));
         assert_eq!(spec.description.as_deref(), Some("A full daemon example"));
         assert_eq!(spec.user.as_deref(), Some("www-data"));
+        assert_eq!(spec.group.as_deref(), Some("www-data"));
         assert_eq!(
             spec.env.get("NODE_ENV").map(String::as_str),
             Some("production")
@@ -116,6 +128,7 @@ compress_rotated = true
         assert_eq!(hc.target, "http://localhost:3000/health");
         assert_eq!(hc.interval_secs, 15);
         assert_eq!(hc.retries, 5);
+        assert_eq!(hc.on_unhealthy, UnhealthyAction::Restart);
 
         let rl = spec.resource_limits.as_ref().unwrap();
         assert_eq!(rl.max_memory_bytes, Some(536870912));
@@ -146,6 +159,185 @@ command = ["echo", "hello"]
         assert!(spec.working_dir.is_none());
         assert!(spec.description.is_none());
         assert!(spec.user.is_none());
+        assert!(spec.group.is_none());
+        assert!(spec.depends_on.is_empty());
+        assert_eq!(spec.replicas, 1);
+        assert_eq!(spec.ready_mode, crate::daemon::ReadyMode::Immediate);
+        assert_eq!(spec.start_timeout_secs, 90);
+    }
+
+    #[test]
+    fn parse_replicas() {
+        let toml = r#"
+[daemon]
+name = "worker"
+command = ["worker"]
+replicas = 4
+"#;
+        assert_eq!(parse_config(toml).unwrap()[0].replicas, 4);
+
+        let zero = toml.replace("replicas = 4", "replicas = 0");
+        assert!(parse_config(&zero).is_err());
+        let colon = toml.replace("name = \"worker\"", "name = \"worker:1\"");
+        let err = parse_config(&colon).unwrap_err();
+        assert!(err.to_string().contains("must not contain"), "{}", err);
+    }
+
+    #[test]
+    fn parse_sockets() {
+        let toml = r#"
+[daemon]
+name = "web"
+command = ["web"]
+lazy_start = true
+
+[[daemon.sockets]]
+listen = "127.0.0.1:8080"
+
+[[daemon.sockets]]
+name = "admin"
+listen = "/run/web-admin.sock"
+"#;
+        let spec = &parse_config(toml).unwrap()[0];
+        assert!(spec.lazy_start);
+        assert_eq!(spec.sockets.len(), 2);
+        assert_eq!(spec.sockets[0].name, None);
+        assert_eq!(spec.sockets[1].name.as_deref(), Some("admin"));
+
+        let without = toml.split("[[daemon.sockets]]").next().unwrap();
+        let err = parse_config(without).unwrap_err();
+        assert!(err.to_string().contains("no sockets"), "{}", err);
+    }
+
+    #[test]
+    fn parse_ready_mode() {
+        let toml = r#"
+[daemon]
+name = "web"
+command = ["web"]
+ready_mode = "notify"
+start_timeout_secs = 20
+"#;
+        let spec = &parse_config(toml).unwrap()[0];
+        assert_eq!(spec.ready_mode, crate::daemon::ReadyMode::Notify);
+        assert_eq!(spec.start_timeout_secs, 20);
+
+        let health = toml.replace("\"notify\"", "\"health\"");
+        let err = parse_config(&health).unwrap_err();
+        assert!(err.to_string().contains("no health_check"), "{}", err);
+    }
+
+    #[test]
+    fn parse_watchdog() {
+        let toml = r#"
+[daemon]
+name = "worker"
+command = ["worker"]
+watchdog_secs = 10
+heartbeat_file = "/run/worker.alive"
+"#;
+        let spec = &parse_config(toml).unwrap()[0];
+        assert_eq!(spec.watchdog_secs, Some(10));
+        assert_eq!(spec.watchdog_signal, "SIGABRT");
+
+        let no_watchdog = toml.replace("watchdog_secs = 10", "");
+        let err = parse_config(&no_watchdog).unwrap_err();
+        assert!(err.to_string().contains("no watchdog_secs"), "{}", err);
+
+        let too_long = toml.replace("watchdog_secs = 10", "watchdog_secs = 100000000000000");
+        let err = parse_config(&too_long).unwrap_err();
+        assert!(err.to_string().contains("watchdog_secs above"), "{}", err);
+
+        let relative = toml.replace("/run/worker.alive", "worker.alive");
+        let err = parse_config(&relative).unwrap_err();
+        assert!(
+            err.to_string().contains("absolute heartbeat_file"),
+            "{}",
+            err
+        );
+        let in_working_dir = format!("{}working_dir = \"/srv/worker\"\n", relative);
+        let spec = &parse_config(&in_working_dir).unwrap()[0];
+        assert_eq!(
+            spec.heartbeat_path(),
+            Some(PathBuf::from("/srv/worker/worker.alive"))
+        );
+        #[cfg(unix)]
+        {
+            let bad_signal = format!("{}watchdog_signal = \"SIGNOPE\"\n", toml);
+            let err = parse_config(&bad_signal).unwrap_err();
+            assert!(
+                err.to_string().contains("unknown watchdog_signal"),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn parse_schedule_options() {
+        let toml = r#"
+[daemon]
+name = "poll"
+command = ["poll"]
+every = "15m"
+jitter = "30s"
+catch_up = "once"
+"#;
+        let spec = &parse_config(toml).unwrap()[0];
+        assert!(spec.is_scheduled());
+        assert_eq!(spec.every.as_deref(), Some("15m"));
+        assert_eq!(spec.catch_up, CatchUp::Once);
+
+        for (bad, message) in [
+            ("schedule = \"0 0 0 * * *\"", "both a schedule and every"),
+            ("schedule_timezone = \"UTC\"", "no schedule"),
+        ] {
+            let err = parse_config(&format!("{}{}\n", toml, bad)).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+        let err = parse_config(&toml.replace("15m", "0s")).unwrap_err();
+        assert!(err.to_string().contains("every above 0"), "{}", err);
+        let unscheduled = toml.replace("every = \"15m\"", "");
+        let err = parse_config(&unscheduled).unwrap_err();
+        assert!(err.to_string().contains("no schedule or every"), "{}", err);
+    }
+
+    #[test]
+    fn parse_depends_on() {
+        let toml = r#"
+[[daemon]]
+name = "worker"
+command = ["worker"]
+depends_on = ["api", { name = "db", condition = "healthy" }]
+
+[[daemon]]
+name = "api"
+command = ["api"]
+"#;
+        let specs = parse_config(toml).unwrap();
+        let deps = &specs[0].depends_on;
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[0].name, "api");
+        assert_eq!(deps[0].condition, DependencyCondition::Started);
+        assert_eq!(deps[1].name, "db");
+        assert_eq!(deps[1].condition, DependencyCondition::Healthy);
+    }
+
+    #[test]
+    fn parse_dependency_cycle_returns_error() {
+        let toml = r#"
+[[daemon]]
+name = "a"
+command = ["a"]
+depends_on = ["b"]
+
+[[daemon]]
+name = "b"
+command = ["b"]
+depends_on = ["a"]
+"#;
+        let err = parse_config(toml).unwrap_err();
+        assert!(err.to_string().contains("a -> b -> a"), "{}", err);
     }
 
     #[test]
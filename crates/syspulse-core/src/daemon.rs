@@ -4,9 +4,12 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use crate::error::{Result, SyspulseError};
 use crate::lifecycle::LifecycleState;
 use crate::resources::ResourceLimits;
 use crate::restart::RestartPolicy;
+use crate::scheduler::{self, JobSchedule};
+use crate::sockets::SocketSpec;
 
 fn default_interval() -> u64 {
     30
@@ -32,6 +35,46 @@ fn default_stop_timeout() -> u64 {
     30
 }
 
+fn default_replicas() -> u32 {
+    1
+}
+
+fn default_start_timeout() -> u64 {
+    90
+}
+
+fn default_watchdog_signal() -> String {
+    "SIGABRT".to_string()
+}
+
+/// Separates a daemon's name from a replica index in instance names.
+pub const REPLICA_SEPARATOR: char = ':';
+
+/// Longest `watchdog_secs` accepted: a day.
+pub const MAX_WATCHDOG_SECS: u64 = 24 * 60 * 60;
+
+/// The name of one replica of a daemon: the daemon's own name for replica 0,
+/// `name:index` for the others.
+pub fn replica_name(name: &str, replica: u32) -> String {
+    if replica == 0 {
+        name.to_string()
+    } else {
+        format!("{}{}{}", name, REPLICA_SEPARATOR, replica)
+    }
+}
+
+/// Split an instance name into the daemon's name and the replica index, and
+/// whether the index was given explicitly (`name:0` addresses replica 0 only).
+pub fn parse_replica_name(name: &str) -> (&str, u32, bool) {
+    match name.rsplit_once(REPLICA_SEPARATOR) {
+        Some((daemon, index)) if !daemon.is_empty() => match index.parse() {
+            Ok(replica) => (daemon, replica, true),
+            Err(_) => (name, 0, false),
+        },
+        _ => (name, 0, false),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthCheckSpec {
     #[serde(rename = "type")]
@@ -45,6 +88,64 @@ pub struct HealthCheckSpec {
     pub retries: u32,
     #[serde(default)]
     pub start_period_secs: u64,
+    /// What to do once the daemon is marked unhealthy.
+    #[serde(default)]
+    pub on_unhealthy: UnhealthyAction,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnhealthyAction {
+    /// Only report the daemon as unhealthy.
+    #[default]
+    None,
+    /// Stop the daemon as a failure and let its restart policy decide.
+    Restart,
+    /// Stop the daemon.
+    Stop,
+}
+
+/// When a started daemon counts as ready, moving from `Starting` to
+/// `Running`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadyMode {
+    /// As soon as the process is spawned.
+    #[default]
+    Immediate,
+    /// When the process sends `READY=1` to its `NOTIFY_SOCKET`.
+    Notify,
+    /// When the health check first reports healthy.
+    Health,
+}
+
+/// What a scheduled daemon does when its schedule fires while the previous
+/// run is still going.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleOverlap {
+    /// Let the previous run finish and drop this one.
+    #[default]
+    Skip,
+    /// Start this run once the previous one has finished. At most one run
+    /// waits at a time.
+    Queue,
+    /// Stop the previous run and start this one.
+    Replace,
+}
+
+/// What a scheduled daemon does on manager startup about runs its schedule
+/// missed while the manager was down.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchUp {
+    /// Skip them and wait for the next one.
+    #[default]
+    None,
+    /// Run once if any were missed.
+    Once,
+    /// Run once for each missed one, one after another.
+    All,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +166,50 @@ pub struct LogConfig {
     pub compress_rotated: bool,
 }
 
+/// Another daemon that must be started first.
+///
+/// Written either as a plain name (`"db"`) or as a table
+/// (`{ name = "db", condition = "healthy" }`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "DependencyDef")]
+pub struct Dependency {
+    pub name: String,
+    pub condition: DependencyCondition,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyCondition {
+    /// The dependency only has to be running.
+    #[default]
+    Started,
+    /// The dependency's health check has to report healthy.
+    Healthy,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DependencyDef {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default)]
+        condition: DependencyCondition,
+    },
+}
+
+impl From<DependencyDef> for Dependency {
+    fn from(def: DependencyDef) -> Self {
+        match def {
+            DependencyDef::Name(name) => Dependency {
+                name,
+                condition: DependencyCondition::Started,
+            },
+            DependencyDef::Full { name, condition } => Dependency { name, condition },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonSpec {
     pub name: String,
@@ -79,8 +224,29 @@ pub struct DaemonSpec {
     pub restart_policy: RestartPolicy,
     #[serde(default)]
     pub resource_limits: Option<ResourceLimits>,
+    /// Cron expression (with seconds) the daemon is started on.
     #[serde(default)]
     pub schedule: Option<String>,
+    /// IANA time zone `schedule` is evaluated in, e.g. `Europe/Berlin`.
+    /// UTC when unset.
+    #[serde(default)]
+    pub schedule_timezone: Option<String>,
+    /// Start the daemon at this interval (e.g. `15m`) instead of on a
+    /// cron `schedule`.
+    #[serde(default)]
+    pub every: Option<String>,
+    /// Delay each scheduled start by a random time up to this long.
+    #[serde(default)]
+    pub jitter: Option<String>,
+    /// What to do on startup about runs missed while the manager was down.
+    #[serde(default)]
+    pub catch_up: CatchUp,
+    /// What to do when the schedule fires during a run.
+    #[serde(default)]
+    pub schedule_overlap: ScheduleOverlap,
+    /// Seconds a run may take before it is killed as failed.
+    #[serde(default)]
+    pub max_runtime_secs: Option<u64>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default = "default_stop_timeout")]
@@ -91,21 +257,207 @@ pub struct DaemonSpec {
     pub description: Option<String>,
     #[serde(default)]
     pub user: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub depends_on: Vec<Dependency>,
+    /// Number of identical processes to run. Each gets its index in
+    /// `SYSPULSE_INSTANCE`.
+    #[serde(default = "default_replicas")]
+    pub replicas: u32,
+    /// Sockets the manager listens on and passes to the daemon on spawn.
+    #[serde(default)]
+    pub sockets: Vec<SocketSpec>,
+    /// Start the daemon on the first connection to one of its `sockets`
+    /// instead of when asked to.
+    #[serde(default)]
+    pub lazy_start: bool,
+    /// When the daemon counts as started.
+    #[serde(default)]
+    pub ready_mode: ReadyMode,
+    /// Seconds a daemon may take to become ready before it is stopped as
+    /// failed. Not used with `ready_mode = "immediate"`.
+    #[serde(default = "default_start_timeout")]
+    pub start_timeout_secs: u64,
+    /// Seconds the running daemon may go without a keepalive (`WATCHDOG=1`
+    /// or touching `heartbeat_file`) before it is killed as failed.
+    #[serde(default)]
+    pub watchdog_secs: Option<u64>,
+    /// File whose modification counts as a keepalive for `watchdog_secs`.
+    /// A relative path is relative to `working_dir`.
+    #[serde(default)]
+    pub heartbeat_file: Option<PathBuf>,
+    /// Signal sent to a daemon that missed its watchdog.
+    #[serde(default = "default_watchdog_signal")]
+    pub watchdog_signal: String,
+}
+
+impl DaemonSpec {
+    /// Whether the daemon is started on a `schedule` or `every` interval.
+    pub fn is_scheduled(&self) -> bool {
+        self.schedule.is_some() || self.every.is_some()
+    }
+
+    /// Where the daemon's `heartbeat_file` is, with a relative path resolved
+    /// against `working_dir` like the daemon itself would.
+    pub fn heartbeat_path(&self) -> Option<PathBuf> {
+        let file = self.heartbeat_file.as_ref()?;
+        match self.working_dir {
+            Some(ref dir) if file.is_relative() => Some(dir.join(file)),
+            _ => Some(file.clone()),
+        }
+    }
+
+    /// Reject specs the manager cannot run.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.contains(REPLICA_SEPARATOR) {
+            return Err(SyspulseError::Config(format!(
+                "Daemon name '{}' must not contain '{}'",
+                self.name, REPLICA_SEPARATOR
+            )));
+        }
+        if self.replicas == 0 {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' needs at least one replica",
+                self.name
+            )));
+        }
+        if self.lazy_start && self.sockets.is_empty() {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has lazy_start but no sockets",
+                self.name
+            )));
+        }
+        if self.watchdog_secs == Some(0) {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' needs a watchdog_secs above 0",
+                self.name
+            )));
+        }
+        if self
+            .watchdog_secs
+            .is_some_and(|secs| secs > MAX_WATCHDOG_SECS)
+        {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has a watchdog_secs above {}",
+                self.name, MAX_WATCHDOG_SECS
+            )));
+        }
+        if self.max_runtime_secs == Some(0) {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' needs a max_runtime_secs above 0",
+                self.name
+            )));
+        }
+        if self.heartbeat_file.is_some() && self.watchdog_secs.is_none() {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has a heartbeat_file but no watchdog_secs",
+                self.name
+            )));
+        }
+        if self.heartbeat_path().is_some_and(|path| path.is_relative()) {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' needs an absolute heartbeat_file, or a working_dir to resolve it against",
+                self.name
+            )));
+        }
+        #[cfg(unix)]
+        if self
+            .watchdog_signal
+            .parse::<nix::sys::signal::Signal>()
+            .is_err()
+        {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has an unknown watchdog_signal '{}'",
+                self.name, self.watchdog_signal
+            )));
+        }
+        if self.schedule.is_some() && self.every.is_some() {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has both a schedule and every",
+                self.name
+            )));
+        }
+        if self.schedule_timezone.is_some() && self.schedule.is_none() {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has a schedule_timezone but no schedule",
+                self.name
+            )));
+        }
+        if !self.is_scheduled() && (self.jitter.is_some() || self.catch_up != CatchUp::None) {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has jitter or catch_up but no schedule or every",
+                self.name
+            )));
+        }
+        JobSchedule::from_spec(self)?;
+        scheduler::jitter(self)?;
+        if self.ready_mode == ReadyMode::Health && self.health_check.is_none() {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has ready_mode = \"health\" but no health_check",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One version of a daemon's spec. Every update creates a new revision;
+/// older ones are kept so they can be rolled back to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecRevision {
+    pub revision: u64,
+    /// When this revision was registered.
+    pub created_at: DateTime<Utc>,
+    pub spec: DaemonSpec,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonInstance {
     pub id: String,
     pub spec_name: String,
+    /// Index of this process among the daemon's replicas.
+    #[serde(default)]
+    pub replica: u32,
     pub state: LifecycleState,
     pub pid: Option<u32>,
     pub started_at: Option<DateTime<Utc>>,
     pub stopped_at: Option<DateTime<Utc>>,
     pub exit_code: Option<i32>,
+    /// Signal that terminated the last process, if it didn't exit by itself.
+    #[serde(default)]
+    pub exit_signal: Option<i32>,
+    /// When the process `pid` refers to was started, in seconds since the
+    /// epoch. Used to recognise it again after a manager restart.
+    #[serde(default)]
+    pub pid_start_time: Option<u64>,
+    /// The notify socket the process `pid` refers to was pointed at.
+    #[serde(default)]
+    pub notify_socket: Option<PathBuf>,
     pub restart_count: u32,
     pub health_status: HealthStatus,
     pub stdout_log: Option<PathBuf>,
     pub stderr_log: Option<PathBuf>,
+    /// Limits or options that could not be applied on the last start.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Why the manager last acted on the daemon on its own, e.g. restarted it
+    /// after a soft resource limit was exceeded.
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Last `STATUS=` the process sent to its notify socket.
+    #[serde(default)]
+    pub status_message: Option<String>,
+    /// When the process last sent `WATCHDOG=1`.
+    #[serde(default)]
+    pub last_watchdog: Option<DateTime<Utc>>,
+    /// When the daemon's `schedule` fires next.
+    #[serde(default)]
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// When the daemon was last restarted after exiting, within its restart
+    /// policy's burst window. Not persisted.
+    #[serde(skip)]
+    pub recent_restarts: Vec<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -119,18 +471,37 @@ pub enum HealthStatus {
 
 impl DaemonInstance {
     pub fn new(spec_name: &str) -> Self {
+        Self::replica(spec_name, 0)
+    }
+
+    pub fn replica(spec_name: &str, replica: u32) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             spec_name: spec_name.to_string(),
+            replica,
             state: LifecycleState::Stopped,
             pid: None,
             started_at: None,
             stopped_at: None,
             exit_code: None,
+            exit_signal: None,
+            pid_start_time: None,
+            notify_socket: None,
             restart_count: 0,
             health_status: HealthStatus::Unknown,
             stdout_log: None,
             stderr_log: None,
+            warnings: Vec::new(),
+            reason: None,
+            status_message: None,
+            last_watchdog: None,
+            next_run_at: None,
+            recent_restarts: Vec::new(),
         }
     }
+
+    /// The instance's name: see [`replica_name`].
+    pub fn name(&self) -> String {
+        replica_name(&self.spec_name, self.replica)
+    }
 }
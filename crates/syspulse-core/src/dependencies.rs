@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use crate::daemon::DaemonSpec;
+use crate::error::{Result, SyspulseError};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Visiting,
+    Done,
+}
+
+/// Dependency graph of a set of daemon specs. Dependencies on daemons that
+/// are not part of the set are ignored.
+pub struct DependencyGraph<'a> {
+    edges: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    pub fn new(specs: &'a [DaemonSpec]) -> Self {
+        let edges = specs
+            .iter()
+            .map(|spec| {
+                let deps = spec.depends_on.iter().map(|d| d.name.as_str()).collect();
+                (spec.name.as_str(), deps)
+            })
+            .collect();
+        Self { edges }
+    }
+
+    /// Fail with a config error naming the cycle if the graph has one.
+    pub fn check_cycles(&self) -> Result<()> {
+        self.order_of(self.names()).map(|_| ())
+    }
+
+    /// Every daemon, dependencies before their dependents.
+    pub fn start_order(&self) -> Result<Vec<String>> {
+        self.order_of(self.names())
+    }
+
+    /// `name` and everything it transitively depends on, dependencies first.
+    pub fn with_dependencies(&self, name: &str) -> Result<Vec<String>> {
+        let mut missing = None;
+        let mut stack = vec![name];
+        let mut seen = vec![name];
+        while let Some(current) = stack.pop() {
+            let Some(deps) = self.edges.get(current) else {
+                missing = Some(current);
+                break;
+            };
+            for dep in deps {
+                if !seen.contains(dep) {
+                    seen.push(dep);
+                    stack.push(dep);
+                }
+            }
+        }
+        if let Some(missing) = missing {
+            return Err(SyspulseError::DaemonNotFound(missing.to_string()));
+        }
+        self.order_of(seen)
+    }
+
+    fn names(&self) -> Vec<&'a str> {
+        let mut names: Vec<&str> = self.edges.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn order_of(&self, roots: Vec<&str>) -> Result<Vec<String>> {
+        let mut marks = HashMap::new();
+        let mut path = Vec::new();
+        let mut order = Vec::new();
+        for root in roots {
+            self.visit(root, &mut marks, &mut path, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    fn visit<'n>(
+        &'n self,
+        name: &'n str,
+        marks: &mut HashMap<&'n str, Mark>,
+        path: &mut Vec<&'n str>,
+        order: &mut Vec<String>,
+    ) -> Result<()> {
+        match marks.get(name) {
+            Some(Mark::Done) => return Ok(()),
+            Some(Mark::Visiting) => {
+                let start = path.iter().position(|n| *n == name).unwrap_or(0);
+                let mut cycle: Vec<&str> = path[start..].to_vec();
+                cycle.push(name);
+                return Err(SyspulseError::Config(format!(
+                    "Dependency cycle: {}",
+                    cycle.join(" -> ")
+                )));
+            }
+            None => {}
+        }
+        let Some(deps) = self.edges.get(name) else {
+            return Ok(());
+        };
+
+        marks.insert(name, Mark::Visiting);
+        path.push(name);
+        for dep in deps {
+            self.visit(dep, marks, path, order)?;
+        }
+        path.pop();
+        marks.insert(name, Mark::Done);
+        order.push(name.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_config;
+    use crate::daemon::{Dependency, DependencyCondition};
+
+    fn specs(toml: &str) -> Vec<DaemonSpec> {
+        parse_config(toml).unwrap()
+    }
+
+    const STACK: &str = r#"
+[[daemon]]
+name = "worker"
+command = ["worker"]
+depends_on = ["api", { name = "db", condition = "healthy" }]
+
+[[daemon]]
+name = "api"
+command = ["api"]
+depends_on = ["db"]
+
+[[daemon]]
+name = "db"
+command = ["db"]
+
+[[daemon]]
+name = "cache"
+command = ["cache"]
+"#;
+
+    #[test]
+    fn start_order_puts_dependencies_first() {
+        let specs = specs(STACK);
+        let order = DependencyGraph::new(&specs).start_order().unwrap();
+        let pos = |name: &str| order.iter().position(|n| n == name).unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(pos("db") < pos("api"));
+        assert!(pos("api") < pos("worker"));
+    }
+
+    #[test]
+    fn with_dependencies_includes_only_what_is_needed() {
+        let specs = specs(STACK);
+        let graph = DependencyGraph::new(&specs);
+        assert_eq!(graph.with_dependencies("api").unwrap(), vec!["db", "api"]);
+        assert_eq!(
+            graph.with_dependencies("worker").unwrap(),
+            vec!["db", "api", "worker"]
+        );
+    }
+
+    #[test]
+    fn missing_dependency_is_reported_when_starting() {
+        let mut specs = specs(STACK);
+        specs.retain(|s| s.name != "db");
+        let graph = DependencyGraph::new(&specs);
+        assert!(graph.check_cycles().is_ok());
+        assert!(matches!(
+            graph.with_dependencies("worker"),
+            Err(SyspulseError::DaemonNotFound(name)) if name == "db"
+        ));
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let mut specs = specs(STACK);
+        let db = specs.iter_mut().find(|s| s.name == "db").unwrap();
+        db.depends_on = vec![Dependency {
+            name: "worker".to_string(),
+            condition: DependencyCondition::Started,
+        }];
+        let err = DependencyGraph::new(&specs).check_cycles().unwrap_err();
+        assert!(err.to_string().contains("cycle"), "{}", err);
+    }
+}
@@ -8,6 +8,8 @@ pub enum SyspulseError {
     DaemonAlreadyExists(String),
     #[error("Invalid state transition from {from:?} to {to:?}")]
     InvalidStateTransition { from: String, to: String },
+    #[error("Daemon '{0}' is crash-looping; run `syspulse reset-failed {0}` before starting it")]
+    CrashLooping(String),
     #[error("Process error: {0}")]
     Process(String),
     #[error("Health check failed: {0}")]
@@ -26,6 +28,14 @@ pub enum SyspulseError {
     Serialization(#[from] serde_json::Error),
     #[error("Database error: {0}")]
     Database(String),
+    #[error(
+        "Daemon '{name}' was changed concurrently (expected revision {expected}, found {actual})"
+    )]
+    RevisionConflict {
+        name: String,
+        expected: u64,
+        actual: u64,
+    },
     #[error("Timeout after {0:?}")]
     Timeout(std::time::Duration),
 }
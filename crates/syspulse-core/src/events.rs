@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::daemon::{parse_replica_name, HealthStatus};
+use crate::error::{Result, SyspulseError};
+use crate::process::ExitStatus;
+
+/// Events older than this are pruned from the history.
+pub const EVENT_RETENTION_DAYS: i64 = 30;
+
+/// At most this many events are kept per daemon.
+pub const EVENT_RETENTION_COUNT: usize = 1000;
+
+/// One entry of a daemon's lifecycle history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonEvent {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: EventKind,
+    /// Why the manager acted, e.g. a failed health check or a soft limit.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventKind {
+    /// The process was spawned.
+    Started { pid: u32 },
+    /// The daemon was stopped on request.
+    Stopped {
+        exit_code: Option<i32>,
+        #[serde(default)]
+        signal: Option<i32>,
+    },
+    /// The process ended without being asked to, or was ended by the manager
+    /// because of `message`.
+    Exited {
+        exit_code: Option<i32>,
+        signal: Option<i32>,
+    },
+    /// A restart was scheduled by the restart policy.
+    Restarting { attempt: u32, backoff_ms: u64 },
+    /// The health check result changed.
+    Health { status: HealthStatus },
+    /// The cron schedule fired.
+    CronTriggered,
+    /// A resource usage sample. Only pushed to subscribers, never recorded
+    /// in the history, so these events always have an `id` of 0.
+    Resources {
+        cpu_percent: f64,
+        memory_bytes: u64,
+        process_count: u32,
+    },
+}
+
+impl EventKind {
+    /// Every `kind` tag, for validating subscription filters.
+    pub const NAMES: &'static [&'static str] = &[
+        "started",
+        "stopped",
+        "exited",
+        "restarting",
+        "health",
+        "cron_triggered",
+        "resources",
+    ];
+
+    /// The `kind` tag, as stored and filtered on.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::Started { .. } => "started",
+            EventKind::Stopped { .. } => "stopped",
+            EventKind::Exited { .. } => "exited",
+            EventKind::Restarting { .. } => "restarting",
+            EventKind::Health { .. } => "health",
+            EventKind::CronTriggered => "cron_triggered",
+            EventKind::Resources { .. } => "resources",
+        }
+    }
+
+    /// An exit as reported by the process driver.
+    pub fn exited(status: Option<ExitStatus>) -> Self {
+        EventKind::Exited {
+            exit_code: status.and_then(ExitStatus::code),
+            signal: status.and_then(ExitStatus::signal),
+        }
+    }
+}
+
+/// Which events a subscriber gets: those of any of `names` and any of
+/// `kinds`, where an empty list matches everything. A daemon name matches
+/// the events of all its replicas, `name:replica` only that replica's.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    names: Vec<String>,
+    kinds: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn new(names: Vec<String>, kinds: Vec<String>) -> Result<Self> {
+        if let Some(kind) = kinds
+            .iter()
+            .find(|k| !EventKind::NAMES.contains(&k.as_str()))
+        {
+            return Err(SyspulseError::Config(format!(
+                "Unknown event kind '{}', expected one of: {}",
+                kind,
+                EventKind::NAMES.join(", ")
+            )));
+        }
+        Ok(Self { names, kinds })
+    }
+
+    pub fn matches(&self, event: &DaemonEvent) -> bool {
+        let daemon = parse_replica_name(&event.name).0;
+        (self.names.is_empty() || self.names.iter().any(|n| *n == event.name || n == daemon))
+            && (self.kinds.is_empty() || self.kinds.iter().any(|k| k == event.kind.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_json_is_flat() {
+        let event = DaemonEvent {
+            id: 1,
+            timestamp: Utc::now(),
+            name: "web".to_string(),
+            kind: EventKind::Restarting {
+                attempt: 2,
+                backoff_ms: 4000,
+            },
+            message: None,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["kind"], "restarting");
+        assert_eq!(json["attempt"], 2);
+
+        let back: DaemonEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(back.kind, event.kind);
+    }
+
+    #[test]
+    fn exit_status_is_split_into_code_and_signal() {
+        assert_eq!(
+            EventKind::exited(Some(ExitStatus::Signal(9))),
+            EventKind::Exited {
+                exit_code: None,
+                signal: Some(9)
+            }
+        );
+        assert_eq!(
+            EventKind::exited(Some(ExitStatus::Code(3))),
+            EventKind::Exited {
+                exit_code: Some(3),
+                signal: None
+            }
+        );
+    }
+
+    fn event(name: &str, kind: EventKind) -> DaemonEvent {
+        DaemonEvent {
+            id: 0,
+            timestamp: Utc::now(),
+            name: name.to_string(),
+            kind,
+            message: None,
+        }
+    }
+
+    fn filter(names: &[&str], kinds: &[&str]) -> EventFilter {
+        let strings = |s: &[&str]| s.iter().map(|s| s.to_string()).collect();
+        EventFilter::new(strings(names), strings(kinds)).unwrap()
+    }
+
+    #[test]
+    fn filter_matches_daemons_and_their_replicas() {
+        let started = |name| event(name, EventKind::Started { pid: 1 });
+        let all = filter(&[], &[]);
+        assert!(all.matches(&started("web")) && all.matches(&started("db:2")));
+
+        let web = filter(&["web"], &[]);
+        assert!(web.matches(&started("web")));
+        assert!(web.matches(&started("web:0")) && web.matches(&started("web:3")));
+        assert!(!web.matches(&started("webapp")) && !web.matches(&started("db")));
+
+        let replica = filter(&["web:1", "db"], &[]);
+        assert!(replica.matches(&started("web:1")) && replica.matches(&started("db")));
+        assert!(!replica.matches(&started("web:2")) && !replica.matches(&started("web")));
+    }
+
+    #[test]
+    fn filter_matches_kinds() {
+        let unhealthy = |name| {
+            let status = HealthStatus::Unhealthy;
+            event(name, EventKind::Health { status })
+        };
+        let health = filter(&["web"], &["health", "exited"]);
+        assert!(health.matches(&unhealthy("web:1")));
+        assert!(health.matches(&event("web", EventKind::exited(None))));
+        assert!(!health.matches(&event("web", EventKind::CronTriggered)));
+        assert!(!health.matches(&unhealthy("db")));
+
+        let err = EventFilter::new(Vec::new(), vec!["crashed".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Unknown event kind 'crashed'"));
+    }
+}
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use tokio::io::{ReadHalf, WriteHalf};
 use tracing::debug;
 
 use crate::error::{Result, SyspulseError};
@@ -24,22 +25,33 @@ impl IpcClient {
     /// Send a request to the daemon manager and return the response.
     /// Each call creates a fresh connection (simple request-response model).
     pub async fn send(&self, request: Request) -> Result<Response> {
+        let mut stream = self.stream(request).await?;
+
+        let response = stream.next().await?.ok_or_else(|| {
+            SyspulseError::Ipc("Server closed connection without response".into())
+        })?;
+
+        debug!("Received IPC response: {:?}", response);
+        Ok(response)
+    }
+
+    /// Send a streaming request (e.g. `Logs { follow: true }`) and return the
+    /// open connection so the caller can read responses as they arrive.
+    pub async fn stream(&self, request: Request) -> Result<ResponseStream> {
         let name = self.socket_name()?;
         let stream = LocalSocketStream::connect(name)
             .await
             .map_err(|e| SyspulseError::Ipc(format!("Failed to connect: {}", e)))?;
 
-        let (mut reader, mut writer) = tokio::io::split(stream);
+        let (reader, mut writer) = tokio::io::split(stream);
 
         write_message(&mut writer, &request).await?;
         debug!("Sent IPC request: {:?}", request);
 
-        let response: Response = read_message(&mut reader).await?.ok_or_else(|| {
-            SyspulseError::Ipc("Server closed connection without response".into())
-        })?;
-
-        debug!("Received IPC response: {:?}", response);
-        Ok(response)
+        Ok(ResponseStream {
+            reader,
+            _writer: writer,
+        })
     }
 
     /// Check if the daemon manager is reachable by sending a Ping.
@@ -58,7 +70,7 @@ impl IpcClient {
                 .to_str()
                 .ok_or_else(|| SyspulseError::Ipc("Invalid socket path".into()))?;
             path_str
-                .to_ns_name::<NameType>()
+                .to_fs_name::<NameType>()
                 .map_err(|e| SyspulseError::Ipc(format!("Invalid socket name: {}", e)))
         }
         #[cfg(windows)]
@@ -74,3 +86,18 @@ impl IpcClient {
         }
     }
 }
+
+/// An open connection the manager pushes responses on.
+pub struct ResponseStream {
+    reader: ReadHalf<LocalSocketStream>,
+    // Kept so the connection stays open for as long as we're reading.
+    _writer: WriteHalf<LocalSocketStream>,
+}
+
+impl ResponseStream {
+    /// Wait for the next response. Returns `Ok(None)` once the manager closes
+    /// the stream.
+    pub async fn next(&mut self) -> Result<Option<Response>> {
+        read_message(&mut self.reader).await
+    }
+}
@@ -1,26 +1,47 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-use crate::daemon::{DaemonInstance, DaemonSpec};
+use crate::apply::ApplyPlan;
+use crate::daemon::{DaemonInstance, DaemonSpec, SpecRevision};
 use crate::error::SyspulseError;
+use crate::events::DaemonEvent;
+use crate::resources::DaemonStats;
+use crate::runs::DaemonRun;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Request {
     Start {
         name: String,
+        /// Reply only once the daemon is running and healthy.
         wait: bool,
+        /// How long to wait; defaults to 60 seconds.
         timeout_secs: Option<u64>,
+        /// Start the daemons it depends on first.
+        #[serde(default)]
+        with_deps: bool,
     },
     Stop {
         name: String,
         force: bool,
+        /// Overrides the daemon's `stop_timeout_secs`.
         timeout_secs: Option<u64>,
     },
     Restart {
         name: String,
         force: bool,
+        /// Reply only once the daemon is running and healthy again.
         wait: bool,
+        /// How long to wait; defaults to 60 seconds. With `rolling`, how
+        /// long each new process has to become healthy.
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+        /// Start each replica's new process before stopping the old one,
+        /// keeping the old one if the new one doesn't become healthy.
+        /// Rejected together with `force`.
+        #[serde(default)]
+        rolling: bool,
     },
     Status {
         name: Option<String>,
@@ -30,6 +51,9 @@ pub enum Request {
         name: String,
         lines: usize,
         stderr: bool,
+        /// Keep the connection open and stream new lines as `Response::Logs`.
+        #[serde(default)]
+        follow: bool,
     },
     Add {
         spec: DaemonSpec,
@@ -38,6 +62,84 @@ pub enum Request {
         name: String,
         force: bool,
     },
+    /// The current spec of a daemon, or an earlier revision of it.
+    GetSpec {
+        name: String,
+        #[serde(default)]
+        revision: Option<u64>,
+    },
+    /// Every revision of a daemon's spec, newest first.
+    Revisions {
+        name: String,
+    },
+    /// Replace a daemon's spec. A running daemon is restarted only if a
+    /// field that affects the process changed.
+    Update {
+        spec: DaemonSpec,
+        /// The revision `spec` was based on. The update is rejected with a
+        /// conflict if the daemon has been changed since.
+        #[serde(default)]
+        revision: Option<u64>,
+    },
+    /// Set how many replicas of a daemon run, starting or stopping the
+    /// difference. Answered like `Update`.
+    Scale {
+        name: String,
+        replicas: u32,
+    },
+    /// Make the registered daemons match `specs`: add new ones, update
+    /// changed ones in place and, with `prune`, remove the rest. Running
+    /// daemons are restarted only if a field that affects the process
+    /// changed.
+    Apply {
+        specs: Vec<DaemonSpec>,
+        /// Only compute the plan.
+        #[serde(default)]
+        dry_run: bool,
+        /// Remove registered daemons that are not in `specs`.
+        #[serde(default)]
+        prune: bool,
+    },
+    /// Recent resource usage of running daemons, or of one daemon.
+    Stats {
+        name: Option<String>,
+    },
+    /// Lifecycle history of one daemon, or of all daemons, oldest first.
+    Events {
+        name: Option<String>,
+        /// Only events at or after this time.
+        #[serde(default)]
+        since: Option<DateTime<Utc>>,
+        /// Return the latest `limit` events; defaults to 50.
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    /// Run a scheduled daemon now, as if its schedule had fired. Recorded
+    /// as a manual run.
+    Trigger {
+        name: String,
+    },
+    /// Move a failed or crash-looping daemon (or one replica) back to
+    /// stopped and reset its restart count.
+    ResetFailed {
+        name: String,
+    },
+    /// Run history of a scheduled daemon (or one replica), oldest first.
+    Runs {
+        name: String,
+        /// Return the latest `limit` runs; defaults to 20.
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    /// Keep the connection open and push matching events as
+    /// `Response::Event`. Empty filters match everything.
+    Subscribe {
+        #[serde(default)]
+        names: Vec<String>,
+        /// Event kinds such as `exited`, `health` or `resources`.
+        #[serde(default)]
+        kinds: Vec<String>,
+    },
     Shutdown,
     Ping,
 }
@@ -45,12 +147,51 @@ pub enum Request {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Response {
-    Ok { message: String },
-    Status { instance: DaemonInstance },
-    List { instances: Vec<DaemonInstance> },
-    Logs { lines: Vec<String> },
+    Ok {
+        message: String,
+    },
+    Status {
+        instance: DaemonInstance,
+    },
+    List {
+        instances: Vec<DaemonInstance>,
+    },
+    Logs {
+        lines: Vec<String>,
+    },
+    Stats {
+        stats: Vec<DaemonStats>,
+    },
+    Events {
+        events: Vec<DaemonEvent>,
+    },
+    Runs {
+        runs: Vec<DaemonRun>,
+    },
+    Event {
+        event: DaemonEvent,
+    },
+    Plan {
+        plan: ApplyPlan,
+    },
+    Spec {
+        spec: DaemonSpec,
+        revision: u64,
+    },
+    Revisions {
+        revisions: Vec<SpecRevision>,
+    },
+    Updated {
+        revision: u64,
+        /// Top-level spec fields that changed; empty if nothing did.
+        fields: Vec<String>,
+        restarted: bool,
+    },
     Pong,
-    Error { code: u32, message: String },
+    Error {
+        code: u32,
+        message: String,
+    },
 }
 
 /// Encode a message as 4-byte big-endian length prefix + JSON payload.
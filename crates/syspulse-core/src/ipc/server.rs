@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 
 use crate::error::{Result, SyspulseError};
@@ -14,6 +15,21 @@ use interprocess::local_socket::GenericNamespaced as NameType;
 
 use interprocess::local_socket::{tokio::prelude::*, traits::tokio::Listener, ListenerOptions};
 
+/// What a request handler sends back over the connection.
+pub enum Reply {
+    /// A single response; the connection then waits for the next request.
+    Once(Box<Response>),
+    /// A stream of responses pushed until the sender is dropped or the client
+    /// disconnects. The connection is closed when the stream ends.
+    Stream(mpsc::Receiver<Response>),
+}
+
+impl From<Response> for Reply {
+    fn from(response: Response) -> Self {
+        Reply::Once(Box::new(response))
+    }
+}
+
 pub struct IpcServer {
     socket_path: PathBuf,
 }
@@ -25,7 +41,7 @@ impl IpcServer {
 
     /// Run the IPC server, dispatching each request to the given handler.
     ///
-    /// The handler receives a `Request` and returns a `Response`. The server
+    /// The handler receives a `Request` and returns a `Reply`. The server
     /// keeps running until the handler returns a response indicating shutdown
     /// or `shutdown_rx` fires.
     pub async fn run<F, Fut>(
@@ -35,7 +51,7 @@ impl IpcServer {
     ) -> Result<()>
     where
         F: Fn(Request) -> Fut + Send + Sync + 'static,
-        Fut: std::future::Future<Output = Response> + Send,
+        Fut: std::future::Future<Output = Reply> + Send,
     {
         // On Unix, remove stale socket file if it exists.
         #[cfg(unix)]
@@ -94,7 +110,7 @@ impl IpcServer {
                 .to_str()
                 .ok_or_else(|| SyspulseError::Ipc("Invalid socket path".into()))?;
             path_str
-                .to_ns_name::<NameType>()
+                .to_fs_name::<NameType>()
                 .map_err(|e| SyspulseError::Ipc(format!("Invalid socket name: {}", e)))
         }
         #[cfg(windows)]
@@ -119,7 +135,7 @@ async fn handle_connection<F, Fut>(
 ) -> Result<()>
 where
     F: Fn(Request) -> Fut + Send + Sync + 'static,
-    Fut: std::future::Future<Output = Response> + Send,
+    Fut: std::future::Future<Output = Reply> + Send,
 {
     let (mut reader, mut writer) = tokio::io::split(stream);
 
@@ -132,9 +148,16 @@ where
         };
 
         let is_shutdown = matches!(request, Request::Shutdown);
-        let response = handler(request).await;
-        write_message(&mut writer, &response).await?;
-        writer.flush().await?;
+        match handler(request).await {
+            Reply::Once(response) => {
+                write_message(&mut writer, &response).await?;
+                writer.flush().await?;
+            }
+            Reply::Stream(rx) => {
+                stream_responses(&mut reader, &mut writer, rx).await?;
+                break;
+            }
+        }
 
         if is_shutdown {
             break;
@@ -143,3 +166,25 @@ where
 
     Ok(())
 }
+
+/// Forward streamed responses to the client until the producer finishes or
+/// the client goes away. Dropping `rx` tells the producer to stop.
+async fn stream_responses(
+    reader: &mut (impl AsyncReadExt + Unpin),
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    mut rx: mpsc::Receiver<Response>,
+) -> Result<()> {
+    let mut probe = [0u8; 1];
+    loop {
+        tokio::select! {
+            next = rx.recv() => match next {
+                Some(response) => write_message(writer, &response).await?,
+                None => break,
+            },
+            // Clients don't send anything while streaming, so a read returning
+            // means they hung up (or broke protocol); either way we're done.
+            _ = reader.read(&mut probe) => break,
+        }
+    }
+    Ok(())
+}
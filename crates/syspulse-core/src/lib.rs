@@ -1,17 +1,23 @@
+pub mod apply;
 pub mod config;
 pub mod daemon;
+pub mod dependencies;
 pub mod error;
+pub mod events;
 pub mod health;
 pub mod ipc;
 pub mod lifecycle;
 pub mod logs;
 pub mod manager;
+pub mod notify;
 pub mod paths;
 pub mod process;
 pub mod registry;
 pub mod resources;
 pub mod restart;
+pub mod runs;
 pub mod scheduler;
+pub mod sockets;
 
 pub use config::parse_config;
 pub use daemon::{DaemonInstance, DaemonSpec, HealthCheckSpec, HealthStatus};
@@ -9,6 +9,9 @@ pub enum LifecycleState {
     Stopping,
     Failed,
     Scheduled,
+    /// Restarted more often than the restart policy's burst limit allows.
+    /// Stays here until reset with `reset-failed`.
+    CrashLooping,
 }
 
 impl LifecycleState {
@@ -29,6 +32,10 @@ impl LifecycleState {
                 | (Failed, Stopped)
                 | (Scheduled, Starting)
                 | (Scheduled, Stopped)
+                | (Starting, CrashLooping)
+                | (Running, CrashLooping)
+                | (Stopping, CrashLooping)
+                | (CrashLooping, Stopped)
         )
     }
 
@@ -57,6 +64,7 @@ impl std::fmt::Display for LifecycleState {
             Self::Stopping => write!(f, "stopping"),
             Self::Failed => write!(f, "failed"),
             Self::Scheduled => write!(f, "scheduled"),
+            Self::CrashLooping => write!(f, "crash_looping"),
         }
     }
 }
@@ -82,6 +90,10 @@ mod tests {
             (Failed, Stopped),
             (Scheduled, Starting),
             (Scheduled, Stopped),
+            (Starting, CrashLooping),
+            (Running, CrashLooping),
+            (Stopping, CrashLooping),
+            (CrashLooping, Stopped),
         ];
 
         for (from, to) in valid {
@@ -120,6 +132,9 @@ mod tests {
             (Scheduled, Running),
             (Scheduled, Stopping),
             (Scheduled, Failed),
+            (CrashLooping, Starting),
+            (CrashLooping, Scheduled),
+            (Failed, CrashLooping),
         ];
 
         for (from, to) in invalid {
@@ -140,7 +155,15 @@ mod tests {
 
     #[test]
     fn self_transitions_are_invalid() {
-        let all_states = vec![Stopped, Starting, Running, Stopping, Failed, Scheduled];
+        let all_states = vec![
+            Stopped,
+            Starting,
+            Running,
+            Stopping,
+            Failed,
+            Scheduled,
+            CrashLooping,
+        ];
         for state in all_states {
             assert!(
                 !state.can_transition_to(state),
@@ -159,6 +182,7 @@ mod tests {
         assert!(Stopping.is_active());
         assert!(!Failed.is_active());
         assert!(!Scheduled.is_active());
+        assert!(!CrashLooping.is_active());
     }
 
     #[test]
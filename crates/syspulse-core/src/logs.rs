@@ -1,10 +1,14 @@
+use std::collections::VecDeque;
 use std::fs;
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 
-use crate::daemon::LogConfig;
+use crate::daemon::{parse_replica_name, LogConfig};
 use crate::error::Result;
 use crate::paths;
 
@@ -25,8 +29,16 @@ impl LogManager {
         }
     }
 
+    /// `logs/<name>` for a daemon's first replica, `logs/<name>/<index>` for
+    /// the others.
     fn log_dir(&self, daemon_name: &str) -> PathBuf {
-        self.data_dir.join("logs").join(daemon_name)
+        let (name, replica, _) = parse_replica_name(daemon_name);
+        let dir = self.data_dir.join("logs").join(name);
+        if replica == 0 {
+            dir
+        } else {
+            dir.join(replica.to_string())
+        }
     }
 
     /// Create log directory and return (stdout_path, stderr_path).
@@ -41,7 +53,12 @@ impl LogManager {
     }
 
     /// Rotate log files if they exceed the configured max size.
-    /// Rotated files are named with a timestamp suffix. Files beyond retain_count are pruned.
+    ///
+    /// Uses copy-truncate so a running daemon can keep writing through its
+    /// open append-mode descriptor: the contents are copied to a file with a
+    /// timestamp suffix (gzipped when `compress_rotated` is set) and the live
+    /// file is truncated. Lines written between the copy and the truncate are
+    /// lost. Files beyond retain_count are pruned.
     pub fn rotate_logs(&self, daemon_name: &str, config: &LogConfig) -> Result<()> {
         let dir = self.log_dir(daemon_name);
 
@@ -51,18 +68,32 @@ impl LogManager {
                 continue;
             }
 
-            let metadata = fs::metadata(&log_path)?;
-            if metadata.len() < config.max_size_bytes {
+            let mut live = fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&log_path)?;
+            let len = live.metadata()?.len();
+            if len < config.max_size_bytes {
                 continue;
             }
 
-            // Rotate: rename current log with timestamp suffix
-            let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+            let timestamp = Utc::now().format("%Y%m%d_%H%M%S_%3f");
             let base = log_name.trim_end_matches(".log");
-            let rotated_name = format!("{}_{}.log", base, timestamp);
+            let rotated_name = if config.compress_rotated {
+                format!("{}_{}.log.gz", base, timestamp)
+            } else {
+                format!("{}_{}.log", base, timestamp)
+            };
             let rotated_path = dir.join(&rotated_name);
 
-            fs::rename(&log_path, &rotated_path)?;
+            // Write under a temporary name so readers never see a partial
+            // segment.
+            let tmp_path = dir.join(format!(".{}.tmp", rotated_name));
+            live.seek(SeekFrom::Start(0))?;
+            copy_segment(&mut live, len, &tmp_path, config.compress_rotated)?;
+            fs::rename(&tmp_path, &rotated_path)?;
+            live.set_len(0)?;
+
             tracing::info!(
                 daemon = daemon_name,
                 file = %log_name,
@@ -79,22 +110,9 @@ impl LogManager {
 
     /// Remove oldest rotated log files beyond retain_count.
     fn prune_rotated(&self, dir: &Path, base_name: &str, retain_count: u32) -> Result<()> {
-        let prefix = format!("{}_", base_name);
-
-        let mut rotated: Vec<PathBuf> = fs::read_dir(dir)?
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                let name = entry.file_name().to_string_lossy().to_string();
-                name.starts_with(&prefix) && name.ends_with(".log")
-            })
-            .map(|entry| entry.path())
-            .collect();
-
-        // Sort by name descending (newest first, since names contain timestamps)
-        rotated.sort();
-        rotated.reverse();
-
-        for path in rotated.iter().skip(retain_count as usize) {
+        let rotated = rotated_segments(dir, base_name)?;
+
+        for path in rotated.iter().rev().skip(retain_count as usize) {
             tracing::debug!(path = %path.display(), "Pruning old rotated log");
             if let Err(e) = fs::remove_file(path) {
                 tracing::warn!(path = %path.display(), error = %e, "Failed to prune log file");
@@ -106,43 +124,257 @@ impl LogManager {
 
     /// Read the last N lines from a daemon's log file.
     /// If `stderr` is true, reads from stderr.log; otherwise stdout.log.
+    /// Continues into rotated segments when the live file is too short.
     pub fn read_logs(&self, daemon_name: &str, lines: usize, stderr: bool) -> Result<Vec<String>> {
         let dir = self.log_dir(daemon_name);
-        let filename = if stderr { "stderr.log" } else { "stdout.log" };
-        let log_path = dir.join(filename);
+        let base = if stderr { "stderr" } else { "stdout" };
+        let log_path = dir.join(format!("{}.log", base));
+
+        let mut tail = if log_path.exists() {
+            tail_file(&log_path, lines)?
+        } else {
+            Vec::new()
+        };
+        prepend_rotated(&dir, base, lines, &mut tail)?;
+        Ok(tail)
+    }
 
-        if !log_path.exists() {
-            return Ok(Vec::new());
+    /// Read the last N lines of a daemon's log and return a follower positioned
+    /// right after them, so no line is lost or repeated between the two.
+    pub fn follow_logs(
+        &self,
+        daemon_name: &str,
+        lines: usize,
+        stderr: bool,
+    ) -> Result<(Vec<String>, LogFollower)> {
+        let dir = self.log_dir(daemon_name);
+        let base = if stderr { "stderr" } else { "stdout" };
+        let log_path = dir.join(format!("{}.log", base));
+
+        let (mut tail, follower) = if log_path.exists() {
+            let mut file = fs::File::open(&log_path)?;
+            let file_size = file.metadata()?.len();
+            let tail = tail_range(&mut file, file_size, lines)?;
+            (tail, LogFollower::from_open(log_path, file, file_size)?)
+        } else {
+            (Vec::new(), LogFollower::new(log_path))
+        };
+        prepend_rotated(&dir, base, lines, &mut tail)?;
+        Ok((tail, follower))
+    }
+}
+
+/// Incrementally reads lines appended to a log file.
+///
+/// Survives rotation: when the file is truncated in place the follower starts
+/// over from the beginning, and when it is renamed away and recreated the
+/// follower drains the old file before switching to the new one.
+pub struct LogFollower {
+    path: PathBuf,
+    file: Option<fs::File>,
+    identity: Option<FileIdentity>,
+    pos: u64,
+    partial: Vec<u8>,
+}
+
+impl LogFollower {
+    /// Follow `path` from its current end. The file does not need to exist yet.
+    pub fn new(path: PathBuf) -> Self {
+        let mut follower = Self {
+            path,
+            file: None,
+            identity: None,
+            pos: 0,
+            partial: Vec::new(),
+        };
+        if let Ok(file) = fs::File::open(&follower.path) {
+            if let Ok(metadata) = file.metadata() {
+                follower.pos = metadata.len();
+                follower.identity = file_identity(&metadata);
+                follower.file = Some(file);
+            }
+        }
+        follower
+    }
+
+    fn from_open(path: PathBuf, file: fs::File, pos: u64) -> Result<Self> {
+        let identity = file_identity(&file.metadata()?);
+        Ok(Self {
+            path,
+            file: Some(file),
+            identity,
+            pos,
+            partial: Vec::new(),
+        })
+    }
+
+    /// Return the complete lines written since the last call.
+    pub fn poll(&mut self) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+
+        let current = match fs::metadata(&self.path) {
+            Ok(metadata) => Some(metadata),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(ref metadata) = current {
+            let replaced = self.file.is_none()
+                || (self.identity.is_some() && file_identity(metadata) != self.identity);
+            if replaced {
+                // Finish whatever was written to the old file before it was
+                // rotated away, then start reading the new one from the top.
+                self.read_available(&mut lines)?;
+                self.flush_partial(&mut lines);
+                let file = fs::File::open(&self.path)?;
+                self.identity = file_identity(&file.metadata()?);
+                self.file = Some(file);
+                self.pos = 0;
+            } else if metadata.len() < self.pos {
+                // Truncated in place (copy-truncate rotation).
+                self.flush_partial(&mut lines);
+                self.pos = 0;
+            }
         }
 
-        tail_file(&log_path, lines)
+        self.read_available(&mut lines)?;
+        Ok(lines)
     }
+
+    fn read_available(&mut self, lines: &mut Vec<String>) -> Result<()> {
+        let file = match self.file.as_mut() {
+            Some(f) => f,
+            None => return Ok(()),
+        };
+
+        file.seek(SeekFrom::Start(self.pos))?;
+        let mut buf = Vec::new();
+        let read = file.read_to_end(&mut buf)?;
+        self.pos += read as u64;
+        self.partial.extend_from_slice(&buf);
+
+        while let Some(idx) = self.partial.iter().position(|&b| b == b'\n') {
+            let rest = self.partial.split_off(idx + 1);
+            let line = std::mem::replace(&mut self.partial, rest);
+            let text = String::from_utf8_lossy(&line[..idx]);
+            lines.push(text.trim_end_matches('\r').to_string());
+        }
+        Ok(())
+    }
+
+    fn flush_partial(&mut self, lines: &mut Vec<String>) {
+        if !self.partial.is_empty() {
+            lines.push(String::from_utf8_lossy(&self.partial).to_string());
+            self.partial.clear();
+        }
+    }
+}
+
+type FileIdentity = (u64, u64);
+
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> Option<FileIdentity> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_metadata: &fs::Metadata) -> Option<FileIdentity> {
+    None
 }
 
-/// Read the last `n` lines from a file efficiently.
+/// Copy the first `len` bytes of `src` into a new file at `dest`.
+fn copy_segment(src: &mut fs::File, len: u64, dest: &Path, compress: bool) -> Result<()> {
+    let out = fs::File::create(dest)?;
+    let mut src = src.take(len);
+    if compress {
+        let mut encoder = GzEncoder::new(out, Compression::default());
+        io::copy(&mut src, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    } else {
+        let mut out = out;
+        io::copy(&mut src, &mut out)?;
+        out.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Rotated segments of `base_name` ("stdout" or "stderr") in `dir`, oldest
+/// first. Timestamp suffixes make name order match rotation order.
+fn rotated_segments(dir: &Path, base_name: &str) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let prefix = format!("{}_", base_name);
+
+    let mut rotated: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(&prefix) && (name.ends_with(".log") || name.ends_with(".log.gz"))
+        })
+        .map(|entry| entry.path())
+        .collect();
+    rotated.sort();
+    Ok(rotated)
+}
+
+/// Fill `lines` up to `n` entries with the newest lines of rotated segments.
+fn prepend_rotated(dir: &Path, base_name: &str, n: usize, lines: &mut Vec<String>) -> Result<()> {
+    for segment in rotated_segments(dir, base_name)?.iter().rev() {
+        if lines.len() >= n {
+            break;
+        }
+        let mut older = tail_file(segment, n - lines.len())?;
+        older.append(lines);
+        *lines = older;
+    }
+    Ok(())
+}
+
+/// Read the last `n` lines from a file efficiently. Gzipped segments are
+/// decompressed on the fly.
 fn tail_file(path: &Path, n: usize) -> Result<Vec<String>> {
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(GzDecoder::new(fs::File::open(path)?));
+        let mut tail = VecDeque::with_capacity(n);
+        for line in reader.lines().map_while(|l| l.ok()) {
+            if tail.len() == n {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+        return Ok(tail.into());
+    }
+
+    let mut file = fs::File::open(path)?;
+    let file_size = file.metadata()?.len();
+    tail_range(&mut file, file_size, n)
+}
+
+/// Read the last `n` lines from the first `file_size` bytes of `file`.
+fn tail_range(file: &mut fs::File, file_size: u64, n: usize) -> Result<Vec<String>> {
     if n == 0 {
         return Ok(Vec::new());
     }
 
-    let file = fs::File::open(path)?;
-    let metadata = file.metadata()?;
-    let file_size = metadata.len();
-
     if file_size == 0 {
         return Ok(Vec::new());
     }
 
     // For small files (< 64KB), just read the whole thing
     if file_size < 64 * 1024 {
-        let reader = BufReader::new(file);
-        let all_lines: Vec<String> = reader.lines().filter_map(|l| l.ok()).collect();
+        file.seek(SeekFrom::Start(0))?;
+        let reader = BufReader::new(file.take(file_size));
+        let all_lines: Vec<String> = reader.lines().map_while(|l| l.ok()).collect();
         let start = all_lines.len().saturating_sub(n);
         return Ok(all_lines[start..].to_vec());
     }
 
     // For larger files, read backwards in chunks
-    let mut file = file;
     let chunk_size: u64 = 8192;
     let mut remaining = file_size;
     let mut trailing_data = Vec::new();
@@ -153,7 +385,7 @@ fn tail_file(path: &Path, n: usize) -> Result<Vec<String>> {
 
         file.seek(SeekFrom::Start(remaining))?;
         let mut buf = vec![0u8; read_size as usize];
-        std::io::Read::read_exact(&mut file, &mut buf)?;
+        file.read_exact(&mut buf)?;
 
         // Prepend new chunk to trailing data
         buf.extend_from_slice(&trailing_data);
@@ -171,3 +403,190 @@ fn tail_file(path: &Path, n: usize) -> Result<Vec<String>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn append(path: &Path, text: &str) {
+        let mut f = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        f.write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn follow_returns_tail_then_new_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LogManager::new(dir.path());
+        let (stdout, _) = manager.setup_log_files("app").unwrap();
+        append(&stdout, "one\ntwo\nthree\n");
+
+        let (tail, mut follower) = manager.follow_logs("app", 2, false).unwrap();
+        assert_eq!(tail, vec!["two", "three"]);
+        assert!(follower.poll().unwrap().is_empty());
+
+        append(&stdout, "four\nfi");
+        assert_eq!(follower.poll().unwrap(), vec!["four"]);
+        append(&stdout, "ve\n");
+        assert_eq!(follower.poll().unwrap(), vec!["five"]);
+    }
+
+    #[test]
+    fn replicas_log_to_their_own_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LogManager::new(dir.path());
+        let (first, _) = manager.setup_log_files("app").unwrap();
+        let (third, _) = manager.setup_log_files("app:2").unwrap();
+        assert_eq!(first, dir.path().join("logs/app/stdout.log"));
+        assert_eq!(third, dir.path().join("logs/app/2/stdout.log"));
+
+        append(&third, "from replica 2\n");
+        assert!(manager.read_logs("app", 10, false).unwrap().is_empty());
+        assert_eq!(
+            manager.read_logs("app:2", 10, false).unwrap(),
+            vec!["from replica 2"]
+        );
+    }
+
+    #[test]
+    fn follow_waits_for_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LogManager::new(dir.path());
+        manager.setup_log_files("app").unwrap();
+
+        let (tail, mut follower) = manager.follow_logs("app", 10, true).unwrap();
+        assert!(tail.is_empty());
+        assert!(follower.poll().unwrap().is_empty());
+
+        append(&dir.path().join("logs/app/stderr.log"), "boom\n");
+        assert_eq!(follower.poll().unwrap(), vec!["boom"]);
+    }
+
+    #[test]
+    fn follow_survives_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.log");
+        append(&path, "old line\n");
+
+        let mut follower = LogFollower::new(path.clone());
+        fs::OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        append(&path, "new\n");
+        assert_eq!(follower.poll().unwrap(), vec!["new"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follow_survives_rename_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.log");
+        append(&path, "first\n");
+
+        let mut follower = LogFollower::new(path.clone());
+        append(&path, "before rotate\n");
+        fs::rename(&path, dir.path().join("out_1.log")).unwrap();
+        append(&path, "after rotate\n");
+
+        assert_eq!(
+            follower.poll().unwrap(),
+            vec!["before rotate", "after rotate"]
+        );
+    }
+
+    fn log_config(max_size_bytes: u64, retain_count: u32, compress_rotated: bool) -> LogConfig {
+        LogConfig {
+            max_size_bytes,
+            retain_count,
+            compress_rotated,
+        }
+    }
+
+    #[test]
+    fn rotation_truncates_in_place_and_keeps_writer_working() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = LogManager::new(dir.path());
+        let (stdout, _) = mgr.setup_log_files("app").unwrap();
+
+        // Simulates the daemon's inherited append-mode descriptor.
+        let mut writer = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&stdout)
+            .unwrap();
+        writer.write_all(b"one\ntwo\n").unwrap();
+
+        mgr.rotate_logs("app", &log_config(4, 5, false)).unwrap();
+        assert_eq!(fs::metadata(&stdout).unwrap().len(), 0);
+
+        writer.write_all(b"three\n").unwrap();
+        assert_eq!(fs::read_to_string(&stdout).unwrap(), "three\n");
+        assert_eq!(
+            mgr.read_logs("app", 10, false).unwrap(),
+            vec!["one", "two", "three"]
+        );
+    }
+
+    #[test]
+    fn rotation_skips_small_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = LogManager::new(dir.path());
+        let (stdout, _) = mgr.setup_log_files("app").unwrap();
+        append(&stdout, "small\n");
+
+        mgr.rotate_logs("app", &log_config(1024, 5, false)).unwrap();
+        let log_dir = dir.path().join("logs").join("app");
+        assert!(rotated_segments(&log_dir, "stdout").unwrap().is_empty());
+        assert_eq!(fs::read_to_string(&stdout).unwrap(), "small\n");
+    }
+
+    #[test]
+    fn compressed_segments_are_readable_and_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = LogManager::new(dir.path());
+        let (stdout, _) = mgr.setup_log_files("app").unwrap();
+        let log_dir = dir.path().join("logs").join("app");
+        let config = log_config(1, 2, true);
+
+        for i in 0..4 {
+            append(&stdout, &format!("line {}a\nline {}b\n", i, i));
+            mgr.rotate_logs("app", &config).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        append(&stdout, "live\n");
+
+        let segments = rotated_segments(&log_dir, "stdout").unwrap();
+        assert_eq!(segments.len(), 2);
+        assert!(segments
+            .iter()
+            .all(|p| p.to_string_lossy().ends_with(".log.gz")));
+
+        assert_eq!(
+            mgr.read_logs("app", 4, false).unwrap(),
+            vec!["line 2b", "line 3a", "line 3b", "live"]
+        );
+        assert_eq!(
+            mgr.read_logs("app", 100, false).unwrap(),
+            vec!["line 2a", "line 2b", "line 3a", "line 3b", "live"]
+        );
+    }
+
+    #[test]
+    fn follow_tail_includes_rotated_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = LogManager::new(dir.path());
+        let (stdout, _) = mgr.setup_log_files("app").unwrap();
+        append(&stdout, "old\n");
+        mgr.rotate_logs("app", &log_config(1, 5, false)).unwrap();
+        append(&stdout, "new\n");
+
+        let (tail, _) = mgr.follow_logs("app", 5, false).unwrap();
+        assert_eq!(tail, vec!["old", "new"]);
+    }
+}
@@ -1,23 +1,94 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
-use chrono::Utc;
-use tokio::sync::{broadcast, Mutex, RwLock};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
 use tracing::{error, info, warn};
-
-use crate::daemon::{DaemonInstance, DaemonSpec, HealthStatus};
+use uuid::Uuid;
+
+use crate::apply::{ApplyPlan, SpecChange};
+use crate::daemon::{
+    parse_replica_name, replica_name, CatchUp, DaemonInstance, DaemonSpec, DependencyCondition,
+    HealthCheckSpec, HealthCheckType, HealthStatus, LogConfig, ReadyMode, ScheduleOverlap,
+    SpecRevision, UnhealthyAction,
+};
+use crate::dependencies::DependencyGraph;
 use crate::error::{Result, SyspulseError};
+use crate::events::{DaemonEvent, EventFilter, EventKind};
 use crate::ipc::protocol::{Request, Response};
-use crate::ipc::server::IpcServer;
+use crate::ipc::server::{IpcServer, Reply};
 use crate::lifecycle::LifecycleState;
 use crate::logs::LogManager;
+use crate::notify::{NotifyMessage, NotifySocket, NOTIFY_SOCKET_ENV, WATCHDOG_USEC_ENV};
 use crate::paths;
-use crate::process::{self, ProcessDriver};
+use crate::process::{self, ExitStatus, ProcessDriver, ProcessExit};
 use crate::registry::Registry;
+use crate::resources::{
+    DaemonStats, LimitAction, ResourceLimits, ResourceSample, SoftLimitTracker, StatsHistory,
+};
 use crate::restart::RestartEvaluator;
-use crate::scheduler::Scheduler;
+use crate::runs::{DaemonRun, RunStatus, RunTrigger};
+use crate::scheduler::{self, JobSchedule, Scheduler};
+use crate::sockets::{BoundSocket, SocketTable};
+
+/// How often followed log files are checked for new output.
+const LOG_FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often running daemons' logs are checked against their `log_config`.
+const LOG_ROTATION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often CPU and memory of running daemons are sampled.
+const STATS_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Samples kept per daemon (five minutes at the sample interval).
+const STATS_HISTORY_LEN: usize = 150;
+
+/// How long `Start`/`Restart` with `wait` block when no timeout is given.
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a waited-for daemon is checked for readiness.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long to wait for a killed process to be reaped before giving up on
+/// its exit status.
+const REAP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of events returned by `Request::Events` when no limit is given.
+const DEFAULT_EVENT_LIMIT: usize = 50;
+
+/// Number of runs returned by `Request::Runs` when no limit is given.
+const DEFAULT_RUN_LIMIT: usize = 20;
+
+/// How often a queued run checks whether the previous run has finished.
+const RUN_QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Most missed runs a daemon with `catch_up = "all"` makes up on startup.
+const MAX_CATCH_UP_RUNS: usize = 100;
+
+/// Events buffered per subscriber before a slow one starts missing events.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// How long the new process of a daemon without a health check must stay up
+/// during a rolling restart before the old one is stopped.
+const ROLLING_SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// How often a notify socket's listener checks that its process is still the
+/// daemon's.
+const NOTIFY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the sockets of lazily started daemons are checked for
+/// connections.
+const SOCKET_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Minimum time between two starts of a daemon on connection, so that one
+/// which exits without accepting doesn't spin.
+const SOCKET_ACTIVATION_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Environment variable holding a process's replica index.
+pub const INSTANCE_ENV: &str = "SYSPULSE_INSTANCE";
 
 pub struct DaemonManager {
     registry: Arc<Mutex<Registry>>,
@@ -25,6 +96,14 @@ pub struct DaemonManager {
     log_manager: Arc<LogManager>,
     instances: Arc<RwLock<HashMap<String, DaemonInstance>>>,
     health_handles: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    sockets: Arc<Mutex<SocketTable>>,
+    notify_dir: PathBuf,
+    /// Cron jobs of scheduled daemons; set once `run` starts.
+    scheduler: Mutex<Option<Scheduler>>,
+    /// Instances with a run waiting for the previous one to finish.
+    queued_runs: Arc<Mutex<HashSet<String>>>,
+    stats: Arc<RwLock<StatsHistory>>,
+    events: EventLog,
     shutdown_tx: broadcast::Sender<()>,
 }
 
@@ -44,33 +123,94 @@ impl DaemonManager {
         let saved_states = registry.list_states().unwrap_or_default();
         let mut instances = HashMap::new();
         for inst in saved_states {
-            instances.insert(inst.spec_name.clone(), inst);
+            instances.insert(inst.name(), inst);
         }
 
+        let registry = Arc::new(Mutex::new(registry));
         Ok(Self {
-            registry: Arc::new(Mutex::new(registry)),
+            events: EventLog::new(Arc::clone(&registry)),
+            registry,
             process_driver: Arc::from(process_driver),
             log_manager: Arc::new(log_manager),
             instances: Arc::new(RwLock::new(instances)),
             health_handles: Arc::new(Mutex::new(HashMap::new())),
+            sockets: Arc::new(Mutex::new(SocketTable::new())),
+            notify_dir: data.join("notify"),
+            scheduler: Mutex::new(None),
+            queued_runs: Arc::new(Mutex::new(HashSet::new())),
+            stats: Arc::new(RwLock::new(StatsHistory::new(STATS_HISTORY_LEN))),
             shutdown_tx,
         })
     }
 
-    /// Start a daemon by name.
-    pub async fn start_daemon(&self, name: &str) -> Result<DaemonInstance> {
+    /// The instances a request for `name` acts on: a single replica if
+    /// `name` carries an index (`worker:2`), otherwise all of the daemon's
+    /// replicas.
+    async fn targets(&self, name: &str) -> Result<Vec<String>> {
+        let (daemon, replica, explicit) = parse_replica_name(name);
+        let replicas = {
+            let reg = self.registry.lock().await;
+            reg.get_spec(daemon)?.replicas
+        };
+        if !explicit {
+            return Ok((0..replicas).map(|r| replica_name(daemon, r)).collect());
+        }
+        if replica >= replicas {
+            return Err(SyspulseError::DaemonNotFound(name.to_string()));
+        }
+        Ok(vec![replica_name(daemon, replica)])
+    }
+
+    /// Whether any replica of the daemon `name` is active.
+    async fn is_active(&self, name: &str) -> bool {
+        let instances = self.instances.read().await;
+        instances
+            .values()
+            .any(|i| i.spec_name == name && i.state.is_active())
+    }
+
+    /// Start a daemon by name: all of its replicas that are not running yet,
+    /// or the single replica `name:index` names.
+    pub async fn start_daemon(&self, name: &str) -> Result<Vec<DaemonInstance>> {
+        let targets = self.targets(name).await?;
+        let inactive: Vec<String> = {
+            let instances = self.instances.read().await;
+            targets
+                .iter()
+                .filter(|t| !instances.get(*t).is_some_and(|i| i.state.is_active()))
+                .cloned()
+                .collect()
+        };
+        if inactive.is_empty() {
+            // Everything is running already; let the first replica say so.
+            return Ok(vec![self.start_instance(&targets[0]).await?]);
+        }
+
+        let mut started = Vec::new();
+        for target in inactive {
+            started.push(self.start_instance(&target).await?);
+        }
+        Ok(started)
+    }
+
+    /// Start a single replica.
+    async fn start_instance(&self, name: &str) -> Result<DaemonInstance> {
+        let (daemon, replica, _) = parse_replica_name(name);
         let spec = {
             let reg = self.registry.lock().await;
-            reg.get_spec(name)?
+            reg.get_spec(daemon)?
         };
 
         // Get or create the instance.
         let mut instances = self.instances.write().await;
         let instance = instances
             .entry(name.to_string())
-            .or_insert_with(|| DaemonInstance::new(name));
+            .or_insert_with(|| DaemonInstance::replica(daemon, replica));
 
         // Validate state transition.
+        if instance.state == LifecycleState::CrashLooping {
+            return Err(SyspulseError::CrashLooping(name.to_string()));
+        }
         let new_state = instance.state.transition_to(LifecycleState::Starting)?;
         instance.state = new_state;
 
@@ -80,16 +220,32 @@ impl DaemonManager {
         instance.stderr_log = Some(stderr_path.clone());
 
         // Spawn the process.
+        let mut process_spec = replica_spec(&spec, replica);
+        let notify = bind_notify_socket(&self.notify_dir, name, &mut process_spec)?;
+        let sockets = bound_sockets(&self.sockets, &spec).await?;
         let proc_info = self
             .process_driver
-            .spawn(&spec, &stdout_path, &stderr_path)
+            .spawn(name, &process_spec, &stdout_path, &stderr_path, &sockets)
             .await?;
 
         instance.pid = Some(proc_info.pid);
+        instance.pid_start_time = self
+            .process_driver
+            .identity(proc_info.pid)
+            .await
+            .map(|identity| identity.start_time);
+        set_notify_socket(instance, notify.as_ref());
+        instance.warnings = proc_info.warnings;
         instance.started_at = Some(Utc::now());
         instance.stopped_at = None;
         instance.exit_code = None;
-        instance.state = instance.state.transition_to(LifecycleState::Running)?;
+        instance.exit_signal = None;
+        instance.status_message = None;
+        instance.last_watchdog = None;
+        // Otherwise it stays Starting until it reports ready.
+        if spec.ready_mode == ReadyMode::Immediate {
+            instance.state = instance.state.transition_to(LifecycleState::Running)?;
+        }
 
         if spec.health_check.is_some() {
             instance.health_status = HealthStatus::Unknown;
@@ -108,21 +264,16 @@ impl DaemonManager {
         // Drop the write lock before spawning health check.
         drop(instances);
 
+        self.events
+            .record(name, EventKind::Started { pid: proc_info.pid }, None)
+            .await;
+        if let Some(socket) = notify {
+            self.spawn_notify_listener(name, proc_info.pid, socket);
+        }
+
         // Start health check background task if configured.
         if let Some(ref health_spec) = spec.health_check {
-            let daemon_name = name.to_string();
-            let shutdown_rx = self.shutdown_tx.subscribe();
-            let instances = Arc::clone(&self.instances);
-            let registry = Arc::clone(&self.registry);
-            let health_spec: crate::daemon::HealthCheckSpec = health_spec.clone();
-
-            let handle = tokio::spawn(async move {
-                Self::run_health_check(instances, registry, daemon_name, health_spec, shutdown_rx)
-                    .await;
-            });
-
-            let mut handles = self.health_handles.lock().await;
-            handles.insert(name.to_string(), handle);
+            self.spawn_health_check(name, health_spec).await;
         }
 
         info!(
@@ -133,8 +284,98 @@ impl DaemonManager {
         Ok(result)
     }
 
-    /// Stop a running daemon.
-    pub async fn stop_daemon(&self, name: &str, force: bool) -> Result<DaemonInstance> {
+    /// Run `health_spec` against the daemon in the background until it stops.
+    async fn spawn_health_check(&self, name: &str, health_spec: &HealthCheckSpec) {
+        let ready_on_healthy = {
+            let reg = self.registry.lock().await;
+            reg.get_spec(parse_replica_name(name).0)
+                .is_ok_and(|spec| spec.ready_mode == ReadyMode::Health)
+        };
+        let daemon_name = name.to_string();
+        let shutdown_rx = self.shutdown_tx.subscribe();
+        let instances = Arc::clone(&self.instances);
+        let registry = Arc::clone(&self.registry);
+        let events = self.events.clone();
+        let health_spec = health_spec.clone();
+
+        let handle = tokio::spawn(async move {
+            Self::run_health_check(
+                instances,
+                registry,
+                events,
+                daemon_name,
+                health_spec,
+                ready_on_healthy,
+                shutdown_rx,
+            )
+            .await;
+        });
+
+        let mut handles = self.health_handles.lock().await;
+        if let Some(previous) = handles.insert(name.to_string(), handle) {
+            previous.abort();
+        }
+    }
+
+    /// Apply what process `pid` of the instance `name` sends to its notify
+    /// socket, in the background.
+    fn spawn_notify_listener(&self, name: &str, pid: u32, socket: NotifySocket) {
+        tokio::spawn(listen_for_notifications(
+            Arc::clone(&self.instances),
+            Arc::clone(&self.registry),
+            name.to_string(),
+            pid,
+            socket,
+            self.shutdown_tx.subscribe(),
+        ));
+    }
+
+    /// Stop a running daemon: all of its active replicas, or the single
+    /// replica `name:index` names. `timeout_secs` overrides the spec's
+    /// `stop_timeout_secs` for this call.
+    pub async fn stop_daemon(
+        &self,
+        name: &str,
+        force: bool,
+        timeout_secs: Option<u64>,
+    ) -> Result<Vec<DaemonInstance>> {
+        let targets = self.targets(name).await?;
+        let active: Vec<String> = {
+            let instances = self.instances.read().await;
+            targets
+                .iter()
+                .filter(|t| instances.get(*t).is_some_and(|i| i.state.is_active()))
+                .cloned()
+                .collect()
+        };
+        if active.is_empty() {
+            // Nothing is running; let the first replica say why.
+            let instance = self
+                .stop_instance_with_reason(&targets[0], force, timeout_secs, None)
+                .await?;
+            return Ok(vec![instance]);
+        }
+
+        let mut stopped = Vec::new();
+        for target in active {
+            stopped.push(
+                self.stop_instance_with_reason(&target, force, timeout_secs, None)
+                    .await?,
+            );
+        }
+        Ok(stopped)
+    }
+
+    /// Stop a single running replica, on the manager's own initiative if
+    /// `reason` is given, recording it on the instance and in its event
+    /// history.
+    async fn stop_instance_with_reason(
+        &self,
+        name: &str,
+        force: bool,
+        timeout_secs: Option<u64>,
+        reason: Option<String>,
+    ) -> Result<DaemonInstance> {
         let mut instances = self.instances.write().await;
         let instance = instances
             .get_mut(name)
@@ -148,6 +389,9 @@ impl DaemonManager {
         }
 
         instance.state = instance.state.transition_to(LifecycleState::Stopping)?;
+        if reason.is_some() {
+            instance.reason = reason.clone();
+        }
 
         // Cancel health check task.
         {
@@ -162,81 +406,641 @@ impl DaemonManager {
             if force {
                 self.process_driver.kill(pid).await?;
             } else {
-                let timeout = {
-                    let reg = self.registry.lock().await;
-                    reg.get_spec(name)
-                        .map(|s| s.stop_timeout_secs)
-                        .unwrap_or(30)
+                let timeout = match timeout_secs {
+                    Some(timeout) => timeout,
+                    None => {
+                        let reg = self.registry.lock().await;
+                        reg.get_spec(&instance.spec_name)
+                            .map(|s| s.stop_timeout_secs)
+                            .unwrap_or(30)
+                    }
                 };
                 self.process_driver.stop(pid, timeout).await?;
             }
-            // Try to get exit code.
-            let exit_code = self.process_driver.wait(pid).await.ok().flatten();
-            instance.exit_code = exit_code;
+            let status = reap(self.process_driver.as_ref(), pid).await;
+            instance.exit_code = status.and_then(ExitStatus::code);
+            instance.exit_signal = status.and_then(ExitStatus::signal);
         }
 
         instance.state = instance.state.transition_to(LifecycleState::Stopped)?;
         instance.stopped_at = Some(Utc::now());
         instance.pid = None;
+        instance.pid_start_time = None;
         instance.health_status = HealthStatus::Unknown;
 
         // Persist state.
         {
             let reg = self.registry.lock().await;
             reg.update_state(instance)?;
+            reg.finish_run(
+                name,
+                RunStatus::Stopped,
+                instance.exit_code,
+                instance.exit_signal,
+            )?;
         }
+        self.events
+            .record(
+                name,
+                EventKind::Stopped {
+                    exit_code: instance.exit_code,
+                    signal: instance.exit_signal,
+                },
+                reason,
+            )
+            .await;
 
         info!("Stopped daemon '{}'", name);
         Ok(instance.clone())
     }
 
-    /// Restart a daemon (stop then start).
-    pub async fn restart_daemon(&self, name: &str, force: bool) -> Result<DaemonInstance> {
-        // Only stop if active.
+    /// Restart a daemon (stop then start), all replicas at once.
+    pub async fn restart_daemon(&self, name: &str, force: bool) -> Result<Vec<DaemonInstance>> {
+        let targets = self.targets(name).await?;
+        // Only stop what is active.
+        for target in &targets {
+            let active = {
+                let instances = self.instances.read().await;
+                instances.get(target).is_some_and(|i| i.state.is_active())
+            };
+            if active {
+                self.stop_instance_with_reason(target, force, None, None)
+                    .await?;
+            }
+        }
+
+        let mut started = Vec::new();
+        for target in &targets {
+            started.push(self.start_instance(target).await?);
+        }
+        Ok(started)
+    }
+
+    /// Restart a daemon one replica at a time, starting each new process
+    /// before stopping the old one. The old process is only stopped once the
+    /// new one passes its health check (or, without a health check, stays up
+    /// for `ROLLING_SETTLE_TIME`); otherwise the new process is stopped, the
+    /// old one keeps running and the remaining replicas are left alone.
+    /// Replicas that aren't running are simply started. Refused for daemons
+    /// whose readiness can't be told apart from the old process's, see
+    /// [`check_rolling`].
+    pub async fn rolling_restart_daemon(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<Vec<DaemonInstance>> {
         {
-            let instances = self.instances.read().await;
-            if let Some(inst) = instances.get(name) {
-                if inst.state.is_active() {
-                    drop(instances);
-                    self.stop_daemon(name, force).await?;
+            let reg = self.registry.lock().await;
+            check_rolling(&reg.get_spec(parse_replica_name(name).0)?)?;
+        }
+        let targets = self.targets(name).await?;
+        let mut restarted = Vec::new();
+        for target in &targets {
+            let running = {
+                let instances = self.instances.read().await;
+                instances
+                    .get(target)
+                    .is_some_and(|i| i.state == LifecycleState::Running && i.pid.is_some())
+            };
+            let instance = if running {
+                self.replace_instance(target, timeout).await?
+            } else {
+                self.start_instance(target).await?
+            };
+            restarted.push(instance);
+        }
+        Ok(restarted)
+    }
+
+    /// Replace the process of a running replica with a new one, see
+    /// [`rolling_restart_daemon`](Self::rolling_restart_daemon).
+    async fn replace_instance(&self, name: &str, timeout: Duration) -> Result<DaemonInstance> {
+        let (daemon, replica, _) = parse_replica_name(name);
+        let spec = {
+            let reg = self.registry.lock().await;
+            reg.get_spec(daemon)?
+        };
+        let old_pid = self
+            .status(name)
+            .await?
+            .pid
+            .ok_or_else(|| SyspulseError::Process(format!("Daemon '{}' has no PID", name)))?;
+
+        let (stdout_path, stderr_path) = self.log_manager.setup_log_files(name)?;
+        let mut process_spec = replica_spec(&spec, replica);
+        let notify = bind_notify_socket(&self.notify_dir, name, &mut process_spec)?;
+        let sockets = bound_sockets(&self.sockets, &spec).await?;
+        let proc_info = self
+            .process_driver
+            .spawn(name, &process_spec, &stdout_path, &stderr_path, &sockets)
+            .await?;
+        let new_pid = proc_info.pid;
+        info!(
+            "Rolling restart of daemon '{}': started PID {} next to PID {}",
+            name, new_pid, old_pid
+        );
+
+        let outcome = match self
+            .probe_new_process(&spec, new_pid, timeout, notify.as_ref())
+            .await
+        {
+            Ok(()) => {
+                let mut instances = self.instances.write().await;
+                match instances.get_mut(name) {
+                    // Stopped or restarted by someone else in the meantime.
+                    Some(inst)
+                        if inst.state == LifecycleState::Running && inst.pid == Some(old_pid) =>
+                    {
+                        inst.pid = Some(new_pid);
+                        inst.pid_start_time = self
+                            .process_driver
+                            .identity(new_pid)
+                            .await
+                            .map(|identity| identity.start_time);
+                        set_notify_socket(inst, notify.as_ref());
+                        inst.warnings = proc_info.warnings;
+                        inst.started_at = Some(Utc::now());
+                        inst.status_message = None;
+                        inst.last_watchdog = None;
+                        inst.stdout_log = Some(stdout_path);
+                        inst.stderr_log = Some(stderr_path);
+                        inst.health_status = if spec.health_check.is_some() {
+                            HealthStatus::Healthy
+                        } else {
+                            HealthStatus::NotConfigured
+                        };
+                        let reg = self.registry.lock().await;
+                        if let Err(e) = reg.update_state(inst) {
+                            warn!("Failed to persist state of daemon '{}': {}", name, e);
+                        }
+                        Ok(inst.clone())
+                    }
+                    _ => Err(SyspulseError::Process(format!(
+                        "Daemon '{}' changed state during its rolling restart",
+                        name
+                    ))),
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        let instance = match outcome {
+            Ok(instance) => instance,
+            Err(e) => {
+                warn!(
+                    "Rolling restart of daemon '{}' failed, keeping PID {}: {}",
+                    name, old_pid, e
+                );
+                if self.process_driver.is_alive(new_pid).await {
+                    self.process_driver
+                        .stop(new_pid, spec.stop_timeout_secs)
+                        .await?;
+                }
+                reap(self.process_driver.as_ref(), new_pid).await;
+                return Err(SyspulseError::Process(format!(
+                    "Rolled back the restart of daemon '{}', PID {} is still running: {}",
+                    name, old_pid, e
+                )));
+            }
+        };
+
+        self.events
+            .record(
+                name,
+                EventKind::Started { pid: new_pid },
+                Some(format!("rolling restart, replacing PID {}", old_pid)),
+            )
+            .await;
+        if let Some(socket) = notify {
+            self.spawn_notify_listener(name, new_pid, socket);
+        }
+        if let Some(ref health_spec) = spec.health_check {
+            self.spawn_health_check(name, health_spec).await;
+        }
+
+        // The old process is no longer the instance's, so its exit is only
+        // collected here.
+        self.process_driver
+            .stop(old_pid, spec.stop_timeout_secs)
+            .await?;
+        let status = reap(self.process_driver.as_ref(), old_pid).await;
+        self.events
+            .record(
+                name,
+                EventKind::Stopped {
+                    exit_code: status.and_then(ExitStatus::code),
+                    signal: status.and_then(ExitStatus::signal),
+                },
+                Some(format!("replaced by PID {}", new_pid)),
+            )
+            .await;
+
+        info!(
+            "Rolling restart of daemon '{}': PID {} replaced PID {}",
+            name, new_pid, old_pid
+        );
+        Ok(instance)
+    }
+
+    /// Wait for a process started by a rolling restart to become ready: to
+    /// send `READY=1` to `notify` with `ready_mode = "notify"`, otherwise to
+    /// pass the daemon's health check, or to stay up for
+    /// `ROLLING_SETTLE_TIME` if it has none.
+    async fn probe_new_process(
+        &self,
+        spec: &DaemonSpec,
+        pid: u32,
+        timeout: Duration,
+        notify: Option<&NotifySocket>,
+    ) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let exited =
+            || SyspulseError::Process(format!("new process {} exited before becoming ready", pid));
+
+        if let Some(socket) = notify.filter(|_| spec.ready_mode == ReadyMode::Notify) {
+            loop {
+                tokio::select! {
+                    received = socket.recv() => {
+                        if received?.contains(&NotifyMessage::Ready) {
+                            return Ok(());
+                        }
+                    }
+                    _ = tokio::time::sleep(WAIT_POLL_INTERVAL) => {}
+                }
+                if !self.process_driver.is_alive(pid).await {
+                    return Err(exited());
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(SyspulseError::Timeout(timeout));
+                }
+            }
+        }
+
+        let Some(ref health_spec) = spec.health_check else {
+            let settled = tokio::time::Instant::now() + ROLLING_SETTLE_TIME;
+            while tokio::time::Instant::now() < settled {
+                if !self.process_driver.is_alive(pid).await {
+                    return Err(exited());
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(SyspulseError::Timeout(timeout));
+                }
+                tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+            }
+            return Ok(());
+        };
+
+        let checker = crate::health::create_checker(health_spec.clone());
+        let mut next_check =
+            tokio::time::Instant::now() + Duration::from_secs(health_spec.start_period_secs);
+        loop {
+            if !self.process_driver.is_alive(pid).await {
+                return Err(exited());
+            }
+            if tokio::time::Instant::now() >= next_check {
+                if let Ok(HealthStatus::Healthy) = checker.check().await {
+                    return Ok(());
                 }
+                next_check =
+                    tokio::time::Instant::now() + Duration::from_secs(health_spec.interval_secs);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(SyspulseError::Timeout(timeout));
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Start a daemon after starting, in dependency order, everything it
+    /// depends on that is not already running.
+    pub async fn start_with_dependencies(&self, name: &str) -> Result<Vec<DaemonInstance>> {
+        let daemon = parse_replica_name(name).0;
+        let order = {
+            let reg = self.registry.lock().await;
+            let specs = reg.list_specs()?;
+            DependencyGraph::new(&specs).with_dependencies(daemon)?
+        };
+
+        for dep in order.iter().filter(|n| n.as_str() != daemon) {
+            if !self.is_active(dep).await {
+                self.wait_for_dependencies(dep).await?;
+                info!("Starting '{}' as a dependency of '{}'", dep, name);
+                self.start_daemon(dep).await?;
             }
         }
+        self.wait_for_dependencies(daemon).await?;
         self.start_daemon(name).await
     }
 
+    /// Wait for the dependencies of `name` that must be healthy before it
+    /// starts.
+    async fn wait_for_dependencies(&self, name: &str) -> Result<()> {
+        let spec = {
+            let reg = self.registry.lock().await;
+            reg.get_spec(name)?
+        };
+        for dep in &spec.depends_on {
+            match dep.condition {
+                DependencyCondition::Healthy => {
+                    self.wait_until_ready(&dep.name, DEFAULT_WAIT_TIMEOUT)
+                        .await
+                        .map_err(|e| {
+                            SyspulseError::Process(format!(
+                                "Dependency '{}' of '{}' is not healthy: {}",
+                                dep.name, name, e
+                            ))
+                        })?;
+                }
+                // A dependency still starting has to report ready first.
+                DependencyCondition::Started => {
+                    let starting = {
+                        let instances = self.instances.read().await;
+                        instances
+                            .values()
+                            .any(|i| i.spec_name == dep.name && i.state == LifecycleState::Starting)
+                    };
+                    if starting {
+                        self.wait_until_started(&dep.name, DEFAULT_WAIT_TIMEOUT)
+                            .await
+                            .map_err(|e| {
+                                SyspulseError::Process(format!(
+                                    "Dependency '{}' of '{}' did not start: {}",
+                                    dep.name, name, e
+                                ))
+                            })?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// All registered daemons, dependencies before their dependents. Falls
+    /// back to name order if the stored specs contain a cycle.
+    async fn start_order(&self) -> Vec<String> {
+        let specs = {
+            let reg = self.registry.lock().await;
+            reg.list_specs().unwrap_or_default()
+        };
+        DependencyGraph::new(&specs)
+            .start_order()
+            .unwrap_or_else(|e| {
+                warn!("Ignoring daemon dependencies: {}", e);
+                let mut names: Vec<String> = specs.iter().map(|s| s.name.clone()).collect();
+                names.sort();
+                names
+            })
+    }
+
+    /// Wait until a started daemon is running and, if it has a health check,
+    /// reported healthy. Fails if the daemon exits first or `timeout` elapses.
+    /// A daemon with several replicas is ready once all of them are.
+    pub async fn wait_until_ready(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<Vec<DaemonInstance>> {
+        self.wait_for_targets(name, timeout, true).await
+    }
+
+    /// Wait until a started daemon has left `Starting`, i.e. reported ready
+    /// according to its `ready_mode`.
+    async fn wait_until_started(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<Vec<DaemonInstance>> {
+        self.wait_for_targets(name, timeout, false).await
+    }
+
+    async fn wait_for_targets(
+        &self,
+        name: &str,
+        timeout: Duration,
+        healthy: bool,
+    ) -> Result<Vec<DaemonInstance>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut ready = Vec::new();
+        for target in self.targets(name).await? {
+            ready.push(
+                self.wait_for_instance(&target, timeout, deadline, healthy)
+                    .await?,
+            );
+        }
+        Ok(ready)
+    }
+
+    async fn wait_for_instance(
+        &self,
+        name: &str,
+        timeout: Duration,
+        deadline: tokio::time::Instant,
+        healthy: bool,
+    ) -> Result<DaemonInstance> {
+        loop {
+            let inst = self.status(name).await?;
+            match readiness(&inst, healthy) {
+                Readiness::Ready => return Ok(inst),
+                Readiness::Exited => {
+                    return Err(SyspulseError::Process(format!(
+                        "Daemon '{}' exited before becoming ready",
+                        name
+                    )));
+                }
+                Readiness::Pending => {}
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(SyspulseError::Timeout(timeout));
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+    }
+
     /// Get the current status of a daemon.
     pub async fn status(&self, name: &str) -> Result<DaemonInstance> {
-        let instances = self.instances.read().await;
-        instances
-            .get(name)
-            .cloned()
-            .ok_or_else(|| SyspulseError::DaemonNotFound(name.to_string()))
+        let mut instance = {
+            let instances = self.instances.read().await;
+            instances
+                .get(name)
+                .cloned()
+                .ok_or_else(|| SyspulseError::DaemonNotFound(name.to_string()))?
+        };
+        self.fill_next_runs(std::slice::from_mut(&mut instance))
+            .await;
+        Ok(instance)
     }
 
-    /// List all daemon instances.
+    /// List all daemon instances, replicas of a daemon together.
     pub async fn list(&self) -> Result<Vec<DaemonInstance>> {
+        let mut list: Vec<DaemonInstance> = {
+            let instances = self.instances.read().await;
+            instances.values().cloned().collect()
+        };
+        list.sort_by(|a, b| (&a.spec_name, a.replica).cmp(&(&b.spec_name, b.replica)));
+        self.fill_next_runs(&mut list).await;
+        Ok(list)
+    }
+
+    /// Set `next_run_at` of scheduled instances from their cron jobs.
+    async fn fill_next_runs(&self, instances: &mut [DaemonInstance]) {
+        let mut scheduler = self.scheduler.lock().await;
+        let Some(scheduler) = scheduler.as_mut() else {
+            return;
+        };
+        for instance in instances {
+            instance.next_run_at = scheduler.next_run(&instance.spec_name).await.ok().flatten();
+        }
+    }
+
+    /// Recent resource usage of one daemon, or of every running daemon.
+    pub async fn stats(&self, name: Option<&str>) -> Result<Vec<DaemonStats>> {
         let instances = self.instances.read().await;
-        Ok(instances.values().cloned().collect())
+        let history = self.stats.read().await;
+
+        let mut stats: Vec<DaemonStats> = match name {
+            Some(name) => {
+                let targets = self.targets(name).await?;
+                targets
+                    .into_iter()
+                    .map(|target| DaemonStats {
+                        pid: instances.get(&target).and_then(|inst| inst.pid),
+                        samples: history.get(&target),
+                        name: target,
+                    })
+                    .collect()
+            }
+            None => instances
+                .iter()
+                .filter(|(_, inst)| inst.state == LifecycleState::Running)
+                .map(|(name, inst)| DaemonStats {
+                    name: name.clone(),
+                    pid: inst.pid,
+                    samples: history.get(name),
+                })
+                .collect(),
+        };
+        stats.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(stats)
+    }
+
+    /// Recorded lifecycle events, oldest first.
+    pub async fn events(
+        &self,
+        name: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<DaemonEvent>> {
+        let reg = self.registry.lock().await;
+        if let Some(name) = name {
+            reg.get_spec(parse_replica_name(name).0)?;
+        }
+        reg.list_events(name, since, limit.unwrap_or(DEFAULT_EVENT_LIMIT))
+    }
+
+    /// Run a scheduled daemon now, the way its schedule firing would,
+    /// including its `schedule_overlap`. Returns the instances started;
+    /// replicas whose previous run was still going are left out.
+    pub async fn trigger(&self, name: &str) -> Result<Vec<DaemonInstance>> {
+        let spec = {
+            let reg = self.registry.lock().await;
+            reg.get_spec(name)?
+        };
+        if !spec.is_scheduled() {
+            return Err(SyspulseError::Config(format!(
+                "Daemon '{}' has no schedule; use start instead",
+                name
+            )));
+        }
+        info!("Manual trigger: starting daemon '{}'", name);
+        trigger_run(&self.components(), name, RunTrigger::Manual).await
+    }
+
+    /// Clear the failed or crash-looping state of a daemon (or one replica)
+    /// so it can be started again, and start its restart count over.
+    /// Returns the names of the instances that were failed.
+    pub async fn reset_failed(&self, name: &str) -> Result<Vec<String>> {
+        let (daemon, replica, single) = parse_replica_name(name);
+        let spec = {
+            let reg = self.registry.lock().await;
+            reg.get_spec(daemon)?
+        };
+
+        let mut matched = false;
+        let mut reset = Vec::new();
+        let mut instances = self.instances.write().await;
+        for inst in instances
+            .values_mut()
+            .filter(|i| i.spec_name == daemon && (!single || i.replica == replica))
+        {
+            matched = true;
+            inst.restart_count = 0;
+            inst.recent_restarts.clear();
+            if matches!(
+                inst.state,
+                LifecycleState::Failed | LifecycleState::CrashLooping
+            ) {
+                inst.state = if spec.is_scheduled() {
+                    LifecycleState::Scheduled
+                } else {
+                    LifecycleState::Stopped
+                };
+                inst.reason = None;
+                reset.push(inst.name());
+            }
+            self.registry.lock().await.update_state(inst)?;
+        }
+        if single && !matched {
+            return Err(SyspulseError::DaemonNotFound(name.to_string()));
+        }
+        reset.sort();
+        info!("Reset failed state of '{}'", name);
+        Ok(reset)
+    }
+
+    /// The latest runs of a scheduled daemon, oldest first.
+    pub async fn runs(&self, name: &str, limit: Option<usize>) -> Result<Vec<DaemonRun>> {
+        let reg = self.registry.lock().await;
+        reg.get_spec(parse_replica_name(name).0)?;
+        reg.list_runs(name, limit.unwrap_or(DEFAULT_RUN_LIMIT))
     }
 
     /// Register a new daemon spec.
     pub async fn add_daemon(&self, spec: DaemonSpec) -> Result<()> {
+        spec.validate()?;
         let name = spec.name.clone();
 
         {
             let reg = self.registry.lock().await;
+            let mut specs = reg.list_specs()?;
+            specs.retain(|s| s.name != name);
+            specs.push(spec.clone());
+            DependencyGraph::new(&specs).check_cycles()?;
             reg.register(&spec)?;
         }
 
-        // Initialize instance in Stopped state (or Scheduled if it has a cron).
-        let mut instance = DaemonInstance::new(&name);
-        if spec.schedule.is_some() {
-            instance.state = LifecycleState::Scheduled;
+        // Listen before anything can start the daemon, and fail the add if
+        // an address is taken.
+        if let Err(e) = self.sockets.lock().await.bind(&spec) {
+            let reg = self.registry.lock().await;
+            reg.unregister(&name)?;
+            return Err(e);
+        }
+        if let Err(e) = self.schedule_daemon(&spec).await {
+            self.sockets.lock().await.unbind(&name);
+            let reg = self.registry.lock().await;
+            reg.unregister(&name)?;
+            return Err(e);
         }
 
+        // Initialize instances in Stopped state (or Scheduled if it has a cron).
         let mut instances = self.instances.write().await;
-        instances.insert(name.clone(), instance);
+        for replica in 0..spec.replicas {
+            let mut instance = DaemonInstance::replica(&name, replica);
+            if spec.is_scheduled() {
+                instance.state = LifecycleState::Scheduled;
+            }
+            instances.insert(instance.name(), instance);
+        }
 
         info!("Added daemon '{}'", name);
         Ok(())
@@ -244,32 +1048,15 @@ impl DaemonManager {
 
     /// Remove a daemon. If `force` is true, stop it first if running.
     pub async fn remove_daemon(&self, name: &str, force: bool) -> Result<()> {
-        // Check if running.
-        {
-            let instances = self.instances.read().await;
-            if let Some(inst) = instances.get(name) {
-                if inst.state.is_active() {
-                    if !force {
-                        return Err(SyspulseError::Process(format!(
-                            "Daemon '{}' is still running. Use force to stop and remove.",
-                            name
-                        )));
-                    }
-                }
-            }
-        }
-
-        // Stop if active and force.
-        if force {
-            let instances = self.instances.read().await;
-            let is_active = instances
-                .get(name)
-                .map(|i| i.state.is_active())
-                .unwrap_or(false);
-            drop(instances);
-            if is_active {
-                self.stop_daemon(name, true).await?;
+        // Check if running; stop if active and force.
+        if self.is_active(name).await {
+            if !force {
+                return Err(SyspulseError::Process(format!(
+                    "Daemon '{}' is still running. Use force to stop and remove.",
+                    name
+                )));
             }
+            self.stop_daemon(name, true, None).await?;
         }
 
         // Unregister.
@@ -277,18 +1064,35 @@ impl DaemonManager {
             let reg = self.registry.lock().await;
             reg.unregister(name)?;
         }
+        self.sockets.lock().await.unbind(name);
+        if let Some(scheduler) = self.scheduler.lock().await.as_mut() {
+            if let Err(e) = scheduler.unschedule_daemon(name).await {
+                warn!("Failed to unschedule daemon '{}': {}", name, e);
+            }
+        }
 
         // Remove from in-memory map.
-        {
+        let removed: Vec<String> = {
             let mut instances = self.instances.write().await;
-            instances.remove(name);
-        }
+            let keys: Vec<String> = instances
+                .iter()
+                .filter(|(_, inst)| inst.spec_name == name)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in &keys {
+                instances.remove(key);
+                self.process_driver.forget(key);
+            }
+            keys
+        };
 
         // Cancel any health check.
         {
             let mut handles = self.health_handles.lock().await;
-            if let Some(handle) = handles.remove(name) {
-                handle.abort();
+            for key in &removed {
+                if let Some(handle) = handles.remove(key) {
+                    handle.abort();
+                }
             }
         }
 
@@ -296,7 +1100,276 @@ impl DaemonManager {
         Ok(())
     }
 
-    /// Read logs for a daemon.
+    /// Make the registered daemons match `specs`. Daemons missing from
+    /// `specs` are stopped and removed, new ones are added (but not
+    /// started), and changed specs are updated in place. A running daemon is
+    /// restarted only if a field in `apply::RESTART_FIELDS` changed.
+    pub async fn apply(
+        &self,
+        specs: Vec<DaemonSpec>,
+        dry_run: bool,
+        prune: bool,
+    ) -> Result<ApplyPlan> {
+        let mut seen = HashSet::new();
+        for spec in &specs {
+            spec.validate()?;
+            if !seen.insert(spec.name.as_str()) {
+                return Err(SyspulseError::Config(format!(
+                    "Daemon '{}' is defined more than once",
+                    spec.name
+                )));
+            }
+        }
+
+        // Check the daemons as they will be after the apply, including the
+        // registered ones the file leaves alone, before changing anything.
+        let plan = {
+            let reg = self.registry.lock().await;
+            let current = reg.list_specs()?;
+            let plan = ApplyPlan::new(&current, &specs, prune)?;
+            let mut merged: Vec<DaemonSpec> = current
+                .into_iter()
+                .filter(|s| !seen.contains(s.name.as_str()) && !plan.removed.contains(&s.name))
+                .collect();
+            merged.extend(specs.iter().cloned());
+            DependencyGraph::new(&merged).check_cycles()?;
+            plan
+        };
+        if dry_run {
+            return Ok(plan);
+        }
+
+        // Dependents go before the daemons they depend on.
+        let removals: Vec<String> = self
+            .start_order()
+            .await
+            .into_iter()
+            .rev()
+            .filter(|name| plan.removed.contains(name))
+            .collect();
+        for name in removals {
+            if self.is_active(&name).await {
+                self.stop_daemon(&name, false, None).await?;
+            }
+            self.remove_daemon(&name, false).await?;
+        }
+
+        let mut specs: HashMap<String, DaemonSpec> =
+            specs.into_iter().map(|s| (s.name.clone(), s)).collect();
+        for name in &plan.added {
+            if let Some(spec) = specs.remove(name) {
+                self.add_daemon(spec).await?;
+            }
+        }
+        for change in &plan.changed {
+            if let Some(spec) = specs.remove(&change.name) {
+                let revision = {
+                    let reg = self.registry.lock().await;
+                    reg.update_spec(&spec, None)?
+                };
+                info!("Updated daemon '{}' to revision {}", spec.name, revision);
+                self.reconcile_spec(&spec, change).await?;
+            }
+        }
+
+        info!(
+            "Applied config: {} added, {} changed, {} removed",
+            plan.added.len(),
+            plan.changed.len(),
+            plan.removed.len()
+        );
+        Ok(plan)
+    }
+
+    /// Replace a daemon's spec. `revision` is the revision the new spec was
+    /// based on; the update fails if the spec has changed since. A running
+    /// daemon is restarted if the change needs it. Returns the new revision,
+    /// what changed, and whether the daemon was restarted.
+    pub async fn update_daemon(
+        &self,
+        spec: DaemonSpec,
+        revision: Option<u64>,
+    ) -> Result<(u64, SpecChange, bool)> {
+        spec.validate()?;
+        let (revision, change) = {
+            let reg = self.registry.lock().await;
+            let current = reg.get_current_revision(&spec.name)?;
+            if let Some(expected) = revision {
+                if expected != current.revision {
+                    return Err(SyspulseError::RevisionConflict {
+                        name: spec.name.clone(),
+                        expected,
+                        actual: current.revision,
+                    });
+                }
+            }
+
+            let change = SpecChange::between(&current.spec, &spec)?;
+            if change.is_empty() {
+                return Ok((current.revision, change, false));
+            }
+
+            let mut specs = reg.list_specs()?;
+            specs.retain(|s| s.name != spec.name);
+            specs.push(spec.clone());
+            DependencyGraph::new(&specs).check_cycles()?;
+
+            (reg.update_spec(&spec, Some(current.revision))?, change)
+        };
+
+        info!("Updated daemon '{}' to revision {}", spec.name, revision);
+        let restarted = self.reconcile_spec(&spec, &change).await?;
+        Ok((revision, change, restarted))
+    }
+
+    /// Bring a daemon in line with its just-changed spec. Returns whether it
+    /// was restarted.
+    async fn reconcile_spec(&self, spec: &DaemonSpec, change: &SpecChange) -> Result<bool> {
+        let name = spec.name.as_str();
+        if change.fields.iter().any(|f| f == "replicas") {
+            self.set_replicas(spec).await?;
+        }
+
+        let active: Vec<String> = {
+            let instances = self.instances.read().await;
+            instances
+                .iter()
+                .filter(|(_, i)| i.spec_name == name && i.state.is_active())
+                .map(|(key, _)| key.clone())
+                .collect()
+        };
+        if !active.is_empty() {
+            if change.restart {
+                info!("Restarting daemon '{}' to apply its new spec", name);
+                self.restart_daemon(name, false).await?;
+                return Ok(true);
+            }
+            if change.fields.iter().any(|f| f == "health_check") {
+                for key in &active {
+                    self.replace_health_check(key, spec.health_check.as_ref())
+                        .await;
+                }
+            }
+        }
+
+        // A running daemon may still hold changed sockets' addresses; they
+        // are bound again when it next starts.
+        if let Err(e) = self.sockets.lock().await.bind(spec) {
+            warn!("Failed to rebind sockets of daemon '{}': {}", name, e);
+        }
+        if change
+            .fields
+            .iter()
+            .any(|f| ["schedule", "schedule_timezone", "every", "jitter"].contains(&f.as_str()))
+        {
+            if let Err(e) = self.schedule_daemon(spec).await {
+                warn!("Failed to reschedule daemon '{}': {}", name, e);
+            }
+        }
+
+        let mut instances = self.instances.write().await;
+        for inst in instances.values_mut().filter(|i| {
+            i.spec_name == name
+                && matches!(i.state, LifecycleState::Stopped | LifecycleState::Scheduled)
+        }) {
+            inst.state = if spec.is_scheduled() {
+                LifecycleState::Scheduled
+            } else {
+                LifecycleState::Stopped
+            };
+        }
+        Ok(false)
+    }
+
+    /// Change how many replicas of a daemon run. Goes through
+    /// [`update_daemon`](Self::update_daemon), so it creates a new revision.
+    pub async fn scale(&self, name: &str, replicas: u32) -> Result<(u64, SpecChange, bool)> {
+        let current = {
+            let reg = self.registry.lock().await;
+            reg.get_current_revision(name)?
+        };
+        let mut spec = current.spec;
+        spec.replicas = replicas;
+        self.update_daemon(spec, Some(current.revision)).await
+    }
+
+    /// Add or remove replicas until the daemon has `spec.replicas` of them.
+    /// New replicas are started if the daemon is running; the highest ones
+    /// are stopped and forgotten when scaling down.
+    async fn set_replicas(&self, spec: &DaemonSpec) -> Result<()> {
+        let name = spec.name.as_str();
+        let (running, mut existing) = {
+            let instances = self.instances.read().await;
+            let replicas: Vec<&DaemonInstance> =
+                instances.values().filter(|i| i.spec_name == name).collect();
+            (
+                replicas.iter().any(|i| i.state.is_active()),
+                replicas.iter().map(|i| i.replica).collect::<Vec<u32>>(),
+            )
+        };
+        existing.sort_unstable();
+
+        for &replica in existing.iter().rev().filter(|r| **r >= spec.replicas) {
+            let key = replica_name(name, replica);
+            let active = {
+                let instances = self.instances.read().await;
+                instances.get(&key).is_some_and(|i| i.state.is_active())
+            };
+            if active {
+                self.stop_instance_with_reason(&key, false, None, Some("scaled down".to_string()))
+                    .await?;
+            }
+            self.instances.write().await.remove(&key);
+            self.registry.lock().await.delete_state(name, replica)?;
+            self.process_driver.forget(&key);
+            if let Some(handle) = self.health_handles.lock().await.remove(&key) {
+                handle.abort();
+            }
+            info!("Removed replica '{}'", key);
+        }
+
+        for replica in (0..spec.replicas).filter(|r| !existing.contains(r)) {
+            let key = replica_name(name, replica);
+            {
+                let mut instance = DaemonInstance::replica(name, replica);
+                if spec.is_scheduled() {
+                    instance.state = LifecycleState::Scheduled;
+                }
+                self.instances.write().await.insert(key.clone(), instance);
+            }
+            if running {
+                self.start_instance(&key).await?;
+            }
+            info!("Added replica '{}'", key);
+        }
+        Ok(())
+    }
+
+    /// Swap the health check of a running daemon without restarting it.
+    async fn replace_health_check(&self, name: &str, health_spec: Option<&HealthCheckSpec>) {
+        {
+            let mut instances = self.instances.write().await;
+            if let Some(inst) = instances.get_mut(name) {
+                inst.health_status = if health_spec.is_some() {
+                    HealthStatus::Unknown
+                } else {
+                    HealthStatus::NotConfigured
+                };
+            }
+        }
+
+        match health_spec {
+            Some(health_spec) => self.spawn_health_check(name, health_spec).await,
+            None => {
+                let mut handles = self.health_handles.lock().await;
+                if let Some(handle) = handles.remove(name) {
+                    handle.abort();
+                }
+            }
+        }
+    }
+
+    /// Read logs for a daemon.
     pub async fn get_logs(&self, name: &str, lines: usize, stderr: bool) -> Result<Vec<String>> {
         // Verify the daemon exists.
         {
@@ -308,35 +1381,204 @@ impl DaemonManager {
         self.log_manager.read_logs(name, lines, stderr)
     }
 
+    /// Stream a daemon's logs: the last `lines` lines first, then new lines as
+    /// they are written, until the receiver is dropped or the manager shuts down.
+    pub async fn follow_logs(
+        &self,
+        name: &str,
+        lines: usize,
+        stderr: bool,
+    ) -> Result<mpsc::Receiver<Response>> {
+        {
+            let instances = self.instances.read().await;
+            if !instances.contains_key(name) {
+                return Err(SyspulseError::DaemonNotFound(name.to_string()));
+            }
+        }
+
+        let (tail, mut follower) = self.log_manager.follow_logs(name, lines, stderr)?;
+        let (tx, rx) = mpsc::channel(64);
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            if tx.send(Response::Logs { lines: tail }).await.is_err() {
+                return;
+            }
+
+            let mut interval = tokio::time::interval(LOG_FOLLOW_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = tx.closed() => break,
+                    _ = shutdown_rx.recv() => break,
+                }
+
+                match follower.poll() {
+                    Ok(lines) if lines.is_empty() => {}
+                    Ok(lines) => {
+                        if tx.send(Response::Logs { lines }).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(error_response(e)).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Stream events matching `names` and `kinds` (empty matches everything)
+    /// until the receiver is dropped or the manager shuts down.
+    pub fn subscribe(
+        &self,
+        names: Vec<String>,
+        kinds: Vec<String>,
+    ) -> Result<mpsc::Receiver<Response>> {
+        let filter = EventFilter::new(names, kinds)?;
+        let mut events_rx = self.events.subscribe();
+        let (tx, rx) = mpsc::channel(64);
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    event = events_rx.recv() => event,
+                    _ = tx.closed() => break,
+                    _ = shutdown_rx.recv() => break,
+                };
+                let event = match event {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("Event subscriber fell behind, dropped {} events", missed);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                if filter.matches(&event) && tx.send(Response::Event { event }).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Dispatch an IPC request, streaming responses for requests that keep the
+    /// connection open and answering everything else with a single response.
+    pub async fn dispatch(self: &Arc<Self>, request: Request) -> Reply {
+        match request {
+            Request::Logs {
+                name,
+                lines,
+                stderr,
+                follow: true,
+            } => match self.follow_logs(&name, lines, stderr).await {
+                Ok(rx) => Reply::Stream(rx),
+                Err(e) => error_response(e).into(),
+            },
+            Request::Subscribe { names, kinds } => match self.subscribe(names, kinds) {
+                Ok(rx) => Reply::Stream(rx),
+                Err(e) => error_response(e).into(),
+            },
+            request => self.handle_request(request).await.into(),
+        }
+    }
+
     /// Dispatch an IPC request to the appropriate method and return a response.
     pub async fn handle_request(self: &Arc<Self>, request: Request) -> Response {
         match request {
-            Request::Start { name, .. } => match self.start_daemon(&name).await {
-                Ok(inst) => Response::Ok {
-                    message: format!("Daemon '{}' started (PID {})", name, inst.pid.unwrap_or(0)),
-                },
-                Err(e) => error_response(e),
-            },
-            Request::Stop { name, force, .. } => match self.stop_daemon(&name, force).await {
+            Request::Start {
+                name,
+                wait,
+                timeout_secs,
+                with_deps,
+            } => {
+                let started = if with_deps {
+                    self.start_with_dependencies(&name).await
+                } else {
+                    self.start_daemon(&name).await
+                };
+                let result = match started {
+                    Ok(_) if wait => {
+                        self.wait_until_ready(&name, wait_timeout(timeout_secs))
+                            .await
+                    }
+                    result => result,
+                };
+                match result {
+                    Ok(instances) => Response::Ok {
+                        message: format!("Daemon '{}' started ({})", name, pids(&instances)),
+                    },
+                    Err(e) => error_response(e),
+                }
+            }
+            Request::Stop {
+                name,
+                force,
+                timeout_secs,
+            } => match self.stop_daemon(&name, force, timeout_secs).await {
                 Ok(_) => Response::Ok {
                     message: format!("Daemon '{}' stopped", name),
                 },
                 Err(e) => error_response(e),
             },
-            Request::Restart { name, force, .. } => match self.restart_daemon(&name, force).await {
-                Ok(inst) => Response::Ok {
-                    message: format!(
-                        "Daemon '{}' restarted (PID {})",
-                        name,
-                        inst.pid.unwrap_or(0)
-                    ),
-                },
-                Err(e) => error_response(e),
-            },
-            Request::Status { name } => match name {
-                Some(name) => match self.status(&name).await {
-                    Ok(instance) => Response::Status { instance },
+            Request::Restart {
+                name,
+                force,
+                wait,
+                timeout_secs,
+                rolling,
+            } => {
+                let restarted = if rolling && force {
+                    Err(SyspulseError::Config(
+                        "A rolling restart stops the old processes gracefully and can't be forced"
+                            .to_string(),
+                    ))
+                } else if rolling {
+                    self.rolling_restart_daemon(&name, wait_timeout(timeout_secs))
+                        .await
+                } else {
+                    self.restart_daemon(&name, force).await
+                };
+                let result = match restarted {
+                    Ok(_) if wait => {
+                        self.wait_until_ready(&name, wait_timeout(timeout_secs))
+                            .await
+                    }
+                    result => result,
+                };
+                match result {
+                    Ok(instances) => Response::Ok {
+                        message: format!("Daemon '{}' restarted ({})", name, pids(&instances)),
+                    },
                     Err(e) => error_response(e),
+                }
+            }
+            Request::Status { name } => match name {
+                // A daemon with several replicas is listed replica by replica.
+                Some(name) => match self.targets(&name).await {
+                    Ok(targets) if targets.len() > 1 => {
+                        let mut replicas: Vec<DaemonInstance> = {
+                            let instances = self.instances.read().await;
+                            targets
+                                .iter()
+                                .filter_map(|t| instances.get(t).cloned())
+                                .collect()
+                        };
+                        self.fill_next_runs(&mut replicas).await;
+                        Response::List {
+                            instances: replicas,
+                        }
+                    }
+                    _ => match self.status(&name).await {
+                        Ok(instance) => Response::Status { instance },
+                        Err(e) => error_response(e),
+                    },
                 },
                 None => match self.list().await {
                     Ok(instances) => Response::List { instances },
@@ -351,6 +1593,7 @@ impl DaemonManager {
                 name,
                 lines,
                 stderr,
+                ..
             } => match self.get_logs(&name, lines, stderr).await {
                 Ok(log_lines) => Response::Logs { lines: log_lines },
                 Err(e) => error_response(e),
@@ -367,6 +1610,83 @@ impl DaemonManager {
                 },
                 Err(e) => error_response(e),
             },
+            Request::Apply {
+                specs,
+                dry_run,
+                prune,
+            } => match self.apply(specs, dry_run, prune).await {
+                Ok(plan) => Response::Plan { plan },
+                Err(e) => error_response(e),
+            },
+            Request::GetSpec { name, revision } => {
+                let reg = self.registry.lock().await;
+                let result = match revision {
+                    Some(revision) => reg.get_revision(&name, revision),
+                    None => reg.get_current_revision(&name),
+                };
+                match result {
+                    Ok(SpecRevision { revision, spec, .. }) => Response::Spec { spec, revision },
+                    Err(e) => error_response(e),
+                }
+            }
+            Request::Revisions { name } => {
+                let reg = self.registry.lock().await;
+                match reg.list_revisions(&name) {
+                    Ok(revisions) => Response::Revisions { revisions },
+                    Err(e) => error_response(e),
+                }
+            }
+            Request::Update { spec, revision } => match self.update_daemon(spec, revision).await {
+                Ok((revision, change, restarted)) => Response::Updated {
+                    revision,
+                    fields: change.fields,
+                    restarted,
+                },
+                Err(e) => error_response(e),
+            },
+            Request::Scale { name, replicas } => match self.scale(&name, replicas).await {
+                Ok((revision, change, restarted)) => Response::Updated {
+                    revision,
+                    fields: change.fields,
+                    restarted,
+                },
+                Err(e) => error_response(e),
+            },
+            Request::Stats { name } => match self.stats(name.as_deref()).await {
+                Ok(stats) => Response::Stats { stats },
+                Err(e) => error_response(e),
+            },
+            Request::Subscribe { .. } => error_response(SyspulseError::Ipc(
+                "Subscribe is only supported on a streaming connection".to_string(),
+            )),
+            Request::Events { name, since, limit } => {
+                match self.events(name.as_deref(), since, limit).await {
+                    Ok(events) => Response::Events { events },
+                    Err(e) => error_response(e),
+                }
+            }
+            Request::Trigger { name } => match self.trigger(&name).await {
+                Ok(instances) if instances.is_empty() => Response::Ok {
+                    message: format!("Daemon '{}' triggered, previous run still going", name),
+                },
+                Ok(instances) => Response::Ok {
+                    message: format!("Daemon '{}' triggered ({})", name, pids(&instances)),
+                },
+                Err(e) => error_response(e),
+            },
+            Request::ResetFailed { name } => match self.reset_failed(&name).await {
+                Ok(reset) if reset.is_empty() => Response::Ok {
+                    message: format!("Daemon '{}' was not failed; restart count reset", name),
+                },
+                Ok(reset) => Response::Ok {
+                    message: format!("Reset {}", reset.join(", ")),
+                },
+                Err(e) => error_response(e),
+            },
+            Request::Runs { name, limit } => match self.runs(&name, limit).await {
+                Ok(runs) => Response::Runs { runs },
+                Err(e) => error_response(e),
+            },
             Request::Shutdown => {
                 info!("Shutdown requested via IPC");
                 // The actual shutdown is triggered by the caller seeing this response.
@@ -388,13 +1708,20 @@ impl DaemonManager {
         let pid_path = paths::pid_path();
         std::fs::write(&pid_path, std::process::id().to_string())?;
 
+        // Before any daemon is started, e.g. by being restored.
+        self.process_driver.init();
+
         // Restore daemons that were Running before a crash/restart.
         self.restore_running_daemons().await;
+        self.bind_sockets().await;
 
         // Set up cron scheduler for scheduled daemons.
-        let mut scheduler = Scheduler::new().await?;
-        self.setup_scheduled_daemons(&mut scheduler).await?;
-        scheduler.start().await?;
+        *self.scheduler.lock().await = Some(Scheduler::new().await?);
+        self.setup_scheduled_daemons().await;
+        if let Some(scheduler) = self.scheduler.lock().await.as_ref() {
+            scheduler.start().await?;
+        }
+        self.catch_up_missed_runs().await;
 
         // Start the IPC server.
         let socket_path = paths::socket_path();
@@ -405,7 +1732,7 @@ impl DaemonManager {
         let ipc_handle = tokio::spawn(async move {
             let handler = Arc::new(move |req: Request| {
                 let mgr = Arc::clone(&manager_for_ipc);
-                async move { mgr.handle_request(req).await }
+                async move { mgr.dispatch(req).await }
             });
             if let Err(e) = ipc_server.run(handler, shutdown_rx_ipc).await {
                 error!("IPC server error: {}", e);
@@ -419,6 +1746,27 @@ impl DaemonManager {
             Self::monitor_processes(manager_for_monitor, shutdown_rx_monitor).await;
         });
 
+        // Start the log rotation background task.
+        let manager_for_rotation = Arc::clone(&self);
+        let shutdown_rx_rotation = self.shutdown_tx.subscribe();
+        let rotation_handle = tokio::spawn(async move {
+            Self::rotate_logs_periodically(manager_for_rotation, shutdown_rx_rotation).await;
+        });
+
+        // Start the resource sampling background task.
+        let manager_for_stats = Arc::clone(&self);
+        let shutdown_rx_stats = self.shutdown_tx.subscribe();
+        let stats_handle = tokio::spawn(async move {
+            Self::sample_resources(manager_for_stats, shutdown_rx_stats).await;
+        });
+
+        // Start the socket activation background task.
+        let manager_for_sockets = Arc::clone(&self);
+        let shutdown_rx_sockets = self.shutdown_tx.subscribe();
+        let sockets_handle = tokio::spawn(async move {
+            Self::activate_on_connection(manager_for_sockets, shutdown_rx_sockets).await;
+        });
+
         // Wait for shutdown signal (Ctrl+C / SIGTERM).
         let shutdown_tx = self.shutdown_tx.clone();
         tokio::select! {
@@ -436,12 +1784,23 @@ impl DaemonManager {
         self.stop_all_daemons().await;
 
         // Shut down scheduler.
-        scheduler.shutdown().await.ok();
+        if let Some(mut scheduler) = self.scheduler.lock().await.take() {
+            scheduler.shutdown().await.ok();
+        }
 
         // Wait for background tasks to finish.
         ipc_handle.abort();
         monitor_handle.abort();
-        let _ = tokio::join!(ipc_handle, monitor_handle);
+        rotation_handle.abort();
+        stats_handle.abort();
+        sockets_handle.abort();
+        let _ = tokio::join!(
+            ipc_handle,
+            monitor_handle,
+            rotation_handle,
+            stats_handle,
+            sockets_handle
+        );
 
         // Clean up PID file.
         std::fs::remove_file(&pid_path).ok();
@@ -457,17 +1816,32 @@ impl DaemonManager {
     }
 
     /// Attempt to restore daemons that were in Running state when we last shut down.
+    /// Dependencies are restored before their dependents.
     async fn restore_running_daemons(&self) {
+        let order = self.start_order().await;
         let instances = self.instances.read().await;
-        let to_restart: Vec<String> = instances
+        let mut to_restart: Vec<(usize, u32, String)> = instances
             .iter()
-            .filter(|(_, inst)| inst.state == LifecycleState::Running)
-            .map(|(name, _)| name.clone())
+            .filter(|(_, inst)| {
+                matches!(
+                    inst.state,
+                    LifecycleState::Starting | LifecycleState::Running
+                )
+            })
+            .filter_map(|(name, inst)| {
+                let position = order.iter().position(|n| *n == inst.spec_name)?;
+                Some((position, inst.replica, name.clone()))
+            })
             .collect();
         drop(instances);
+        to_restart.sort();
 
-        for name in to_restart {
-            // First mark as stopped (the old process is gone), then start fresh.
+        for (_, _, name) in to_restart {
+            if self.reattach(&name).await {
+                continue;
+            }
+
+            // The old process is gone: mark as stopped, then start fresh.
             {
                 let mut instances = self.instances.write().await;
                 if let Some(inst) = instances.get_mut(&name) {
@@ -476,194 +1850,1075 @@ impl DaemonManager {
                 }
             }
             info!("Restoring previously running daemon '{}'", name);
-            if let Err(e) = self.start_daemon(&name).await {
+            if let Err(e) = self
+                .wait_for_dependencies(parse_replica_name(&name).0)
+                .await
+            {
+                error!("Failed to restore daemon '{}': {}", name, e);
+                continue;
+            }
+            if let Err(e) = self.start_instance(&name).await {
                 error!("Failed to restore daemon '{}': {}", name, e);
             }
         }
     }
 
+    /// Adopt a daemon's process left running by a previous manager, if its
+    /// persisted PID still refers to the same process rather than one that
+    /// reused the PID. Returns whether the daemon was reattached.
+    async fn reattach(&self, name: &str) -> bool {
+        let spec = {
+            let reg = self.registry.lock().await;
+            match reg.get_spec(parse_replica_name(name).0) {
+                Ok(spec) => spec,
+                Err(_) => return false,
+            }
+        };
+
+        let mut instances = self.instances.write().await;
+        let Some(instance) = instances.get_mut(name) else {
+            return false;
+        };
+        let Some(pid) = instance.pid else {
+            return false;
+        };
+        match self.process_driver.identity(pid).await {
+            Some(identity) if identity.matches(instance.pid_start_time, &spec.command) => {}
+            Some(_) => {
+                info!(
+                    "PID {} of daemon '{}' now belongs to another process",
+                    pid, name
+                );
+                return false;
+            }
+            None => return false,
+        }
+
+        instance.health_status = if spec.health_check.is_some() {
+            HealthStatus::Unknown
+        } else {
+            HealthStatus::NotConfigured
+        };
+        // Keepalives sent while no manager was listening were lost.
+        instance.last_watchdog = Some(Utc::now());
+        {
+            let reg = self.registry.lock().await;
+            if let Err(e) = reg.update_state(instance) {
+                warn!("Failed to persist state of daemon '{}': {}", name, e);
+            }
+        }
+        let instance_notify_socket = instance.notify_socket.clone();
+        drop(instances);
+
+        self.events
+            .record(
+                name,
+                EventKind::Started { pid },
+                Some("reattached after manager restart".to_string()),
+            )
+            .await;
+        // The process still sends to the socket it was started with. Processes
+        // started before each got their own path use the instance's.
+        if uses_notify_socket(&spec) {
+            let path = instance_notify_socket
+                .unwrap_or_else(|| self.notify_dir.join(format!("{}.sock", name)));
+            match notify_socket_for(&path, &spec) {
+                Ok(socket) => self.spawn_notify_listener(name, pid, socket),
+                Err(e) => warn!("Failed to listen for notifications of '{}': {}", name, e),
+            }
+        }
+        if let Some(ref health_spec) = spec.health_check {
+            self.spawn_health_check(name, health_spec).await;
+        }
+
+        info!("Reattached to daemon '{}' (PID {})", name, pid);
+        true
+    }
+
+    /// Listen on the sockets of every daemon. Daemons that are already
+    /// running, such as reattached ones, keep the sockets they were given and
+    /// are bound again when they next start.
+    async fn bind_sockets(&self) {
+        let specs = {
+            let reg = self.registry.lock().await;
+            reg.list_specs().unwrap_or_default()
+        };
+        for spec in specs.iter().filter(|s| !s.sockets.is_empty()) {
+            if self.is_active(&spec.name).await {
+                continue;
+            }
+            if let Err(e) = self.sockets.lock().await.bind(spec) {
+                warn!("Failed to bind sockets of daemon '{}': {}", spec.name, e);
+            }
+        }
+    }
+
+    /// Background task: start `lazy_start` daemons that aren't running when
+    /// a connection arrives on one of their sockets.
+    async fn activate_on_connection(
+        manager: Arc<DaemonManager>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let mut interval = tokio::time::interval(SOCKET_POLL_INTERVAL);
+        let mut last_started: HashMap<String, tokio::time::Instant> = HashMap::new();
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => {
+                    info!("Socket activation shutting down");
+                    break;
+                }
+            }
+
+            let pending = manager.sockets.lock().await.pending();
+            for name in pending {
+                if manager.is_active(&name).await {
+                    continue;
+                }
+                if last_started
+                    .get(&name)
+                    .is_some_and(|at| at.elapsed() < SOCKET_ACTIVATION_BACKOFF)
+                {
+                    continue;
+                }
+                last_started.insert(name.clone(), tokio::time::Instant::now());
+
+                info!("Connection on socket of daemon '{}', starting it", name);
+                if let Err(e) = manager.start_with_dependencies(&name).await {
+                    error!("Failed to start daemon '{}' on connection: {}", name, e);
+                }
+            }
+        }
+    }
+
     /// Set up cron schedules for all daemons that have a schedule field.
-    async fn setup_scheduled_daemons(&self, scheduler: &mut Scheduler) -> Result<()> {
+    async fn setup_scheduled_daemons(&self) {
         let specs = {
             let reg = self.registry.lock().await;
             reg.list_specs().unwrap_or_default()
         };
 
         for spec in specs {
-            if let Some(ref cron_expr) = spec.schedule {
-                let manager = Arc::new({
-                    // We need a reference to self for the callback, but we're behind Arc<Self>
-                    // in run(). The callback captures the instances/registry/etc. via clones.
-                    let instances = Arc::clone(&self.instances);
-                    let registry = Arc::clone(&self.registry);
-                    let process_driver = Arc::clone(&self.process_driver);
-                    let log_manager = Arc::clone(&self.log_manager);
-                    let shutdown_tx = self.shutdown_tx.clone();
-                    let health_handles = Arc::clone(&self.health_handles);
-                    ManagerComponents {
-                        instances,
-                        registry,
-                        process_driver,
-                        log_manager,
-                        shutdown_tx,
-                        health_handles,
-                    }
-                });
-
-                scheduler
-                    .schedule_daemon(&spec.name, cron_expr, move |name| {
-                        let mgr = Arc::clone(&manager);
-                        async move {
-                            info!("Cron trigger: starting daemon '{}'", name);
-                            if let Err(e) = cron_start_daemon(&mgr, &name).await {
-                                error!("Cron failed to start '{}': {}", name, e);
-                            }
-                        }
-                    })
-                    .await?;
+            if let Err(e) = self.schedule_daemon(&spec).await {
+                error!("Failed to schedule daemon '{}': {}", spec.name, e);
             }
         }
+    }
 
-        Ok(())
+    /// Start the runs scheduled daemons missed while the manager was down,
+    /// as their `catch_up` asks. Runs are counted from the daemon's last
+    /// scheduled run; one that never ran has nothing to catch up.
+    async fn catch_up_missed_runs(&self) {
+        let specs = {
+            let reg = self.registry.lock().await;
+            reg.list_specs().unwrap_or_default()
+        };
+
+        let now = Utc::now();
+        for spec in specs {
+            let limit = match spec.catch_up {
+                CatchUp::None => continue,
+                CatchUp::Once => 1,
+                CatchUp::All => MAX_CATCH_UP_RUNS,
+            };
+            let Ok(Some(schedule)) = JobSchedule::from_spec(&spec) else {
+                continue;
+            };
+            let last_run = {
+                let reg = self.registry.lock().await;
+                reg.last_scheduled_run(&spec.name)
+            };
+            let last_run = match last_run {
+                Ok(Some(last_run)) => last_run,
+                Ok(None) => continue,
+                Err(e) => {
+                    error!("Failed to look up runs of '{}': {}", spec.name, e);
+                    continue;
+                }
+            };
+            let missed = schedule.ticks_between(last_run, now, limit);
+            if missed == 0 {
+                continue;
+            }
+            info!(
+                "Daemon '{}' missed runs since {}, catching up {}",
+                spec.name,
+                last_run.to_rfc3339(),
+                missed
+            );
+            tokio::spawn(catch_up(self.components(), spec.name, missed));
+        }
+    }
+
+    /// Give a daemon the cron job its spec asks for, replacing or removing
+    /// the one it had. Does nothing until the scheduler is running.
+    async fn schedule_daemon(&self, spec: &DaemonSpec) -> Result<()> {
+        let mut scheduler = self.scheduler.lock().await;
+        let Some(scheduler) = scheduler.as_mut() else {
+            return Ok(());
+        };
+        let Some(schedule) = JobSchedule::from_spec(spec)? else {
+            scheduler.unschedule_daemon(&spec.name).await?;
+            return Ok(());
+        };
+        let jitter = scheduler::jitter(spec)?;
+
+        // The callback outlives this call, so it captures the manager's
+        // shared state rather than the manager.
+        let manager = self.components();
+        scheduler
+            .schedule_daemon(&spec.name, &schedule, move |name| {
+                let mgr = Arc::clone(&manager);
+                async move {
+                    if let Some(jitter) = jitter {
+                        let delay = Duration::from_millis(
+                            rand::thread_rng().gen_range(0..=jitter.as_millis() as u64),
+                        );
+                        info!(
+                            "Delaying scheduled run of '{}' by {}",
+                            name,
+                            humantime::format_duration(delay)
+                        );
+                        let mut shutdown_rx = mgr.shutdown_tx.subscribe();
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            _ = shutdown_rx.recv() => return,
+                        }
+                    }
+                    info!("Cron trigger: starting daemon '{}'", name);
+                    if let Err(e) = trigger_run(&mgr, &name, RunTrigger::Schedule).await {
+                        error!("Cron failed to start '{}': {}", name, e);
+                    }
+                }
+            })
+            .await
     }
 
-    /// Stop all currently running daemons (used during shutdown).
+    /// The manager's shared state, for tasks that start scheduled runs.
+    fn components(&self) -> Arc<ManagerComponents> {
+        Arc::new(ManagerComponents {
+            instances: Arc::clone(&self.instances),
+            registry: Arc::clone(&self.registry),
+            process_driver: Arc::clone(&self.process_driver),
+            log_manager: Arc::clone(&self.log_manager),
+            shutdown_tx: self.shutdown_tx.clone(),
+            health_handles: Arc::clone(&self.health_handles),
+            sockets: Arc::clone(&self.sockets),
+            notify_dir: self.notify_dir.clone(),
+            queued_runs: Arc::clone(&self.queued_runs),
+            events: self.events.clone(),
+        })
+    }
+
+    /// Stop all currently running daemons (used during shutdown), dependents
+    /// before their dependencies.
     async fn stop_all_daemons(&self) {
+        let order = self.start_order().await;
         let names: Vec<String> = {
             let instances = self.instances.read().await;
-            instances
-                .iter()
-                .filter(|(_, inst)| inst.state.is_active())
-                .map(|(name, _)| name.clone())
+            order
+                .into_iter()
+                .rev()
+                .filter(|name| {
+                    instances
+                        .values()
+                        .any(|i| i.spec_name == *name && i.state.is_active())
+                })
                 .collect()
         };
 
         for name in names {
-            if let Err(e) = self.stop_daemon(&name, false).await {
+            if let Err(e) = self.stop_daemon(&name, false, None).await {
                 warn!("Failed to stop daemon '{}' during shutdown: {}", name, e);
                 // Try force kill.
-                if let Err(e2) = self.stop_daemon(&name, true).await {
+                if let Err(e2) = self.stop_daemon(&name, true, None).await {
                     error!("Failed to force-stop daemon '{}': {}", name, e2);
                 }
             }
         }
     }
 
-    /// Background task: monitors running processes, detects unexpected exits,
-    /// and handles restart policies.
-    async fn monitor_processes(
+    /// Background task: sample CPU and memory of every running daemon's
+    /// process tree into the in-memory history.
+    async fn sample_resources(
         manager: Arc<DaemonManager>,
         mut shutdown_rx: broadcast::Receiver<()>,
     ) {
-        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        let mut interval = tokio::time::interval(STATS_SAMPLE_INTERVAL);
+        let mut soft_limits = SoftLimitTracker::new();
 
         loop {
             tokio::select! {
                 _ = interval.tick() => {}
                 _ = shutdown_rx.recv() => {
-                    info!("Process monitor shutting down");
+                    info!("Resource sampler shutting down");
                     break;
                 }
             }
 
-            // Collect names of daemons in Running state.
-            let running: Vec<(String, u32)> = {
+            let running: HashMap<String, u32> = {
                 let instances = manager.instances.read().await;
                 instances
                     .iter()
-                    .filter_map(|(name, inst)| {
-                        if inst.state == LifecycleState::Running {
-                            inst.pid.map(|pid| (name.clone(), pid))
-                        } else {
-                            None
-                        }
+                    .filter(|(_, inst)| inst.state == LifecycleState::Running)
+                    .filter_map(|(name, inst)| inst.pid.map(|pid| (name.clone(), pid)))
+                    .collect()
+            };
+
+            let usage = if running.is_empty() {
+                HashMap::new()
+            } else {
+                let pids: Vec<u32> = running.values().copied().collect();
+                match manager.process_driver.resource_usage(&pids).await {
+                    Ok(usage) => usage,
+                    Err(e) => {
+                        warn!("Failed to sample resource usage: {}", e);
+                        continue;
+                    }
+                }
+            };
+
+            let now = Utc::now();
+            let samples: Vec<(String, u32, ResourceSample)> = running
+                .iter()
+                .filter_map(|(name, pid)| {
+                    let u = usage.get(pid)?;
+                    let sample = ResourceSample {
+                        timestamp: now,
+                        cpu_percent: u.cpu_percent,
+                        memory_bytes: u.memory_bytes,
+                        process_count: u.process_count,
+                    };
+                    Some((name.clone(), *pid, sample))
+                })
+                .collect();
+
+            {
+                let mut history = manager.stats.write().await;
+                history.retain(|name| running.contains_key(name));
+                for (name, _, sample) in &samples {
+                    history.record(name, sample.clone());
+                }
+            }
+
+            if manager.events.has_subscribers() {
+                for (name, _, sample) in &samples {
+                    manager.events.publish(
+                        name,
+                        sample.timestamp,
+                        EventKind::Resources {
+                            cpu_percent: sample.cpu_percent,
+                            memory_bytes: sample.memory_bytes,
+                            process_count: sample.process_count,
+                        },
+                    );
+                }
+            }
+
+            // Check soft limits against the new samples.
+            soft_limits.retain(|name| running.contains_key(name));
+            let limits: HashMap<String, ResourceLimits> = {
+                let reg = manager.registry.lock().await;
+                samples
+                    .iter()
+                    .filter_map(|(name, _, _)| {
+                        let daemon = parse_replica_name(name).0;
+                        let limits = reg.get_spec(daemon).ok()?.resource_limits?;
+                        limits.is_soft().then(|| (name.clone(), limits))
                     })
                     .collect()
             };
+            for (name, pid, sample) in samples {
+                let Some(limits) = limits.get(&name) else {
+                    continue;
+                };
+                if let Some(violation) = soft_limits.observe(&name, limits, &sample) {
+                    let mgr = Arc::clone(&manager);
+                    let action = limits.action;
+                    tokio::spawn(async move {
+                        Self::enforce_soft_limit(mgr, name, pid, action, violation).await;
+                    });
+                }
+            }
+        }
+    }
+
+    /// Take the configured action on a daemon that stayed above a soft
+    /// resource limit for its whole grace period.
+    async fn enforce_soft_limit(
+        manager: Arc<DaemonManager>,
+        name: String,
+        pid: u32,
+        action: LimitAction,
+        violation: String,
+    ) {
+        warn!("Daemon '{}' exceeded a soft limit: {}", name, violation);
+        let reason = format!("soft limit exceeded: {}", violation);
+
+        match action {
+            LimitAction::Warn => {
+                let mut instances = manager.instances.write().await;
+                if let Some(inst) = instances.get_mut(&name) {
+                    inst.reason = Some(reason);
+                }
+            }
+            LimitAction::Stop => {
+                if let Err(e) = manager
+                    .stop_instance_with_reason(&name, false, None, Some(reason))
+                    .await
+                {
+                    error!("Failed to stop daemon '{}': {}", name, e);
+                }
+            }
+            LimitAction::Restart => {
+                Self::restart_as_failure(&manager, &name, pid, None, reason).await;
+            }
+        }
+    }
+
+    /// Stop daemons that didn't become ready within their
+    /// `start_timeout_secs` as failed.
+    async fn handle_start_timeouts(manager: &Arc<Self>) {
+        let starting: Vec<(String, u32, DateTime<Utc>)> = {
+            let instances = manager.instances.read().await;
+            instances
+                .iter()
+                .filter(|(_, inst)| inst.state == LifecycleState::Starting)
+                .filter_map(|(name, inst)| Some((name.clone(), inst.pid?, inst.started_at?)))
+                .collect()
+        };
+        if starting.is_empty() {
+            return;
+        }
+
+        let timeouts: HashMap<String, u64> = {
+            let reg = manager.registry.lock().await;
+            starting
+                .iter()
+                .filter_map(|(name, _, _)| {
+                    let spec = reg.get_spec(parse_replica_name(name).0).ok()?;
+                    Some((name.clone(), spec.start_timeout_secs))
+                })
+                .collect()
+        };
+
+        for (name, pid, started_at) in starting {
+            let Some(&timeout) = timeouts.get(&name) else {
+                continue;
+            };
+            let elapsed = (Utc::now() - started_at).to_std().unwrap_or_default();
+            if elapsed < Duration::from_secs(timeout) {
+                continue;
+            }
+
+            warn!("Daemon '{}' did not become ready within {}s", name, timeout);
+            let reason = format!("not ready within {}s", timeout);
+            let manager = Arc::clone(manager);
+            tokio::spawn(async move {
+                Self::restart_as_failure(&manager, &name, pid, None, reason).await;
+            });
+        }
+    }
+
+    /// Kill running daemons with a `watchdog_secs` that haven't sent a
+    /// keepalive within it, with their `watchdog_signal`, as failed.
+    async fn handle_missed_watchdogs(manager: &Arc<Self>) {
+        let running: Vec<(String, u32, Option<DateTime<Utc>>)> = {
+            let instances = manager.instances.read().await;
+            instances
+                .iter()
+                .filter(|(_, inst)| inst.state == LifecycleState::Running)
+                .filter_map(|(name, inst)| {
+                    let alive_at = inst.started_at.max(inst.last_watchdog);
+                    Some((name.clone(), inst.pid?, alive_at))
+                })
+                .collect()
+        };
+        if running.is_empty() {
+            return;
+        }
+
+        let watchdogs: HashMap<String, DaemonSpec> = {
+            let reg = manager.registry.lock().await;
+            running
+                .iter()
+                .filter_map(|(name, _, _)| {
+                    let spec = reg.get_spec(parse_replica_name(name).0).ok()?;
+                    spec.watchdog_secs?;
+                    Some((name.clone(), spec))
+                })
+                .collect()
+        };
+
+        for (name, pid, alive_at) in running {
+            let Some(spec) = watchdogs.get(&name) else {
+                continue;
+            };
+            let Some(watchdog_secs) = spec.watchdog_secs else {
+                continue;
+            };
+            let touched_at = spec
+                .heartbeat_path()
+                .and_then(|path| std::fs::metadata(path).ok()?.modified().ok())
+                .map(DateTime::<Utc>::from);
+            let Some(alive_at) = alive_at.max(touched_at) else {
+                continue;
+            };
+            let silent = (Utc::now() - alive_at).to_std().unwrap_or_default();
+            if silent < Duration::from_secs(watchdog_secs) {
+                continue;
+            }
+
+            warn!(
+                "Daemon '{}' sent no keepalive for {}s, sending {}",
+                name, watchdog_secs, spec.watchdog_signal
+            );
+            let reason = format!("no watchdog keepalive within {}s", watchdog_secs);
+            let signal = spec.watchdog_signal.clone();
+            let manager = Arc::clone(manager);
+            tokio::spawn(async move {
+                Self::restart_as_failure(&manager, &name, pid, Some(&signal), reason).await;
+            });
+        }
+    }
+
+    /// Kill daemons that have been running for longer than their
+    /// `max_runtime_secs`. The exit counts as a failure, and the run as
+    /// timed out.
+    async fn handle_overruns(manager: &Arc<Self>) {
+        let running: Vec<(String, u32, DateTime<Utc>)> = {
+            let instances = manager.instances.read().await;
+            instances
+                .iter()
+                .filter(|(_, inst)| {
+                    matches!(
+                        inst.state,
+                        LifecycleState::Starting | LifecycleState::Running
+                    )
+                })
+                .filter_map(|(name, inst)| Some((name.clone(), inst.pid?, inst.started_at?)))
+                .collect()
+        };
+        if running.is_empty() {
+            return;
+        }
+
+        let limits: HashMap<String, u64> = {
+            let reg = manager.registry.lock().await;
+            running
+                .iter()
+                .filter_map(|(name, _, _)| {
+                    let spec = reg.get_spec(parse_replica_name(name).0).ok()?;
+                    Some((name.clone(), spec.max_runtime_secs?))
+                })
+                .collect()
+        };
+
+        for (name, pid, started_at) in running {
+            let Some(&max_runtime_secs) = limits.get(&name) else {
+                continue;
+            };
+            let runtime = (Utc::now() - started_at).to_std().unwrap_or_default();
+            if runtime < Duration::from_secs(max_runtime_secs) {
+                continue;
+            }
+
+            warn!(
+                "Daemon '{}' has been running for over {}s, stopping it",
+                name, max_runtime_secs
+            );
+            if let Err(e) = manager
+                .registry
+                .lock()
+                .await
+                .mark_run(&name, RunStatus::TimedOut)
+            {
+                warn!("Failed to record the timeout of '{}': {}", name, e);
+            }
+            let reason = format!("exceeded max_runtime_secs of {}s", max_runtime_secs);
+            let manager = Arc::clone(manager);
+            tokio::spawn(async move {
+                Self::restart_as_failure(&manager, &name, pid, None, reason).await;
+            });
+        }
+    }
+
+    /// Take the configured `on_unhealthy` action on a daemon whose health
+    /// check has failed `retries` times in a row.
+    async fn enforce_unhealthy(
+        manager: Arc<DaemonManager>,
+        name: String,
+        pid: u32,
+        action: UnhealthyAction,
+        reason: String,
+    ) {
+        match action {
+            UnhealthyAction::None => {}
+            UnhealthyAction::Restart => {
+                Self::restart_as_failure(&manager, &name, pid, None, reason).await;
+            }
+            UnhealthyAction::Stop => {
+                if let Err(e) = manager
+                    .stop_instance_with_reason(&name, false, None, Some(reason))
+                    .await
+                {
+                    error!("Failed to stop daemon '{}': {}", name, e);
+                }
+            }
+        }
+    }
+
+    /// Stop a running daemon's process and treat the exit as a failure, so its
+    /// restart policy, backoff and `max_retries` decide what happens next.
+    /// With `signal`, the process is sent that signal instead of being
+    /// stopped gracefully, and killed if it outlives `stop_timeout_secs`.
+    async fn restart_as_failure(
+        manager: &Arc<Self>,
+        name: &str,
+        pid: u32,
+        signal: Option<&str>,
+        reason: String,
+    ) {
+        // Leave Running first so the process monitor doesn't treat the exit
+        // as a crash of its own.
+        {
+            let mut instances = manager.instances.write().await;
+            match instances.get_mut(name) {
+                Some(inst)
+                    if matches!(
+                        inst.state,
+                        LifecycleState::Starting | LifecycleState::Running
+                    ) && inst.pid == Some(pid) =>
+                {
+                    inst.state = LifecycleState::Stopping;
+                }
+                _ => return,
+            }
+        }
+
+        let timeout = {
+            let reg = manager.registry.lock().await;
+            reg.get_spec(parse_replica_name(name).0)
+                .map(|s| s.stop_timeout_secs)
+                .unwrap_or(30)
+        };
+        let stopped = match signal {
+            Some(signal) => {
+                Self::signal_and_wait(manager, pid, signal, Duration::from_secs(timeout)).await
+            }
+            None => manager.process_driver.stop(pid, timeout).await,
+        };
+        if let Err(e) = stopped {
+            error!("Failed to stop daemon '{}': {}", name, e);
+        }
+        let status = reap(manager.process_driver.as_ref(), pid).await;
+        Self::handle_process_exit(manager, name, status, Some(reason)).await;
+    }
+
+    /// Send `signal` to a process and wait for it to exit, killing it if it
+    /// is still alive after `timeout`.
+    async fn signal_and_wait(
+        manager: &Arc<Self>,
+        pid: u32,
+        signal: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        manager.process_driver.signal(pid, signal).await?;
+        let deadline = tokio::time::Instant::now() + timeout;
+        while tokio::time::Instant::now() < deadline {
+            if !manager.process_driver.is_alive(pid).await {
+                return Ok(());
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+        manager.process_driver.kill(pid).await
+    }
+
+    /// Background task: rotate the logs of running daemons that have a
+    /// `log_config` once they exceed its size limit.
+    async fn rotate_logs_periodically(
+        manager: Arc<DaemonManager>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let mut interval = tokio::time::interval(LOG_ROTATION_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => {
+                    info!("Log rotation shutting down");
+                    break;
+                }
+            }
+
+            let running: Vec<String> = {
+                let instances = manager.instances.read().await;
+                instances
+                    .iter()
+                    .filter(|(_, inst)| inst.state == LifecycleState::Running)
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            };
+            if running.is_empty() {
+                continue;
+            }
+
+            let configs: Vec<(String, LogConfig)> = {
+                let reg = manager.registry.lock().await;
+                running
+                    .into_iter()
+                    .filter_map(|name| {
+                        let config = reg.get_spec(parse_replica_name(&name).0).ok()?.log_config?;
+                        Some((name, config))
+                    })
+                    .collect()
+            };
+            if configs.is_empty() {
+                continue;
+            }
+
+            // Copying and compressing large files is blocking work.
+            let log_manager = Arc::clone(&manager.log_manager);
+            let result = tokio::task::spawn_blocking(move || {
+                for (name, config) in configs {
+                    if let Err(e) = log_manager.rotate_logs(&name, &config) {
+                        warn!("Failed to rotate logs for '{}': {}", name, e);
+                    }
+                }
+            })
+            .await;
+            if let Err(e) = result {
+                error!("Log rotation task failed: {}", e);
+            }
+        }
+    }
+
+    /// Background task: handles daemons exiting unexpectedly as soon as the
+    /// process driver reports it, and applies restart policies. A periodic
+    /// tick dispatches `on_unhealthy` actions and catches exits of processes
+    /// the driver isn't tracking.
+    async fn monitor_processes(
+        manager: Arc<DaemonManager>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let mut exits = manager.process_driver.exits();
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+        loop {
+            tokio::select! {
+                exit = exits.recv() => match exit {
+                    Ok(exit) => Self::handle_reported_exit(&manager, exit).await,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("Missed {} process exit notifications", missed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = interval.tick() => {
+                    Self::check_liveness(&manager).await;
+                    Self::handle_unhealthy(&manager).await;
+                    Self::handle_start_timeouts(&manager).await;
+                    Self::handle_missed_watchdogs(&manager).await;
+                    Self::handle_overruns(&manager).await;
+                }
+                _ = shutdown_rx.recv() => {
+                    info!("Process monitor shutting down");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Handle an exit reported by the process driver if it belongs to a
+    /// daemon that is supposed to be running. Exits of daemons being stopped
+    /// are collected by the stop itself.
+    async fn handle_reported_exit(manager: &Arc<Self>, exit: ProcessExit) {
+        let name = {
+            let instances = manager.instances.read().await;
+            instances
+                .iter()
+                .find(|(_, inst)| {
+                    matches!(
+                        inst.state,
+                        LifecycleState::Starting | LifecycleState::Running
+                    ) && inst.pid == Some(exit.pid)
+                })
+                .map(|(name, _)| name.clone())
+        };
+        let Some(name) = name else {
+            return;
+        };
+
+        warn!(
+            "Daemon '{}' (PID {}) has exited unexpectedly",
+            name, exit.pid
+        );
+        // Forget the reaped child; the status came with the notification.
+        manager.process_driver.wait(exit.pid).await.ok();
+        Self::handle_process_exit(manager, &name, exit.status, None).await;
+    }
+
+    /// Fallback for missed notifications and processes the driver didn't
+    /// spawn: check that every running daemon's process still exists.
+    async fn check_liveness(manager: &Arc<Self>) {
+        let running: Vec<(String, u32, Option<u64>)> = {
+            let instances = manager.instances.read().await;
+            instances
+                .iter()
+                .filter(|(_, inst)| {
+                    matches!(
+                        inst.state,
+                        LifecycleState::Starting | LifecycleState::Running
+                    )
+                })
+                .filter_map(|(name, inst)| {
+                    inst.pid.map(|pid| (name.clone(), pid, inst.pid_start_time))
+                })
+                .collect()
+        };
+
+        for (name, pid, start_time) in running {
+            if !manager.process_driver.is_alive(pid).await {
+                warn!("Daemon '{}' (PID {}) has exited unexpectedly", name, pid);
+                let status = manager.process_driver.wait(pid).await.ok().flatten();
+                Self::handle_process_exit(manager, &name, status, None).await;
+                continue;
+            }
+
+            // A reattached process isn't the manager's child, so its exit is
+            // never reaped here, and its PID may already belong to another
+            // process. A process that is gone, or not yet reaped, is left to
+            // the checks above.
+            let Some(start_time) = start_time else {
+                continue;
+            };
+            let reused = manager
+                .process_driver
+                .identity(pid)
+                .await
+                .is_some_and(|identity| identity.start_time != start_time);
+            if reused {
+                warn!(
+                    "Daemon '{}' has exited unexpectedly, PID {} now belongs to another process",
+                    name, pid
+                );
+                Self::handle_process_exit(manager, &name, None, None).await;
+            }
+        }
+    }
+
+    /// Dispatch the `on_unhealthy` action of running daemons that have just
+    /// been marked unhealthy.
+    async fn handle_unhealthy(manager: &Arc<Self>) {
+        let unhealthy: Vec<(String, DaemonInstance)> = {
+            let instances = manager.instances.read().await;
+            instances
+                .iter()
+                .filter(|(_, inst)| inst.health_status == HealthStatus::Unhealthy)
+                .map(|(name, inst)| (name.clone(), inst.clone()))
+                .collect()
+        };
+        if unhealthy.is_empty() {
+            return;
+        }
+
+        let checks: HashMap<String, HealthCheckSpec> = {
+            let reg = manager.registry.lock().await;
+            unhealthy
+                .iter()
+                .filter_map(|(name, _)| {
+                    let check = reg
+                        .get_spec(parse_replica_name(name).0)
+                        .ok()?
+                        .health_check?;
+                    Some((name.clone(), check))
+                })
+                .collect()
+        };
+
+        for (name, inst) in unhealthy {
+            let Some(check) = checks.get(&name) else {
+                continue;
+            };
+            let Some((pid, action)) = unhealthy_action(&inst, check) else {
+                continue;
+            };
 
-            for (name, pid) in running {
-                let alive = manager.process_driver.is_alive(pid).await;
-                if alive {
-                    continue;
+            // Claim the transition so the next tick doesn't act on it again.
+            {
+                let mut instances = manager.instances.write().await;
+                match instances.get_mut(&name) {
+                    Some(inst)
+                        if inst.pid == Some(pid)
+                            && inst.health_status == HealthStatus::Unhealthy =>
+                    {
+                        inst.health_status = HealthStatus::Unknown;
+                    }
+                    _ => continue,
                 }
+            }
 
-                // Process has exited unexpectedly.
-                warn!("Daemon '{}' (PID {}) has exited unexpectedly", name, pid);
-
-                let exit_code = manager.process_driver.wait(pid).await.ok().flatten();
+            let reason = format!("health check failed {} times in a row", check.retries);
+            warn!("Daemon '{}' is unhealthy, taking action {:?}", name, action);
+            tokio::spawn(Self::enforce_unhealthy(
+                Arc::clone(manager),
+                name,
+                pid,
+                action,
+                reason,
+            ));
+        }
+    }
 
-                // Update instance state.
-                let (should_restart, backoff) = {
-                    let mut instances = manager.instances.write().await;
-                    if let Some(inst) = instances.get_mut(&name) {
-                        inst.state = LifecycleState::Failed;
-                        inst.pid = None;
-                        inst.exit_code = exit_code;
-                        inst.stopped_at = Some(Utc::now());
-                        inst.health_status = HealthStatus::Unknown;
+    /// Mark a daemon whose process is gone as Failed and schedule a restart if
+    /// its restart policy allows one. `reason` is set when the manager ended
+    /// the process itself; such exits always count as failures.
+    async fn handle_process_exit(
+        manager: &Arc<DaemonManager>,
+        name: &str,
+        status: Option<ExitStatus>,
+        reason: Option<String>,
+    ) {
+        let exit_code = status.and_then(ExitStatus::code);
+        let policy_exit_code = if reason.is_some() { None } else { exit_code };
+        let succeeded = reason.is_none() && exit_code == Some(0);
 
-                        // Persist the failed state.
-                        if let Ok(reg) = manager.registry.try_lock() {
-                            reg.update_state(inst).ok();
-                        }
+        // Look the spec up before taking the instances lock, so a busy
+        // registry can't make the exit skip the restart policy.
+        let spec = {
+            let reg = manager.registry.lock().await;
+            reg.get_spec(parse_replica_name(name).0).ok()
+        };
 
-                        // Check restart policy.
-                        let spec = {
-                            if let Ok(reg) = manager.registry.try_lock() {
-                                reg.get_spec(&name).ok()
-                            } else {
-                                None
-                            }
-                        };
+        // Update instance state.
+        let (should_restart, backoff, attempt, exited) = {
+            let mut instances = manager.instances.write().await;
+            if let Some(inst) = instances.get_mut(name) {
+                // A scheduled run that went well waits for the next one.
+                let scheduled = spec.as_ref().is_some_and(|s| s.is_scheduled());
+                inst.state = if succeeded && scheduled {
+                    LifecycleState::Scheduled
+                } else {
+                    LifecycleState::Failed
+                };
+                inst.pid = None;
+                inst.pid_start_time = None;
+                inst.exit_code = exit_code;
+                inst.exit_signal = status.and_then(ExitStatus::signal);
+                inst.stopped_at = Some(Utc::now());
+                inst.health_status = HealthStatus::Unknown;
+                if reason.is_some() {
+                    inst.reason = reason.clone();
+                }
 
-                        if let Some(spec) = spec {
-                            let should = RestartEvaluator::should_restart(
-                                &spec.restart_policy,
-                                exit_code,
-                                inst.restart_count,
-                            );
-                            let backoff = RestartEvaluator::backoff_duration(
-                                &spec.restart_policy,
-                                inst.restart_count,
-                            );
-                            inst.restart_count += 1;
-                            (should, backoff)
-                        } else {
-                            (false, Duration::ZERO)
-                        }
-                    } else {
-                        (false, Duration::ZERO)
+                // Check restart policy. A process that stayed up long enough
+                // starts the count over.
+                let (should, backoff, attempt) = if let Some(spec) = &spec {
+                    let policy = &spec.restart_policy;
+                    let now = Utc::now();
+                    let uptime = inst
+                        .started_at
+                        .and_then(|t| (now - t).to_std().ok())
+                        .unwrap_or_default();
+                    if RestartEvaluator::is_stable(policy, uptime) {
+                        inst.restart_count = 0;
+                    }
+                    let mut should = RestartEvaluator::should_restart(
+                        policy,
+                        policy_exit_code,
+                        inst.restart_count,
+                    );
+                    if should
+                        && RestartEvaluator::exceeds_burst(policy, &mut inst.recent_restarts, now)
+                    {
+                        let (limit, window_secs) = policy.burst().unwrap_or_default();
+                        warn!(
+                            "Daemon '{}' restarted {} times in {}s, not restarting it again",
+                            name, limit, window_secs
+                        );
+                        should = false;
+                        inst.state = LifecycleState::CrashLooping;
+                        inst.reason = Some(format!(
+                            "crash-looping: restarted {} times in {}s",
+                            limit, window_secs
+                        ));
                     }
+                    let backoff = RestartEvaluator::backoff_duration(policy, inst.restart_count);
+                    inst.restart_count += 1;
+                    (should, backoff, inst.restart_count)
+                } else {
+                    (false, Duration::ZERO, 0)
                 };
+                (should, backoff, attempt, Some(inst.clone()))
+            } else {
+                (false, Duration::ZERO, 0, None)
+            }
+        };
 
-                // Cancel health check.
+        // Persist the failed state.
+        if let Some(inst) = exited {
+            if let Err(e) = manager.registry.lock().await.update_state(&inst) {
+                warn!("Failed to persist the state of '{}': {}", name, e);
+            }
+        }
+
+        let run_status = if succeeded {
+            RunStatus::Succeeded
+        } else {
+            RunStatus::Failed
+        };
+        if let Err(e) = manager.registry.lock().await.finish_run(
+            name,
+            run_status,
+            exit_code,
+            status.and_then(ExitStatus::signal),
+        ) {
+            warn!("Failed to record the end of the run of '{}': {}", name, e);
+        }
+        manager
+            .events
+            .record(name, EventKind::exited(status), reason)
+            .await;
+        if should_restart {
+            manager
+                .events
+                .record(
+                    name,
+                    EventKind::Restarting {
+                        attempt,
+                        backoff_ms: backoff.as_millis() as u64,
+                    },
+                    None,
+                )
+                .await;
+        }
+
+        // Cancel health check.
+        {
+            let mut handles = manager.health_handles.lock().await;
+            if let Some(handle) = handles.remove(name) {
+                handle.abort();
+            }
+        }
+
+        if should_restart {
+            info!("Restarting daemon '{}' after {:?} backoff", name, backoff);
+
+            let mgr = Arc::clone(manager);
+            let daemon_name = name.to_string();
+            tokio::spawn(async move {
+                tokio::time::sleep(backoff).await;
+                // Reset state to Stopped so we can transition to Starting.
                 {
-                    let mut handles = manager.health_handles.lock().await;
-                    if let Some(handle) = handles.remove(&name) {
-                        handle.abort();
+                    let mut instances = mgr.instances.write().await;
+                    if let Some(inst) = instances.get_mut(&daemon_name) {
+                        inst.state = LifecycleState::Stopped;
                     }
                 }
-
-                if should_restart {
-                    info!("Restarting daemon '{}' after {:?} backoff", name, backoff);
-
-                    let mgr = Arc::clone(&manager);
-                    let daemon_name = name.clone();
-                    tokio::spawn(async move {
-                        tokio::time::sleep(backoff).await;
-                        // Reset state to Stopped so we can transition to Starting.
-                        {
-                            let mut instances = mgr.instances.write().await;
-                            if let Some(inst) = instances.get_mut(&daemon_name) {
-                                inst.state = LifecycleState::Stopped;
-                            }
-                        }
-                        if let Err(e) = mgr.start_daemon(&daemon_name).await {
-                            error!("Failed to restart daemon '{}': {}", daemon_name, e);
-                        }
-                    });
+                if let Err(e) = mgr.start_instance(&daemon_name).await {
+                    error!("Failed to restart daemon '{}': {}", daemon_name, e);
                 }
-            }
+            });
         }
     }
 
@@ -671,8 +2926,10 @@ impl DaemonManager {
     async fn run_health_check(
         instances: Arc<RwLock<HashMap<String, DaemonInstance>>>,
         registry: Arc<Mutex<Registry>>,
+        events: EventLog,
         daemon_name: String,
         health_spec: crate::daemon::HealthCheckSpec,
+        ready_on_healthy: bool,
         mut shutdown_rx: broadcast::Receiver<()>,
     ) {
         use crate::health;
@@ -689,6 +2946,8 @@ impl DaemonManager {
         let interval = Duration::from_secs(health_spec.interval_secs);
         let max_failures = health_spec.retries;
         let mut consecutive_failures: u32 = 0;
+        // Last status written to the event history, so only changes are recorded.
+        let mut reported = HealthStatus::Unknown;
 
         loop {
             tokio::select! {
@@ -705,15 +2964,24 @@ impl DaemonManager {
                 }
             };
 
-            match status {
+            let changed = match status {
                 HealthStatus::Healthy => {
                     consecutive_failures = 0;
                     let mut insts = instances.write().await;
                     if let Some(inst) = insts.get_mut(&daemon_name) {
                         if inst.state == LifecycleState::Running {
                             inst.health_status = HealthStatus::Healthy;
+                        } else if inst.state == LifecycleState::Starting && ready_on_healthy {
+                            info!("Daemon '{}' is healthy and ready", daemon_name);
+                            inst.state = LifecycleState::Running;
+                            inst.health_status = HealthStatus::Healthy;
+                            inst.last_watchdog = Some(Utc::now());
+                            if let Ok(reg) = registry.try_lock() {
+                                reg.update_state(inst).ok();
+                            }
                         }
                     }
+                    reported != HealthStatus::Healthy
                 }
                 HealthStatus::Unhealthy => {
                     consecutive_failures += 1;
@@ -729,9 +2997,19 @@ impl DaemonManager {
                                 reg.update_state(inst).ok();
                             }
                         }
+                        reported != HealthStatus::Unhealthy
+                    } else {
+                        false
                     }
                 }
-                _ => {}
+                _ => false,
+            };
+
+            if changed {
+                reported = status.clone();
+                events
+                    .record(&daemon_name, EventKind::Health { status }, None)
+                    .await;
             }
         }
     }
@@ -746,26 +3024,455 @@ struct ManagerComponents {
     log_manager: Arc<LogManager>,
     shutdown_tx: broadcast::Sender<()>,
     health_handles: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    sockets: Arc<Mutex<SocketTable>>,
+    notify_dir: PathBuf,
+    queued_runs: Arc<Mutex<HashSet<String>>>,
+    events: EventLog,
+}
+
+/// The sockets to pass to a process of `spec`'s daemon, bound first if they
+/// aren't yet.
+async fn bound_sockets(
+    sockets: &Mutex<SocketTable>,
+    spec: &DaemonSpec,
+) -> Result<Arc<Vec<BoundSocket>>> {
+    let mut sockets = sockets.lock().await;
+    sockets.bind(spec)?;
+    Ok(sockets.get(&spec.name).unwrap_or_default())
+}
+
+/// Whether a rolling restart can tell when the new process of `spec` is
+/// ready. An HTTP or TCP health check is answered by whichever process holds
+/// the address, which is still the old one, so it needs `ready_mode =
+/// "notify"` instead.
+fn check_rolling(spec: &DaemonSpec) -> Result<()> {
+    let address_based = spec
+        .health_check
+        .as_ref()
+        .is_some_and(|hc| matches!(hc.check_type, HealthCheckType::Http | HealthCheckType::Tcp));
+    if address_based && spec.ready_mode != ReadyMode::Notify {
+        return Err(SyspulseError::Config(format!(
+            "Daemon '{}' can't be restarted with --rolling: its health check would be \
+             answered by the old process; use ready_mode = \"notify\"",
+            spec.name
+        )));
+    }
+    Ok(())
+}
+
+/// Where a new process of the instance `name` sends its notifications. Each
+/// process gets its own path, so a rolling restart doesn't take the socket
+/// away from the process it replaces.
+fn notify_path(dir: &Path, name: &str) -> PathBuf {
+    let token = Uuid::new_v4().simple().to_string();
+    dir.join(format!("{}.{}.sock", name, &token[..8]))
+}
+
+/// Whether processes of `spec` are given a notify socket: to report
+/// readiness, or to send watchdog keepalives.
+fn uses_notify_socket(spec: &DaemonSpec) -> bool {
+    spec.ready_mode == ReadyMode::Notify || spec.watchdog_secs.is_some()
+}
+
+/// Bind the notify socket of the instance `name` if `spec` uses one, and
+/// point the process at it through `NOTIFY_SOCKET` (and `WATCHDOG_USEC`).
+fn bind_notify_socket(
+    dir: &Path,
+    name: &str,
+    spec: &mut DaemonSpec,
+) -> Result<Option<NotifySocket>> {
+    if !uses_notify_socket(spec) {
+        return Ok(None);
+    }
+    let socket = notify_socket_for(&notify_path(dir, name), spec)?;
+    spec.env.insert(
+        NOTIFY_SOCKET_ENV.to_string(),
+        socket.path().to_string_lossy().into_owned(),
+    );
+    if let Some(secs) = spec.watchdog_secs {
+        spec.env.insert(
+            WATCHDOG_USEC_ENV.to_string(),
+            (secs * 1_000_000).to_string(),
+        );
+    }
+    Ok(Some(socket))
+}
+
+/// Record the notify socket of the instance's new process, and remove the
+/// socket file of its previous one, which a manager that was killed leaves
+/// behind.
+fn set_notify_socket(instance: &mut DaemonInstance, notify: Option<&NotifySocket>) {
+    if let Some(previous) = instance.notify_socket.take() {
+        let _ = std::fs::remove_file(previous);
+    }
+    instance.notify_socket = notify.map(|socket| socket.path().to_path_buf());
+}
+
+/// Bind a notify socket at `path` that processes of `spec` can send to, even
+/// when they run as another user.
+fn notify_socket_for(path: &Path, spec: &DaemonSpec) -> Result<NotifySocket> {
+    let socket = NotifySocket::bind(path)?;
+    #[cfg(unix)]
+    if let Some((uid, gid)) = process::switched_ids(spec)? {
+        socket.chown(uid, gid)?;
+    }
+    Ok(socket)
+}
+
+/// Apply the notifications process `pid` of the instance `name` sends until
+/// it is no longer the instance's process.
+async fn listen_for_notifications(
+    instances: Arc<RwLock<HashMap<String, DaemonInstance>>>,
+    registry: Arc<Mutex<Registry>>,
+    name: String,
+    pid: u32,
+    socket: NotifySocket,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) {
+    loop {
+        let messages = tokio::select! {
+            received = socket.recv() => received.unwrap_or_else(|e| {
+                warn!("Failed to read notification of '{}': {}", name, e);
+                Vec::new()
+            }),
+            _ = tokio::time::sleep(NOTIFY_CHECK_INTERVAL) => Vec::new(),
+            _ = shutdown_rx.recv() => break,
+        };
+
+        let mut instances = instances.write().await;
+        let Some(inst) = instances
+            .get_mut(&name)
+            .filter(|inst| inst.pid == Some(pid) && inst.state.is_active())
+        else {
+            break;
+        };
+        for message in messages {
+            match message {
+                NotifyMessage::Ready => {
+                    if inst.state == LifecycleState::Starting {
+                        info!("Daemon '{}' reported it is ready", name);
+                        inst.state = LifecycleState::Running;
+                        inst.last_watchdog = Some(Utc::now());
+                        if let Ok(reg) = registry.try_lock() {
+                            reg.update_state(inst).ok();
+                        }
+                    }
+                }
+                NotifyMessage::Status(status) => inst.status_message = Some(status),
+                NotifyMessage::Watchdog => inst.last_watchdog = Some(Utc::now()),
+                NotifyMessage::Stopping => info!("Daemon '{}' reported it is stopping", name),
+            }
+        }
+    }
+}
+
+/// Start a run of a scheduled daemon: what a cron tick does, and what
+/// `syspulse trigger` does.
+async fn trigger_run(
+    components: &Arc<ManagerComponents>,
+    name: &str,
+    trigger: RunTrigger,
+) -> Result<Vec<DaemonInstance>> {
+    let message = match trigger {
+        RunTrigger::Schedule => None,
+        RunTrigger::Manual => Some("triggered manually".to_string()),
+        RunTrigger::CatchUp => Some("catching up a missed run".to_string()),
+    };
+    components
+        .events
+        .record(name, EventKind::CronTriggered, message)
+        .await;
+    cron_start_daemon(components, name, trigger).await
+}
+
+/// The process to act on and the `on_unhealthy` action to take, if `inst`
+/// is running, has just been marked unhealthy, and `check` asks for one.
+fn unhealthy_action(
+    inst: &DaemonInstance,
+    check: &HealthCheckSpec,
+) -> Option<(u32, UnhealthyAction)> {
+    if inst.state != LifecycleState::Running || inst.health_status != HealthStatus::Unhealthy {
+        return None;
+    }
+    match check.on_unhealthy {
+        UnhealthyAction::None => None,
+        action => Some((inst.pid?, action)),
+    }
+}
+
+/// How far a started instance is from being ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Readiness {
+    Ready,
+    /// The process ended, so it will not become ready.
+    Exited,
+    Pending,
+}
+
+/// Whether `inst` is ready: running and, if `healthy` is asked for, not
+/// waiting on a health check.
+fn readiness(inst: &DaemonInstance, healthy: bool) -> Readiness {
+    match inst.state {
+        LifecycleState::Running
+            if !healthy
+                || matches!(
+                    inst.health_status,
+                    HealthStatus::Healthy | HealthStatus::NotConfigured
+                ) =>
+        {
+            Readiness::Ready
+        }
+        LifecycleState::Stopped | LifecycleState::Failed | LifecycleState::CrashLooping => {
+            Readiness::Exited
+        }
+        _ => Readiness::Pending,
+    }
+}
+
+/// What a run of a scheduled instance does about its previous run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverlapAction {
+    /// Nothing is running: start the run.
+    Start,
+    /// Stop the previous run, then start this one.
+    Replace,
+    /// Start this run once the previous one has finished.
+    Queue,
+    /// Drop this run.
+    Skip,
+}
+
+/// Decide what a new run does, given whether the instance is still active
+/// and whether a run is already waiting for it.
+fn overlap_action(overlap: ScheduleOverlap, active: bool, queued: bool) -> OverlapAction {
+    if !active {
+        return OverlapAction::Start;
+    }
+    match overlap {
+        ScheduleOverlap::Replace => OverlapAction::Replace,
+        ScheduleOverlap::Queue if !queued => OverlapAction::Queue,
+        ScheduleOverlap::Skip | ScheduleOverlap::Queue => OverlapAction::Skip,
+    }
 }
 
-/// Start a daemon using raw components (for cron callbacks).
-async fn cron_start_daemon(components: &ManagerComponents, name: &str) -> Result<DaemonInstance> {
+/// Start every replica of a daemon using raw components (for cron callbacks).
+async fn cron_start_daemon(
+    components: &Arc<ManagerComponents>,
+    name: &str,
+    trigger: RunTrigger,
+) -> Result<Vec<DaemonInstance>> {
     let spec = {
         let reg = components.registry.lock().await;
         reg.get_spec(name)?
     };
 
+    let mut started = Vec::new();
+    for replica in 0..spec.replicas {
+        let key = replica_name(&spec.name, replica);
+        let active = {
+            let instances = components.instances.read().await;
+            instances.get(&key).is_some_and(|i| i.state.is_active())
+        };
+        let action = {
+            let mut queued = components.queued_runs.lock().await;
+            let action = overlap_action(spec.schedule_overlap, active, queued.contains(&key));
+            if action == OverlapAction::Queue {
+                queued.insert(key.clone());
+            }
+            action
+        };
+        match action {
+            OverlapAction::Start => {}
+            OverlapAction::Replace => {
+                info!("Replacing the previous run of '{}'", key);
+                cron_stop_instance(components, &key, spec.stop_timeout_secs).await?;
+            }
+            OverlapAction::Queue => {
+                info!(
+                    "Previous run of '{}' is still going, queueing this one",
+                    key
+                );
+                tokio::spawn(start_when_finished(Arc::clone(components), key, trigger));
+                continue;
+            }
+            OverlapAction::Skip => {
+                info!(
+                    "Previous run of '{}' is still going, skipping this one",
+                    key
+                );
+                let reg = components.registry.lock().await;
+                reg.record_skipped_run(&key, trigger)?;
+                continue;
+            }
+        }
+        started.push(cron_start_instance(components, &spec, replica, trigger).await?);
+    }
+    Ok(started)
+}
+
+/// Start `runs` catch-up runs of a scheduled daemon, each once no replica
+/// is running any more.
+async fn catch_up(components: Arc<ManagerComponents>, name: String, runs: usize) {
+    let mut shutdown_rx = components.shutdown_tx.subscribe();
+    for _ in 0..runs {
+        loop {
+            let active = {
+                let instances = components.instances.read().await;
+                instances
+                    .values()
+                    .any(|i| i.spec_name == name && i.state.is_active())
+            };
+            if !active {
+                break;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(RUN_QUEUE_POLL_INTERVAL) => {}
+                _ = shutdown_rx.recv() => return,
+            }
+        }
+        if let Err(e) = trigger_run(&components, &name, RunTrigger::CatchUp).await {
+            error!("Failed to catch up a run of '{}': {}", name, e);
+            return;
+        }
+    }
+}
+
+/// Start a queued run of the instance `name` once its current run has
+/// finished.
+async fn start_when_finished(
+    components: Arc<ManagerComponents>,
+    name: String,
+    trigger: RunTrigger,
+) {
+    let mut shutdown_rx = components.shutdown_tx.subscribe();
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(RUN_QUEUE_POLL_INTERVAL) => {}
+            _ = shutdown_rx.recv() => break,
+        }
+        let active = {
+            let instances = components.instances.read().await;
+            match instances.get(&name) {
+                Some(inst) => inst.state.is_active(),
+                // Removed or scaled down in the meantime.
+                None => break,
+            }
+        };
+        if active {
+            continue;
+        }
+
+        components.queued_runs.lock().await.remove(&name);
+        let (spec_name, replica, _) = parse_replica_name(&name);
+        let spec = {
+            let reg = components.registry.lock().await;
+            reg.get_spec(spec_name)
+        };
+        let started = match spec {
+            Ok(spec) => cron_start_instance(&components, &spec, replica, trigger).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = started {
+            error!("Failed to start queued run of '{}': {}", name, e);
+        }
+        return;
+    }
+    components.queued_runs.lock().await.remove(&name);
+}
+
+/// Stop the current run of the instance `name` to make way for the next one.
+async fn cron_stop_instance(
+    components: &ManagerComponents,
+    name: &str,
+    timeout_secs: u64,
+) -> Result<()> {
+    // Claim the instance as Stopping, which keeps exit handling off it, and
+    // don't hold the lock while the process takes its time to stop.
+    let pid = {
+        let mut instances = components.instances.write().await;
+        let Some(instance) = instances.get_mut(name) else {
+            return Ok(());
+        };
+        if !instance.state.is_active() {
+            return Ok(());
+        }
+        instance.state = instance.state.transition_to(LifecycleState::Stopping)?;
+        instance.pid
+    };
+    let reason = "replaced by the next scheduled run".to_string();
+
+    if let Some(handle) = components.health_handles.lock().await.remove(name) {
+        handle.abort();
+    }
+
+    let status = match pid {
+        Some(pid) => {
+            components.process_driver.stop(pid, timeout_secs).await?;
+            reap(components.process_driver.as_ref(), pid).await
+        }
+        None => None,
+    };
+
+    let mut instances = components.instances.write().await;
+    let Some(instance) = instances.get_mut(name) else {
+        return Ok(());
+    };
+    instance.reason = Some(reason.clone());
+    if pid.is_some() {
+        instance.exit_code = status.and_then(ExitStatus::code);
+        instance.exit_signal = status.and_then(ExitStatus::signal);
+    }
+    instance.state = instance.state.transition_to(LifecycleState::Stopped)?;
+    instance.stopped_at = Some(Utc::now());
+    instance.pid = None;
+    instance.pid_start_time = None;
+    instance.health_status = HealthStatus::Unknown;
+
+    {
+        let reg = components.registry.lock().await;
+        reg.update_state(instance)?;
+        reg.finish_run(
+            name,
+            RunStatus::Replaced,
+            instance.exit_code,
+            instance.exit_signal,
+        )?;
+    }
+    let kind = EventKind::Stopped {
+        exit_code: instance.exit_code,
+        signal: instance.exit_signal,
+    };
+    drop(instances);
+    components.events.record(name, kind, Some(reason)).await;
+    Ok(())
+}
+
+async fn cron_start_instance(
+    components: &ManagerComponents,
+    spec: &DaemonSpec,
+    replica: u32,
+    trigger: RunTrigger,
+) -> Result<DaemonInstance> {
+    let name = replica_name(&spec.name, replica);
+    let name = name.as_str();
+
     let mut instances = components.instances.write().await;
     let instance = instances
         .entry(name.to_string())
-        .or_insert_with(|| DaemonInstance::new(name));
+        .or_insert_with(|| DaemonInstance::replica(&spec.name, replica));
 
-    // For scheduled daemons, allow Scheduled -> Starting or Stopped -> Starting.
-    if instance.state == LifecycleState::Scheduled || instance.state == LifecycleState::Stopped {
+    // For scheduled daemons, allow starting from Scheduled, Stopped, or after
+    // a failed run.
+    if matches!(
+        instance.state,
+        LifecycleState::Scheduled | LifecycleState::Stopped | LifecycleState::Failed
+    ) {
         instance.state = instance.state.transition_to(LifecycleState::Starting)?;
     } else if instance.state == LifecycleState::Running {
         // Already running, nothing to do.
         return Ok(instance.clone());
+    } else if instance.state == LifecycleState::CrashLooping {
+        return Err(SyspulseError::CrashLooping(name.to_string()));
     } else {
         return Err(SyspulseError::InvalidStateTransition {
             from: format!("{:?}", instance.state),
@@ -777,16 +3484,31 @@ async fn cron_start_daemon(components: &ManagerComponents, name: &str) -> Result
     instance.stdout_log = Some(stdout_path.clone());
     instance.stderr_log = Some(stderr_path.clone());
 
+    let mut process_spec = replica_spec(spec, replica);
+    let notify = bind_notify_socket(&components.notify_dir, name, &mut process_spec)?;
+    let sockets = bound_sockets(&components.sockets, spec).await?;
     let proc_info = components
         .process_driver
-        .spawn(&spec, &stdout_path, &stderr_path)
+        .spawn(name, &process_spec, &stdout_path, &stderr_path, &sockets)
         .await?;
 
     instance.pid = Some(proc_info.pid);
+    instance.pid_start_time = components
+        .process_driver
+        .identity(proc_info.pid)
+        .await
+        .map(|identity| identity.start_time);
+    set_notify_socket(instance, notify.as_ref());
+    instance.warnings = proc_info.warnings;
     instance.started_at = Some(Utc::now());
     instance.stopped_at = None;
     instance.exit_code = None;
-    instance.state = instance.state.transition_to(LifecycleState::Running)?;
+    instance.exit_signal = None;
+    instance.status_message = None;
+    instance.last_watchdog = None;
+    if spec.ready_mode == ReadyMode::Immediate {
+        instance.state = instance.state.transition_to(LifecycleState::Running)?;
+    }
 
     if spec.health_check.is_some() {
         instance.health_status = HealthStatus::Unknown;
@@ -797,21 +3519,48 @@ async fn cron_start_daemon(components: &ManagerComponents, name: &str) -> Result
     {
         let reg = components.registry.lock().await;
         reg.update_state(instance)?;
+        reg.start_run(name, trigger, proc_info.pid)?;
     }
 
     let result = instance.clone();
     drop(instances);
 
+    components
+        .events
+        .record(name, EventKind::Started { pid: proc_info.pid }, None)
+        .await;
+    if let Some(socket) = notify {
+        tokio::spawn(listen_for_notifications(
+            Arc::clone(&components.instances),
+            Arc::clone(&components.registry),
+            name.to_string(),
+            proc_info.pid,
+            socket,
+            components.shutdown_tx.subscribe(),
+        ));
+    }
+
     // Start health check if configured.
     if let Some(ref health_spec) = spec.health_check {
         let daemon_name = name.to_string();
         let shutdown_rx = components.shutdown_tx.subscribe();
         let insts = Arc::clone(&components.instances);
         let registry = Arc::clone(&components.registry);
+        let events = components.events.clone();
         let hs: crate::daemon::HealthCheckSpec = health_spec.clone();
+        let ready_on_healthy = spec.ready_mode == ReadyMode::Health;
 
         let handle = tokio::spawn(async move {
-            DaemonManager::run_health_check(insts, registry, daemon_name, hs, shutdown_rx).await;
+            DaemonManager::run_health_check(
+                insts,
+                registry,
+                events,
+                daemon_name,
+                hs,
+                ready_on_healthy,
+                shutdown_rx,
+            )
+            .await;
         });
 
         let mut handles = components.health_handles.lock().await;
@@ -826,11 +3575,100 @@ async fn cron_start_daemon(components: &ManagerComponents, name: &str) -> Result
     Ok(result)
 }
 
+/// Records lifecycle events in the registry and pushes them, together with
+/// unrecorded resource samples, to subscribers.
+#[derive(Clone)]
+struct EventLog {
+    registry: Arc<Mutex<Registry>>,
+    tx: broadcast::Sender<DaemonEvent>,
+}
+
+impl EventLog {
+    fn new(registry: Arc<Mutex<Registry>>) -> Self {
+        let (tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { registry, tx }
+    }
+
+    /// Append an event to a daemon's history. Failures are only logged.
+    async fn record(&self, name: &str, kind: EventKind, message: Option<String>) {
+        let recorded = {
+            let reg = self.registry.lock().await;
+            reg.record_event(name, kind, message)
+        };
+        match recorded {
+            // Nobody listening is not an error.
+            Ok(event) => {
+                let _ = self.tx.send(event);
+            }
+            Err(e) => warn!("Failed to record event for '{}': {}", name, e),
+        }
+    }
+
+    /// Push an event to subscribers without recording it.
+    fn publish(&self, name: &str, timestamp: DateTime<Utc>, kind: EventKind) {
+        let _ = self.tx.send(DaemonEvent {
+            id: 0,
+            timestamp,
+            name: name.to_string(),
+            kind,
+            message: None,
+        });
+    }
+
+    fn has_subscribers(&self) -> bool {
+        self.tx.receiver_count() > 0
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<DaemonEvent> {
+        self.tx.subscribe()
+    }
+}
+
+/// Collect how a stopped or killed process ended, giving the driver a moment
+/// to reap it.
+async fn reap(driver: &dyn ProcessDriver, pid: u32) -> Option<ExitStatus> {
+    let deadline = tokio::time::Instant::now() + REAP_TIMEOUT;
+    while driver.is_alive(pid).await && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+    }
+    driver.wait(pid).await.ok().flatten()
+}
+
+/// The spec a replica is spawned from: its daemon's, with the replica's
+/// index added to the environment.
+fn replica_spec(spec: &DaemonSpec, replica: u32) -> DaemonSpec {
+    let mut spec = spec.clone();
+    spec.env
+        .insert(INSTANCE_ENV.to_string(), replica.to_string());
+    spec
+}
+
+/// `PID 42`, or `3 replicas, PIDs 42, 43, 44`, for a started-message.
+fn pids(instances: &[DaemonInstance]) -> String {
+    let pids: Vec<String> = instances
+        .iter()
+        .map(|i| i.pid.unwrap_or(0).to_string())
+        .collect();
+    match pids.as_slice() {
+        [pid] => format!("PID {}", pid),
+        _ => format!("{} replicas, PIDs {}", pids.len(), pids.join(", ")),
+    }
+}
+
+/// How long a `wait`ing request blocks for readiness.
+fn wait_timeout(timeout_secs: Option<u64>) -> Duration {
+    timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_WAIT_TIMEOUT)
+}
+
 fn error_response(e: SyspulseError) -> Response {
     let code = match &e {
         SyspulseError::DaemonNotFound(_) => 404,
         SyspulseError::DaemonAlreadyExists(_) => 409,
         SyspulseError::InvalidStateTransition { .. } => 409,
+        SyspulseError::CrashLooping(_) => 409,
+        SyspulseError::RevisionConflict { .. } => 409,
         SyspulseError::Process(_) => 500,
         SyspulseError::HealthCheck(_) => 500,
         SyspulseError::Ipc(_) => 500,
@@ -847,3 +3685,278 @@ fn error_response(e: SyspulseError) -> Response {
         message: e.to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_process_gets_its_own_notify_socket() {
+        let dir = Path::new("/run/syspulse/notify");
+        let first = notify_path(dir, "web:1");
+        let second = notify_path(dir, "web:1");
+        assert_ne!(first, second);
+        assert!(first.starts_with(dir));
+        let file = first.file_name().unwrap().to_string_lossy();
+        assert!(file.starts_with("web:1.") && file.ends_with(".sock"));
+    }
+
+    fn spec(toml: &str) -> DaemonSpec {
+        toml::from_str(&format!("name = \"web\"\ncommand = [\"web\"]\n{}", toml)).unwrap()
+    }
+
+    #[test]
+    fn rolling_restart_needs_a_readiness_signal_of_the_new_process() {
+        let http = "[health_check]\ntype = \"http\"\ntarget = \"http://127.0.0.1:8080/\"";
+        assert!(matches!(
+            check_rolling(&spec(http)),
+            Err(SyspulseError::Config(_))
+        ));
+        let notify = format!("ready_mode = \"notify\"\n{}", http);
+        assert!(check_rolling(&spec(&notify)).is_ok());
+        let command = "[health_check]\ntype = \"command\"\ntarget = \"web --check\"";
+        assert!(check_rolling(&spec(command)).is_ok());
+        assert!(check_rolling(&spec("")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn failed_rolling_restart_keeps_the_old_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DaemonManager::new(Some(dir.path().to_path_buf())).unwrap();
+        // Only the first process finds no marker and stays up.
+        let mut web = spec("");
+        web.command = vec![
+            "sh".into(),
+            "-c".into(),
+            "[ -e started ] && exit 1; touch started; exec sleep 30".into(),
+        ];
+        web.working_dir = Some(dir.path().to_path_buf());
+        manager.add_daemon(web).await.unwrap();
+        let old_pid = manager.start_daemon("web").await.unwrap()[0].pid;
+
+        let err = manager
+            .rolling_restart_daemon("web", Duration::from_secs(10))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Rolled back"));
+        let status = manager.status("web").await.unwrap();
+        assert_eq!(status.state, LifecycleState::Running);
+        assert_eq!(status.pid, old_pid);
+        assert!(manager.process_driver.is_alive(old_pid.unwrap()).await);
+
+        manager.stop_daemon("web", true, None).await.unwrap();
+    }
+
+    #[test]
+    fn overlap_decides_what_a_new_run_does() {
+        use OverlapAction::*;
+        use ScheduleOverlap as O;
+        for overlap in [O::Skip, O::Queue, O::Replace] {
+            assert_eq!(overlap_action(overlap, false, false), Start);
+        }
+        assert_eq!(overlap_action(O::Skip, true, false), Skip);
+        assert_eq!(overlap_action(O::Replace, true, false), Replace);
+        assert_eq!(overlap_action(O::Queue, true, false), Queue);
+        // Only one run waits at a time.
+        assert_eq!(overlap_action(O::Queue, true, true), Skip);
+    }
+
+    async fn scheduled(dir: &Path, overlap: &str) -> DaemonManager {
+        let manager = DaemonManager::new(Some(dir.to_path_buf())).unwrap();
+        let mut job = spec(&format!(
+            "schedule = \"0 0 0 1 1 *\"\nschedule_overlap = \"{}\"",
+            overlap
+        ));
+        job.command = vec!["sleep".into(), "30".into()];
+        manager.add_daemon(job).await.unwrap();
+        manager
+    }
+
+    #[tokio::test]
+    async fn trigger_skips_while_the_previous_run_is_going() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = scheduled(dir.path(), "skip").await;
+        let first = manager.trigger("web").await.unwrap();
+        assert_eq!(first.len(), 1);
+        assert!(manager.trigger("web").await.unwrap().is_empty());
+
+        let runs = manager.runs("web", None).await.unwrap();
+        let statuses: Vec<_> = runs.iter().map(|r| r.status).collect();
+        assert_eq!(statuses, [RunStatus::Running, RunStatus::Skipped]);
+        assert!(runs.iter().all(|r| r.trigger == RunTrigger::Manual));
+        assert_eq!(manager.status("web").await.unwrap().pid, first[0].pid);
+
+        manager.stop_daemon("web", true, None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn trigger_replaces_the_previous_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = scheduled(dir.path(), "replace").await;
+        let old_pid = manager.trigger("web").await.unwrap()[0].pid.unwrap();
+        let new = manager.trigger("web").await.unwrap();
+        assert_eq!(new.len(), 1);
+        assert_ne!(new[0].pid, Some(old_pid));
+        assert!(!manager.process_driver.is_alive(old_pid).await);
+
+        let runs = manager.runs("web", None).await.unwrap();
+        let statuses: Vec<_> = runs.iter().map(|r| r.status).collect();
+        assert_eq!(statuses, [RunStatus::Replaced, RunStatus::Running]);
+
+        manager.stop_daemon("web", true, None).await.unwrap();
+    }
+
+    #[test]
+    fn ready_once_running_and_healthy() {
+        let mut inst = DaemonInstance::new("web");
+        let mut check = |state, health| {
+            inst.state = state;
+            inst.health_status = health;
+            (readiness(&inst, false), readiness(&inst, true))
+        };
+        use HealthStatus as H;
+        use LifecycleState as S;
+        use Readiness::*;
+        assert_eq!(check(S::Starting, H::Unknown), (Pending, Pending));
+        assert_eq!(check(S::Running, H::Unknown), (Ready, Pending));
+        assert_eq!(check(S::Running, H::Unhealthy), (Ready, Pending));
+        assert_eq!(check(S::Running, H::Healthy), (Ready, Ready));
+        assert_eq!(check(S::Running, H::NotConfigured), (Ready, Ready));
+        for state in [S::Stopped, S::Failed, S::CrashLooping] {
+            assert_eq!(check(state, H::Unknown), (Exited, Exited));
+        }
+    }
+
+    #[tokio::test]
+    async fn waiting_for_an_unhealthy_daemon_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DaemonManager::new(Some(dir.path().to_path_buf())).unwrap();
+        let mut web = spec("[health_check]\ntype = \"command\"\ntarget = \"false\"");
+        web.command = vec!["sleep".into(), "30".into()];
+        manager.add_daemon(web).await.unwrap();
+        manager.start_daemon("web").await.unwrap();
+
+        let timeout = Duration::from_millis(300);
+        assert!(matches!(
+            manager.wait_until_ready("web", timeout).await,
+            Err(SyspulseError::Timeout(t)) if t == timeout
+        ));
+        let started = manager.wait_until_started("web", timeout).await.unwrap();
+        assert_eq!(started[0].state, LifecycleState::Running);
+
+        manager.stop_daemon("web", true, None).await.unwrap();
+    }
+
+    #[test]
+    fn unhealthy_action_needs_a_running_unhealthy_process() {
+        let check = |action| {
+            let web = spec(&format!(
+                "[health_check]\ntype = \"command\"\ntarget = \"false\"\non_unhealthy = \"{}\"",
+                action
+            ));
+            web.health_check.unwrap()
+        };
+        let mut inst = DaemonInstance::new("web");
+        inst.state = LifecycleState::Running;
+        inst.pid = Some(42);
+        inst.health_status = HealthStatus::Unhealthy;
+        assert_eq!(
+            unhealthy_action(&inst, &check("stop")),
+            Some((42, UnhealthyAction::Stop))
+        );
+        assert_eq!(
+            unhealthy_action(&inst, &check("restart")),
+            Some((42, UnhealthyAction::Restart))
+        );
+        assert_eq!(unhealthy_action(&inst, &check("none")), None);
+
+        inst.health_status = HealthStatus::Unknown;
+        assert_eq!(unhealthy_action(&inst, &check("stop")), None);
+        inst.health_status = HealthStatus::Unhealthy;
+        inst.state = LifecycleState::Stopping;
+        assert_eq!(unhealthy_action(&inst, &check("stop")), None);
+        inst.state = LifecycleState::Running;
+        inst.pid = None;
+        assert_eq!(unhealthy_action(&inst, &check("stop")), None);
+    }
+
+    #[tokio::test]
+    async fn unhealthy_daemon_is_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(DaemonManager::new(Some(dir.path().to_path_buf())).unwrap());
+        let mut web =
+            spec("[health_check]\ntype = \"command\"\ntarget = \"false\"\non_unhealthy = \"stop\"");
+        web.command = vec!["sleep".into(), "30".into()];
+        manager.add_daemon(web).await.unwrap();
+        let pid = manager.start_daemon("web").await.unwrap()[0].pid.unwrap();
+        manager
+            .wait_until_started("web", Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        manager
+            .instances
+            .write()
+            .await
+            .get_mut("web")
+            .unwrap()
+            .health_status = HealthStatus::Unhealthy;
+        DaemonManager::handle_unhealthy(&manager).await;
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        while manager.status("web").await.unwrap().state != LifecycleState::Stopped {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "daemon was not stopped"
+            );
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+        let status = manager.status("web").await.unwrap();
+        assert!(status.reason.unwrap().contains("health check failed"));
+        assert!(!manager.process_driver.is_alive(pid).await);
+    }
+
+    #[tokio::test]
+    async fn apply_rejects_a_cycle_with_a_daemon_not_in_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DaemonManager::new(Some(dir.path().to_path_buf())).unwrap();
+        let mut x = spec("depends_on = [\"y\"]");
+        x.name = "x".into();
+        let mut y = spec("");
+        y.name = "y".into();
+        manager.add_daemon(y.clone()).await.unwrap();
+        manager.add_daemon(x).await.unwrap();
+
+        y.depends_on = spec("depends_on = [\"x\"]").depends_on;
+        for dry_run in [true, false] {
+            let err = manager
+                .apply(vec![y.clone()], dry_run, false)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Dependency cycle"), "{}", err);
+        }
+        let reg = manager.registry.lock().await;
+        assert!(reg.get_spec("y").unwrap().depends_on.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rolling_restart_cannot_be_forced() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(DaemonManager::new(Some(dir.path().to_path_buf())).unwrap());
+        manager.add_daemon(spec("")).await.unwrap();
+        let response = manager
+            .handle_request(Request::Restart {
+                name: "web".into(),
+                force: true,
+                wait: false,
+                timeout_secs: None,
+                rolling: true,
+            })
+            .await;
+        let Response::Error { code, message } = response else {
+            panic!("expected an error, got {:?}", response);
+        };
+        assert_eq!(code, 400);
+        assert!(message.contains("can't be forced"), "{}", message);
+    }
+}
@@ -0,0 +1,193 @@
+//! The `sd_notify` protocol: daemons report readiness and status by sending
+//! datagrams such as `READY=1` to the Unix socket named in `NOTIFY_SOCKET`.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, SyspulseError};
+
+/// Environment variable telling a daemon where to send notifications.
+pub const NOTIFY_SOCKET_ENV: &str = "NOTIFY_SOCKET";
+
+/// Environment variable holding the watchdog interval in microseconds.
+pub const WATCHDOG_USEC_ENV: &str = "WATCHDOG_USEC";
+
+/// Largest datagram read; longer messages are truncated.
+#[cfg(unix)]
+const MAX_DATAGRAM: usize = 4096;
+
+/// A notification understood by the manager. Other assignments in a
+/// datagram are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotifyMessage {
+    /// `READY=1`: startup is complete.
+    Ready,
+    /// `STATUS=...`: a free-form description of what the daemon is doing.
+    Status(String),
+    /// `WATCHDOG=1`: the daemon is alive.
+    Watchdog,
+    /// `STOPPING=1`: the daemon is shutting down.
+    Stopping,
+}
+
+/// Parse a datagram of newline-separated `KEY=VALUE` assignments.
+pub fn parse(datagram: &str) -> Vec<NotifyMessage> {
+    datagram
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            match (key, value) {
+                ("READY", "1") => Some(NotifyMessage::Ready),
+                ("STATUS", status) => Some(NotifyMessage::Status(status.to_string())),
+                ("WATCHDOG", "1") => Some(NotifyMessage::Watchdog),
+                ("STOPPING", "1") => Some(NotifyMessage::Stopping),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The socket a process sends its notifications to.
+#[derive(Debug)]
+pub struct NotifySocket {
+    #[cfg(unix)]
+    socket: tokio::net::UnixDatagram,
+    path: PathBuf,
+}
+
+impl NotifySocket {
+    /// Bind a socket at `path`, replacing one left behind by an earlier
+    /// process.
+    #[cfg(unix)]
+    pub fn bind(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        let socket = tokio::net::UnixDatagram::bind(path).map_err(|e| {
+            SyspulseError::Process(format!(
+                "Failed to bind notify socket '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Self {
+            socket,
+            path: path.to_path_buf(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn bind(path: &Path) -> Result<Self> {
+        Err(SyspulseError::Config(format!(
+            "Notify socket '{}' is only supported on Unix",
+            path.display()
+        )))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Let a daemon running as `uid`/`gid` send to the socket: hand the
+    /// socket to it, and let it through the socket's directory.
+    #[cfg(unix)]
+    pub fn chown(&self, uid: u32, gid: u32) -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        std::os::unix::fs::chown(&self.path, Some(uid), Some(gid))?;
+        if let Some(dir) = self.path.parent() {
+            let mut permissions = std::fs::metadata(dir)?.permissions();
+            permissions.set_mode(permissions.mode() | 0o111);
+            std::fs::set_permissions(dir, permissions)?;
+        }
+        Ok(())
+    }
+
+    /// Wait for the next datagram and return the notifications in it.
+    #[cfg(unix)]
+    pub async fn recv(&self) -> Result<Vec<NotifyMessage>> {
+        let mut buf = vec![0; MAX_DATAGRAM];
+        let len = self.socket.recv(&mut buf).await?;
+        Ok(parse(&String::from_utf8_lossy(&buf[..len])))
+    }
+
+    #[cfg(not(unix))]
+    pub async fn recv(&self) -> Result<Vec<NotifyMessage>> {
+        std::future::pending().await
+    }
+}
+
+#[cfg(unix)]
+impl Drop for NotifySocket {
+    /// Remove the socket file once nothing listens on it any more.
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_assignments() {
+        assert_eq!(
+            parse("READY=1\nSTATUS=Listening on :8080\nMAINPID=42\nWATCHDOG=1\nSTOPPING=1"),
+            vec![
+                NotifyMessage::Ready,
+                NotifyMessage::Status("Listening on :8080".into()),
+                NotifyMessage::Watchdog,
+                NotifyMessage::Stopping,
+            ]
+        );
+        assert!(parse("READY=0\ngarbage").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn receives_datagrams() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify").join("web.sock");
+        let socket = NotifySocket::bind(&path).unwrap();
+
+        let client = std::os::unix::net::UnixDatagram::unbound().unwrap();
+        client.send_to(b"STATUS=warming up", &path).unwrap();
+        client.send_to(b"READY=1", &path).unwrap();
+        assert_eq!(
+            socket.recv().await.unwrap(),
+            vec![NotifyMessage::Status("warming up".into())]
+        );
+        assert_eq!(socket.recv().await.unwrap(), vec![NotifyMessage::Ready]);
+
+        // Dropping the socket removes its file, and binding replaces one left
+        // behind.
+        drop(socket);
+        assert!(!path.exists());
+        std::fs::write(&path, "").unwrap();
+        NotifySocket::bind(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn chown_opens_the_directory_to_the_daemon() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let notify_dir = dir.path().join("notify");
+        std::fs::create_dir(&notify_dir).unwrap();
+        std::fs::set_permissions(&notify_dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+        let socket = NotifySocket::bind(&notify_dir.join("web.sock")).unwrap();
+
+        let (uid, gid) = (
+            nix::unistd::getuid().as_raw(),
+            nix::unistd::getgid().as_raw(),
+        );
+        socket.chown(uid, gid).unwrap();
+        assert_eq!(std::fs::metadata(socket.path()).unwrap().uid(), uid);
+        let mode = std::fs::metadata(&notify_dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o711);
+    }
+}
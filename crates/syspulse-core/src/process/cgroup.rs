@@ -0,0 +1,207 @@
+//! cgroup v2 support for enforcing `max_cpu_percent` on Linux.
+//!
+//! The manager owns a subtree inside its own cgroup:
+//!
+//! ```text
+//! <manager cgroup>/
+//!     syspulse-manager/      the manager process itself
+//!     syspulse/              cpu controller enabled
+//!         <name>.daemon/     one leaf per CPU-limited instance, with cpu.max;
+//!                            removed with the instance
+//! ```
+//!
+//! cgroup v2 only lets a cgroup hand controllers to its children when it has
+//! no processes of its own, which is why the manager moves itself into a leaf
+//! first. This only works when the manager's cgroup is delegated to it, e.g. a
+//! systemd service with `Delegate=yes`, or when running as root in the root
+//! cgroup.
+
+use std::ffi::CString;
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+const CGROUP_MOUNT: &str = "/sys/fs/cgroup";
+
+/// cpu.max period in microseconds (the kernel default).
+const CPU_PERIOD_USEC: u64 = 100_000;
+
+/// Smallest quota the kernel accepts.
+const MIN_CPU_QUOTA_USEC: u64 = 1_000;
+
+pub struct CgroupTree {
+    root: PathBuf,
+}
+
+impl CgroupTree {
+    /// Set up the syspulse subtree under the manager's cgroup. The error is a
+    /// human-readable reason suitable for surfacing as a daemon warning.
+    pub fn setup() -> std::result::Result<Self, String> {
+        let mount = Path::new(CGROUP_MOUNT);
+        if !mount.join("cgroup.controllers").exists() {
+            return Err(format!("cgroup v2 is not mounted at {}", CGROUP_MOUNT));
+        }
+
+        let proc_cgroup = fs::read_to_string("/proc/self/cgroup")
+            .map_err(|e| format!("cannot read /proc/self/cgroup: {}", e))?;
+        let own = parse_proc_cgroup(&proc_cgroup)
+            .ok_or_else(|| "manager is not in a cgroup v2 hierarchy".to_string())?;
+        let base = mount.join(own.trim_start_matches('/'));
+
+        let controllers = fs::read_to_string(base.join("cgroup.controllers"))
+            .map_err(|e| format!("cannot read controllers of {}: {}", base.display(), e))?;
+        if !controllers.split_whitespace().any(|c| c == "cpu") {
+            return Err(format!(
+                "the cpu controller is not delegated to {}",
+                base.display()
+            ));
+        }
+
+        if base != mount {
+            evacuate_manager(&base)?;
+        }
+        enable_cpu(&base)?;
+
+        let root = base.join("syspulse");
+        create_dir(&root)?;
+        enable_cpu(&root)?;
+
+        tracing::info!(root = %root.display(), "cgroup v2 CPU limits enabled");
+        Ok(Self { root })
+    }
+
+    /// Create (or reuse) the cgroup for the instance `name` (`name` or
+    /// `name:replica`) with `cpu.max` set from `cpu_percent`, and return the
+    /// path of its `cgroup.procs` file.
+    pub fn prepare(&self, name: &str, cpu_percent: f64) -> std::result::Result<CString, String> {
+        let max = cpu_max(cpu_percent)
+            .ok_or_else(|| format!("invalid max_cpu_percent {}", cpu_percent))?;
+
+        let dir = self.root.join(leaf_name(name));
+        create_dir(&dir)?;
+        write(&dir.join("cpu.max"), &max)?;
+
+        CString::new(dir.join("cgroup.procs").as_os_str().as_bytes())
+            .map_err(|e| format!("invalid cgroup path: {}", e))
+    }
+
+    /// Remove the cgroup of the instance `name`. One that still has
+    /// processes, e.g. descendants that outlived the daemon, is left alone.
+    pub fn remove(&self, name: &str) {
+        let dir = self.root.join(leaf_name(name));
+        match fs::remove_dir(&dir) {
+            Ok(()) => {}
+            Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::ResourceBusy) => {}
+            Err(e) => tracing::warn!("cannot remove {}: {}", dir.display(), e),
+        }
+    }
+}
+
+/// Move the manager out of `base` so `base` can delegate controllers.
+fn evacuate_manager(base: &Path) -> std::result::Result<(), String> {
+    let own_pid = std::process::id().to_string();
+    let procs = fs::read_to_string(base.join("cgroup.procs"))
+        .map_err(|e| format!("cannot read {}: {}", base.display(), e))?;
+    if procs.lines().any(|p| p.trim() != own_pid) {
+        return Err(format!(
+            "{} is shared with other processes; run syspulse in its own delegated cgroup",
+            base.display()
+        ));
+    }
+
+    let leaf = base.join("syspulse-manager");
+    create_dir(&leaf)?;
+    write(&leaf.join("cgroup.procs"), &own_pid)
+}
+
+fn enable_cpu(cgroup: &Path) -> std::result::Result<(), String> {
+    write(&cgroup.join("cgroup.subtree_control"), "+cpu")
+}
+
+fn create_dir(path: &Path) -> std::result::Result<(), String> {
+    fs::create_dir_all(path).map_err(|e| format!("cannot create {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, value: &str) -> std::result::Result<(), String> {
+    fs::write(path, value).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+/// Extract the unified (v2) hierarchy path from `/proc/<pid>/cgroup`.
+fn parse_proc_cgroup(content: &str) -> Option<&str> {
+    content.lines().find_map(|line| line.strip_prefix("0::"))
+}
+
+/// `cpu.max` value for a percentage of one CPU. Values above 100 allow the
+/// daemon to use more than one core.
+fn cpu_max(cpu_percent: f64) -> Option<String> {
+    if !cpu_percent.is_finite() || cpu_percent <= 0.0 {
+        return None;
+    }
+    let quota = (cpu_percent / 100.0 * CPU_PERIOD_USEC as f64).round() as u64;
+    Some(format!(
+        "{} {}",
+        quota.max(MIN_CPU_QUOTA_USEC),
+        CPU_PERIOD_USEC
+    ))
+}
+
+/// Directory name for an instance's cgroup. Bytes other than ASCII letters,
+/// digits, `-` and `_` are percent-escaped, so different names never share a
+/// cgroup. The suffix keeps names from clashing with interface files such as
+/// `cpu.max`.
+fn leaf_name(name: &str) -> String {
+    let mut leaf = String::with_capacity(name.len() + ".daemon".len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            leaf.push(byte as char);
+        } else {
+            let _ = write!(leaf, "%{:02X}", byte);
+        }
+    }
+    leaf.push_str(".daemon");
+    leaf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unified_hierarchy_line() {
+        let content = "12:cpu,cpuacct:/legacy\n0::/user.slice/user-1000.slice/syspulse.service\n";
+        assert_eq!(
+            parse_proc_cgroup(content),
+            Some("/user.slice/user-1000.slice/syspulse.service")
+        );
+        assert_eq!(parse_proc_cgroup("0::/\n"), Some("/"));
+        assert_eq!(parse_proc_cgroup("4:memory:/foo\n"), None);
+    }
+
+    #[test]
+    fn cpu_max_from_percent() {
+        assert_eq!(cpu_max(50.0).as_deref(), Some("50000 100000"));
+        assert_eq!(cpu_max(250.0).as_deref(), Some("250000 100000"));
+        assert_eq!(cpu_max(0.1).as_deref(), Some("1000 100000"));
+        assert_eq!(cpu_max(0.0), None);
+        assert_eq!(cpu_max(-5.0), None);
+        assert_eq!(cpu_max(f64::NAN), None);
+    }
+
+    #[test]
+    fn leaf_names_are_escaped() {
+        assert_eq!(leaf_name("web-api"), "web-api.daemon");
+        assert_eq!(leaf_name("cpu.max"), "cpu%2Emax.daemon");
+        assert_eq!(leaf_name("../etc"), "%2E%2E%2Fetc.daemon");
+        assert_eq!(leaf_name("web:1"), "web%3A1.daemon");
+    }
+
+    #[test]
+    fn different_names_get_different_leaves() {
+        let names = ["a.b", "a_b", "a%2Eb", "web:1", "web_1", "web%3A1"];
+        let leaves: std::collections::HashSet<String> =
+            names.iter().map(|n| leaf_name(n)).collect();
+        assert_eq!(leaves.len(), names.len());
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+
+use super::{ExitStatus, ProcessExit};
+
+/// Capacity of the exit notification channel. Missed notifications are
+/// caught by the manager's periodic liveness check.
+const EXIT_CHANNEL_CAPACITY: usize = 64;
+
+enum ChildState {
+    Running,
+    /// Reaped; `None` if waiting on the child failed.
+    Exited(Option<ExitStatus>),
+}
+
+/// Owns the handles of spawned children and reaps each one as soon as it
+/// exits, so no zombies are left behind and the exact status is kept until
+/// the manager asks for it.
+pub(super) struct ExitTracker {
+    children: Arc<Mutex<HashMap<u32, ChildState>>>,
+    tx: broadcast::Sender<ProcessExit>,
+}
+
+impl ExitTracker {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(EXIT_CHANNEL_CAPACITY);
+        Self {
+            children: Arc::new(Mutex::new(HashMap::new())),
+            tx,
+        }
+    }
+
+    /// Wait for `child` on a background task, record how it ended and notify
+    /// subscribers.
+    pub fn track(&self, pid: u32, mut child: tokio::process::Child) {
+        self.children
+            .lock()
+            .unwrap()
+            .insert(pid, ChildState::Running);
+
+        let children = Arc::clone(&self.children);
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let status = match child.wait().await {
+                Ok(status) => Some(ExitStatus::from(status)),
+                Err(e) => {
+                    tracing::warn!(pid, "Failed to wait for process: {}", e);
+                    None
+                }
+            };
+            children
+                .lock()
+                .unwrap()
+                .insert(pid, ChildState::Exited(status));
+            let _ = tx.send(ProcessExit { pid, status });
+        });
+    }
+
+    /// Whether `pid` is still running, or `None` if it isn't tracked.
+    pub fn is_alive(&self, pid: u32) -> Option<bool> {
+        self.children
+            .lock()
+            .unwrap()
+            .get(&pid)
+            .map(|state| matches!(state, ChildState::Running))
+    }
+
+    /// How a tracked child ended, forgetting it. `None` while it's running
+    /// or if it isn't tracked.
+    pub fn take_exit(&self, pid: u32) -> Option<ExitStatus> {
+        let mut children = self.children.lock().unwrap();
+        match children.get(&pid) {
+            Some(ChildState::Exited(status)) => {
+                let status = *status;
+                children.remove(&pid);
+                status
+            }
+            _ => None,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ProcessExit> {
+        self.tx.subscribe()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reaps_child_and_reports_exit_code() {
+        let tracker = ExitTracker::new();
+        let mut exits = tracker.subscribe();
+
+        let child = tokio::process::Command::new("sh")
+            .args(["-c", "exit 3"])
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap();
+        tracker.track(pid, child);
+
+        let exit = exits.recv().await.unwrap();
+        assert_eq!(exit.pid, pid);
+        assert_eq!(exit.status, Some(ExitStatus::Code(3)));
+        assert_eq!(tracker.is_alive(pid), Some(false));
+        assert_eq!(tracker.take_exit(pid), Some(ExitStatus::Code(3)));
+        assert_eq!(tracker.is_alive(pid), None);
+    }
+
+    #[tokio::test]
+    async fn reports_terminating_signal() {
+        let tracker = ExitTracker::new();
+        let mut exits = tracker.subscribe();
+
+        let child = tokio::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap();
+        tracker.track(pid, child);
+        assert_eq!(tracker.is_alive(pid), Some(true));
+
+        nix::sys::signal::kill(
+            nix::unistd::Pid::from_raw(pid as i32),
+            nix::sys::signal::Signal::SIGTERM,
+        )
+        .unwrap();
+        let exit = exits.recv().await.unwrap();
+        assert_eq!(exit.status, Some(ExitStatus::Signal(libc::SIGTERM)));
+    }
+}
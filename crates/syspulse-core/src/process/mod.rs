@@ -1,39 +1,142 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use tokio::sync::broadcast;
 
 use crate::daemon::DaemonSpec;
 use crate::error::Result;
+use crate::sockets::BoundSocket;
 
 #[derive(Debug)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub alive: bool,
+    /// Parts of the spec that could not be honoured, e.g. a CPU limit on a
+    /// host without delegated cgroups. The process was started regardless.
+    pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ResourceUsage {
     pub memory_bytes: u64,
     pub cpu_percent: f64,
+    /// Number of processes the figures were summed over.
+    pub process_count: u32,
+}
+
+/// How a process ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// It exited with this code.
+    Code(i32),
+    /// It was terminated by this signal (Unix only).
+    Signal(i32),
+}
+
+impl ExitStatus {
+    pub fn code(self) -> Option<i32> {
+        match self {
+            ExitStatus::Code(code) => Some(code),
+            ExitStatus::Signal(_) => None,
+        }
+    }
+
+    pub fn signal(self) -> Option<i32> {
+        match self {
+            ExitStatus::Code(_) => None,
+            ExitStatus::Signal(signal) => Some(signal),
+        }
+    }
+}
+
+impl From<std::process::ExitStatus> for ExitStatus {
+    fn from(status: std::process::ExitStatus) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return ExitStatus::Signal(signal);
+            }
+        }
+        // Without a signal there is always a code.
+        ExitStatus::Code(status.code().unwrap_or_default())
+    }
+}
+
+/// What tells a process apart from a later one that reused its PID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessIdentity {
+    /// Seconds since the epoch.
+    pub start_time: u64,
+    pub command: Vec<String>,
+}
+
+impl ProcessIdentity {
+    /// Whether this is the process a daemon was started as. The recorded
+    /// start time is authoritative; without one (e.g. state saved by an
+    /// older version), the command line has to match instead.
+    pub fn matches(&self, start_time: Option<u64>, command: &[String]) -> bool {
+        match start_time {
+            Some(start_time) => self.start_time == start_time,
+            None => self.command == command,
+        }
+    }
+}
+
+/// Sent when a process spawned by the driver exits.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessExit {
+    pub pid: u32,
+    /// `None` if the process was reaped but its status could not be read.
+    pub status: Option<ExitStatus>,
 }
 
 #[async_trait]
 pub trait ProcessDriver: Send + Sync {
+    /// Prepare anything spawns rely on that has to exist before the first
+    /// process is started. Called once when the manager starts.
+    fn init(&self) {}
+
+    /// Clean up what spawning the instance `name` set up, once the instance
+    /// has been removed.
+    fn forget(&self, _name: &str) {}
+
+    /// Start a process of the instance `name` (`name` or `name:replica`).
     async fn spawn(
         &self,
+        name: &str,
         spec: &DaemonSpec,
         stdout_path: &PathBuf,
         stderr_path: &PathBuf,
+        sockets: &[BoundSocket],
     ) -> Result<ProcessInfo>;
 
     async fn stop(&self, pid: u32, timeout_secs: u64) -> Result<()>;
     async fn kill(&self, pid: u32) -> Result<()>;
+    /// Send the signal named `signal` (e.g. `SIGABRT`) to the process alone,
+    /// not its group. Drivers without signals terminate the process.
+    async fn signal(&self, pid: u32, signal: &str) -> Result<()>;
     async fn is_alive(&self, pid: u32) -> bool;
-    async fn wait(&self, pid: u32) -> Result<Option<i32>>;
-    async fn resource_usage(&self, pid: u32) -> Result<ResourceUsage>;
+    /// How an exited process ended. `None` while it's still running, or if it
+    /// wasn't spawned by this driver.
+    async fn wait(&self, pid: u32) -> Result<Option<ExitStatus>>;
+    /// Notifications of spawned processes exiting, sent as soon as they have
+    /// been reaped.
+    fn exits(&self) -> broadcast::Receiver<ProcessExit>;
+    /// Identity of a running process, or `None` if there is no such process.
+    async fn identity(&self, pid: u32) -> Option<ProcessIdentity>;
+    /// CPU and memory of each PID in `pids` together with its descendants.
+    /// CPU usage covers the time since the previous call. PIDs that no longer
+    /// exist are omitted.
+    async fn resource_usage(&self, pids: &[u32]) -> Result<HashMap<u32, ResourceUsage>>;
 }
 
+#[cfg(target_os = "linux")]
+mod cgroup;
+mod exits;
 #[cfg(unix)]
 mod unix;
+mod usage;
 #[cfg(windows)]
 mod windows;
 
@@ -47,3 +150,36 @@ pub fn create_driver() -> Box<dyn ProcessDriver> {
         Box::new(windows::WindowsProcessDriver::new())
     }
 }
+
+/// The user and group ID processes of `spec` are switched to, or `None`
+/// if they run as the manager. Files the manager creates for them, such as
+/// the notify socket, are handed to these IDs.
+#[cfg(unix)]
+pub fn switched_ids(spec: &DaemonSpec) -> Result<Option<(u32, u32)>> {
+    unix::switched_ids(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> ProcessIdentity {
+        ProcessIdentity {
+            start_time: 1_700_000_000,
+            command: vec!["web".to_string(), "--port=80".to_string()],
+        }
+    }
+
+    #[test]
+    fn start_time_decides_when_recorded() {
+        let command = vec!["other".to_string()];
+        assert!(identity().matches(Some(1_700_000_000), &command));
+        assert!(!identity().matches(Some(1_700_000_001), &identity().command));
+    }
+
+    #[test]
+    fn command_decides_without_start_time() {
+        assert!(identity().matches(None, &identity().command));
+        assert!(!identity().matches(None, &["web".to_string()]));
+    }
+}
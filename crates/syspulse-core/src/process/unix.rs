@@ -1,30 +1,410 @@
 use async_trait::async_trait;
 use nix::sys::signal::{self, Signal};
-use nix::unistd::Pid;
-use std::os::unix::process::CommandExt;
-use std::path::PathBuf;
-use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+use nix::unistd::{Gid, Group, Pid, Uid, User};
+use std::collections::HashMap;
+use std::ffi::{CString, OsStr, OsString};
+use std::os::fd::AsRawFd;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tokio::sync::broadcast;
 use tokio::time::{sleep, Duration};
 
-use super::{ProcessDriver, ProcessInfo, ResourceUsage};
+use super::exits::ExitTracker;
+use super::usage::{process_identity, UsageSampler};
+use super::{ExitStatus, ProcessDriver, ProcessExit, ProcessIdentity, ProcessInfo, ResourceUsage};
 use crate::daemon::DaemonSpec;
 use crate::error::{Result, SyspulseError};
+use crate::sockets::{BoundSocket, LISTEN_FDS_START};
 
-pub struct UnixProcessDriver;
+pub struct UnixProcessDriver {
+    /// Set up by `init`, before the manager has children that would keep it
+    /// from moving out of its cgroup.
+    #[cfg(target_os = "linux")]
+    cgroups: std::sync::OnceLock<std::result::Result<super::cgroup::CgroupTree, String>>,
+    usage: UsageSampler,
+    exits: ExitTracker,
+}
 
 impl UnixProcessDriver {
     pub fn new() -> Self {
-        Self
+        Self {
+            #[cfg(target_os = "linux")]
+            cgroups: std::sync::OnceLock::new(),
+            usage: UsageSampler::new(),
+            exits: ExitTracker::new(),
+        }
+    }
+
+    /// Prepare the cgroup of `instance` enforcing `cpu_percent` and return
+    /// the `cgroup.procs` path the child should join. Falls back to no
+    /// limit with a warning.
+    #[cfg(target_os = "linux")]
+    fn cpu_cgroup(
+        &self,
+        instance: &str,
+        cpu_percent: f64,
+        warnings: &mut Vec<String>,
+    ) -> Option<CString> {
+        let tree = match self.cgroups.get_or_init(super::cgroup::CgroupTree::setup) {
+            Ok(tree) => tree,
+            Err(reason) => {
+                warnings.push(format!("max_cpu_percent not enforced: {}", reason));
+                return None;
+            }
+        };
+        match tree.prepare(instance, cpu_percent) {
+            Ok(procs) => Some(procs),
+            Err(reason) => {
+                warnings.push(format!("max_cpu_percent not enforced: {}", reason));
+                None
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn cpu_cgroup(
+        &self,
+        _instance: &str,
+        _cpu_percent: f64,
+        warnings: &mut Vec<String>,
+    ) -> Option<CString> {
+        warnings.push("max_cpu_percent is only enforced on Linux (cgroups v2)".to_string());
+        None
+    }
+}
+
+/// Move the calling process into the cgroup whose `cgroup.procs` is `procs`.
+///
+/// Runs in the forked child before exec, so it only uses async-signal-safe
+/// calls. Writing "0" moves the writer itself.
+fn join_cgroup(procs: &CString) -> std::io::Result<()> {
+    unsafe {
+        let fd = libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+        let err = std::io::Error::last_os_error();
+        libc::close(fd);
+        if written != 1 {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Identity a daemon runs as, resolved in the parent because passwd and group
+/// lookups are not async-signal-safe.
+struct RunAs {
+    /// Passwd entry for `user`, used for HOME/USER/LOGNAME.
+    user: Option<User>,
+    /// IDs to switch to in the child, or `None` when the manager already has
+    /// them.
+    ids: Option<SwitchIds>,
+}
+
+struct SwitchIds {
+    uid: Uid,
+    gid: Gid,
+    groups: Vec<libc::gid_t>,
+}
+
+/// Resolve the `user` and `group` fields of `spec`.
+fn resolve_run_as(spec: &DaemonSpec) -> Result<Option<RunAs>> {
+    if spec.user.is_none() && spec.group.is_none() {
+        return Ok(None);
+    }
+
+    let user = spec
+        .user
+        .as_deref()
+        .map(|name| lookup_user(&spec.name, name))
+        .transpose()?;
+    let group = spec
+        .group
+        .as_deref()
+        .map(|name| lookup_group(&spec.name, name))
+        .transpose()?;
+
+    let euid = Uid::effective();
+    let egid = Gid::effective();
+    let uid = user.as_ref().map_or(euid, |u| u.uid);
+    let gid = group
+        .as_ref()
+        .map(|g| g.gid)
+        .or_else(|| user.as_ref().map(|u| u.gid))
+        .unwrap_or(egid);
+
+    if uid == euid && gid == egid {
+        return Ok(Some(RunAs { user, ids: None }));
+    }
+    if !euid.is_root() {
+        return Err(SyspulseError::Process(format!(
+            "Cannot run daemon '{}' as {}: the manager must run as root to switch users",
+            spec.name,
+            describe_identity(spec)
+        )));
+    }
+
+    let groups = match user {
+        Some(ref u) => supplementary_groups(u, gid)?,
+        None => vec![gid.as_raw()],
+    };
+    Ok(Some(RunAs {
+        user,
+        ids: Some(SwitchIds { uid, gid, groups }),
+    }))
+}
+
+pub(super) fn switched_ids(spec: &DaemonSpec) -> Result<Option<(u32, u32)>> {
+    Ok(resolve_run_as(spec)?
+        .and_then(|run_as| run_as.ids)
+        .map(|ids| (ids.uid.as_raw(), ids.gid.as_raw())))
+}
+
+fn lookup_user(daemon_name: &str, name: &str) -> Result<User> {
+    let found = match name.parse::<u32>() {
+        Ok(id) => User::from_uid(Uid::from_raw(id)),
+        Err(_) => User::from_name(name),
+    };
+    found
+        .map_err(|e| SyspulseError::Process(format!("Failed to look up user '{}': {}", name, e)))?
+        .ok_or_else(|| {
+            SyspulseError::Process(format!(
+                "Unknown user '{}' for daemon '{}'",
+                name, daemon_name
+            ))
+        })
+}
+
+fn lookup_group(daemon_name: &str, name: &str) -> Result<Group> {
+    let found = match name.parse::<u32>() {
+        Ok(id) => Group::from_gid(Gid::from_raw(id)),
+        Err(_) => Group::from_name(name),
+    };
+    found
+        .map_err(|e| SyspulseError::Process(format!("Failed to look up group '{}': {}", name, e)))?
+        .ok_or_else(|| {
+            SyspulseError::Process(format!(
+                "Unknown group '{}' for daemon '{}'",
+                name, daemon_name
+            ))
+        })
+}
+
+fn describe_identity(spec: &DaemonSpec) -> String {
+    match (spec.user.as_deref(), spec.group.as_deref()) {
+        (Some(user), Some(group)) => format!("user '{}' and group '{}'", user, group),
+        (Some(user), None) => format!("user '{}'", user),
+        (None, Some(group)) => format!("group '{}'", group),
+        (None, None) => "the current user".to_string(),
+    }
+}
+
+/// Groups the user is a member of, with `gid` first.
+#[cfg(not(target_vendor = "apple"))]
+fn supplementary_groups(user: &User, gid: Gid) -> Result<Vec<libc::gid_t>> {
+    let name = CString::new(user.name.as_str())
+        .map_err(|e| SyspulseError::Process(format!("Invalid user name: {}", e)))?;
+    let groups = nix::unistd::getgrouplist(&name, gid).map_err(|e| {
+        SyspulseError::Process(format!(
+            "Failed to look up groups of user '{}': {}",
+            user.name, e
+        ))
+    })?;
+    Ok(groups.into_iter().map(Gid::as_raw).collect())
+}
+
+#[cfg(target_vendor = "apple")]
+fn supplementary_groups(_user: &User, gid: Gid) -> Result<Vec<libc::gid_t>> {
+    Ok(vec![gid.as_raw()])
+}
+
+/// Drop to the resolved IDs. Runs in the forked child before exec; the group
+/// list must be set while still root, and the uid last.
+fn switch_ids(ids: &SwitchIds) -> std::io::Result<()> {
+    unsafe {
+        if libc::setgroups(ids.groups.len() as _, ids.groups.as_ptr()) != 0
+            || libc::setgid(ids.gid.as_raw()) != 0
+            || libc::setuid(ids.uid.as_raw()) != 0
+        {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Move the sockets' descriptors to `LISTEN_FDS_START` onwards, without
+/// close-on-exec. Runs between fork and exec, so it doesn't allocate:
+/// `copies` is scratch space as long as `fds`, allocated by the parent.
+fn pass_listen_fds(fds: &[i32], copies: &mut [i32]) -> std::io::Result<()> {
+    // Copy them above the target range first so that moving one can't
+    // overwrite another that hasn't been moved yet.
+    let above = LISTEN_FDS_START + fds.len() as i32;
+    for (&fd, copy) in fds.iter().zip(copies.iter_mut()) {
+        *copy = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, above) };
+        if *copy < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    for (i, &copy) in copies.iter().enumerate() {
+        if unsafe { libc::dup2(copy, LISTEN_FDS_START + i as i32) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// `execve` arguments for a daemon that is passed sockets, prepared by the
+/// parent so that the child only has to fill in `LISTEN_PID`.
+struct ListenExec {
+    program: CString,
+    args: Vec<CString>,
+    env: Vec<CString>,
+    /// `LISTEN_PID=` followed by room for the PID and a NUL.
+    listen_pid: [u8; LISTEN_PID_LEN],
+    arg_ptrs: Vec<*const libc::c_char>,
+    env_ptrs: Vec<*const libc::c_char>,
+}
+
+const LISTEN_PID_PREFIX: &[u8] = b"LISTEN_PID=";
+const LISTEN_PID_LEN: usize = LISTEN_PID_PREFIX.len() + 21;
+
+// The pointers only ever point into the struct's own strings, and are only
+// set in the forked child.
+unsafe impl Send for ListenExec {}
+unsafe impl Sync for ListenExec {}
+
+impl ListenExec {
+    /// Resolve the program of `spec` the way `cmd` would, so that a missing
+    /// one fails the spawn, and take the environment `cmd` would pass.
+    fn new(spec: &DaemonSpec, cmd: &std::process::Command) -> Result<Self> {
+        let mut vars: HashMap<OsString, OsString> = std::env::vars_os().collect();
+        for (key, val) in cmd.get_envs() {
+            match val {
+                Some(val) => vars.insert(key.to_owned(), val.to_owned()),
+                None => vars.remove(key),
+            };
+        }
+        let program = resolve_program(spec, vars.get(OsStr::new("PATH")))?;
+
+        let cstring = |bytes: &[u8]| {
+            CString::new(bytes)
+                .map_err(|e| SyspulseError::Process(format!("Failed to spawn process: {}", e)))
+        };
+        let args = spec
+            .command
+            .iter()
+            .map(|arg| cstring(arg.as_bytes()))
+            .collect::<Result<Vec<_>>>()?;
+        let env = vars
+            .iter()
+            .filter(|(key, _)| key.as_bytes() != b"LISTEN_PID")
+            .map(|(key, val)| cstring(&[key.as_bytes(), b"=", val.as_bytes()].concat()))
+            .collect::<Result<Vec<_>>>()?;
+        let mut listen_pid = [0; LISTEN_PID_LEN];
+        listen_pid[..LISTEN_PID_PREFIX.len()].copy_from_slice(LISTEN_PID_PREFIX);
+
+        Ok(Self {
+            program: cstring(program.as_os_str().as_bytes())?,
+            arg_ptrs: vec![std::ptr::null(); args.len() + 1],
+            env_ptrs: vec![std::ptr::null(); env.len() + 2],
+            args,
+            env,
+            listen_pid,
+        })
+    }
+
+    /// Set `LISTEN_PID` and exec the daemon. Runs between fork and exec, so it
+    /// doesn't allocate; only returns if the exec failed.
+    fn exec(&mut self) -> std::io::Error {
+        let mut pid = std::process::id();
+        let mut digits = [0u8; 10];
+        let mut len = 0;
+        loop {
+            digits[len] = b'0' + (pid % 10) as u8;
+            len += 1;
+            pid /= 10;
+            if pid == 0 {
+                break;
+            }
+        }
+        for (i, digit) in digits[..len].iter().rev().enumerate() {
+            self.listen_pid[LISTEN_PID_PREFIX.len() + i] = *digit;
+        }
+        self.listen_pid[LISTEN_PID_PREFIX.len() + len] = 0;
+
+        for (ptr, arg) in self.arg_ptrs.iter_mut().zip(&self.args) {
+            *ptr = arg.as_ptr();
+        }
+        for (ptr, var) in self.env_ptrs.iter_mut().zip(&self.env) {
+            *ptr = var.as_ptr();
+        }
+        self.env_ptrs[self.env.len()] = self.listen_pid.as_ptr().cast();
+
+        unsafe {
+            libc::execve(
+                self.program.as_ptr(),
+                self.arg_ptrs.as_ptr(),
+                self.env_ptrs.as_ptr(),
+            );
+        }
+        std::io::Error::last_os_error()
     }
 }
 
+/// Find the executable `spec.command[0]` names: relative to `working_dir` if it
+/// contains a `/`, otherwise on `path`.
+fn resolve_program(spec: &DaemonSpec, path: Option<&OsString>) -> Result<PathBuf> {
+    let program = Path::new(&spec.command[0]);
+    let is_executable = |candidate: &Path| {
+        std::fs::metadata(candidate)
+            .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+    };
+    let found = if spec.command[0].contains('/') {
+        let candidate = match spec.working_dir {
+            Some(ref dir) if program.is_relative() => dir.join(program),
+            _ => program.to_path_buf(),
+        };
+        is_executable(&candidate).then_some(candidate)
+    } else {
+        path.into_iter()
+            .flat_map(std::env::split_paths)
+            .map(|dir| dir.join(program))
+            .find(|candidate| is_executable(candidate))
+    };
+    found.ok_or_else(|| {
+        SyspulseError::Process(format!(
+            "Failed to spawn process: '{}' not found or not executable",
+            spec.command[0]
+        ))
+    })
+}
+
 #[async_trait]
 impl ProcessDriver for UnixProcessDriver {
+    fn init(&self) {
+        #[cfg(target_os = "linux")]
+        if let Err(reason) = self.cgroups.get_or_init(super::cgroup::CgroupTree::setup) {
+            tracing::debug!("max_cpu_percent can't be enforced: {}", reason);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn forget(&self, name: &str) {
+        if let Some(Ok(tree)) = self.cgroups.get() {
+            tree.remove(name);
+        }
+    }
+
     async fn spawn(
         &self,
+        name: &str,
         spec: &DaemonSpec,
         stdout_path: &PathBuf,
         stderr_path: &PathBuf,
+        sockets: &[BoundSocket],
     ) -> Result<ProcessInfo> {
         let stdout_file = std::fs::OpenOptions::new()
             .create(true)
@@ -48,19 +428,65 @@ impl ProcessDriver for UnixProcessDriver {
             cmd.current_dir(dir);
         }
 
+        let run_as = resolve_run_as(spec)?;
+        if let Some(user) = run_as.as_ref().and_then(|r| r.user.as_ref()) {
+            cmd.env("HOME", &user.dir)
+                .env("USER", &user.name)
+                .env("LOGNAME", &user.name);
+        }
+        let switch = run_as.and_then(|r| r.ids);
+
         for (key, val) in &spec.env {
             cmd.env(key, val);
         }
 
+        let listen_fds: Vec<i32> = sockets.iter().map(|s| s.as_raw_fd()).collect();
+        let mut listen_fd_copies = vec![0; listen_fds.len()];
+        if !sockets.is_empty() {
+            let names: Vec<&str> = sockets.iter().map(|s| s.name.as_str()).collect();
+            cmd.env("LISTEN_FDS", sockets.len().to_string())
+                .env("LISTEN_FDNAMES", names.join(":"));
+        }
+
+        let mut warnings = Vec::new();
+        let cgroup_procs = spec
+            .resource_limits
+            .as_ref()
+            .filter(|limits| !limits.is_soft())
+            .and_then(|limits| limits.max_cpu_percent)
+            .and_then(|pct| self.cpu_cgroup(name, pct, &mut warnings));
+        for warning in &warnings {
+            tracing::warn!(daemon = %name, "{}", warning);
+        }
+
+        // LISTEN_PID must be the daemon's own PID, which is only known in the
+        // child, so with sockets the child execs the daemon itself.
+        let mut listen_exec = if sockets.is_empty() {
+            None
+        } else {
+            Some(ListenExec::new(spec, cmd.as_std())?)
+        };
+
         let resource_limits = spec.resource_limits.clone();
         unsafe {
             cmd.pre_exec(move || {
                 // Create new session so the daemon runs independently
                 libc::setsid();
 
+                if !listen_fds.is_empty() {
+                    pass_listen_fds(&listen_fds, &mut listen_fd_copies)?;
+                }
+
+                // Join the CPU-limited cgroup before exec so every descendant
+                // is accounted for.
+                if let Some(ref procs) = cgroup_procs {
+                    join_cgroup(procs)?;
+                }
+
                 // Apply resource limits if configured
                 if let Some(ref limits) = resource_limits {
-                    if let Some(max_mem) = limits.max_memory_bytes {
+                    // Soft memory limits are watched by the manager instead.
+                    if let Some(max_mem) = limits.max_memory_bytes.filter(|_| !limits.is_soft()) {
                         rlimit::setrlimit(rlimit::Resource::AS, max_mem, max_mem)
                             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
                     }
@@ -69,7 +495,15 @@ impl ProcessDriver for UnixProcessDriver {
                             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
                     }
                 }
-                Ok(())
+
+                // Drop privileges last; the steps above may need root.
+                if let Some(ref ids) = switch {
+                    switch_ids(ids)?;
+                }
+                match listen_exec {
+                    Some(ref mut exec) => Err(exec.exec()),
+                    None => Ok(()),
+                }
             });
         }
 
@@ -84,10 +518,14 @@ impl ProcessDriver for UnixProcessDriver {
             .id()
             .ok_or_else(|| SyspulseError::Process("Failed to get child PID".into()))?;
 
-        // Detach: drop the child handle so we don't wait on it
-        std::mem::forget(child);
+        // Keep the handle so the child is reaped the moment it exits.
+        self.exits.track(pid, child);
 
-        Ok(ProcessInfo { pid, alive: true })
+        Ok(ProcessInfo {
+            pid,
+            alive: true,
+            warnings,
+        })
     }
 
     async fn stop(&self, pid: u32, timeout_secs: u64) -> Result<()> {
@@ -124,54 +562,120 @@ impl ProcessDriver for UnixProcessDriver {
         Ok(())
     }
 
+    async fn signal(&self, pid: u32, signal: &str) -> Result<()> {
+        let signal: Signal = signal
+            .parse()
+            .map_err(|_| SyspulseError::Config(format!("Unknown signal '{}'", signal)))?;
+        signal::kill(Pid::from_raw(pid as i32), signal).map_err(|e| {
+            SyspulseError::Process(format!(
+                "Failed to send {} to process {}: {}",
+                signal, pid, e
+            ))
+        })?;
+        Ok(())
+    }
+
     async fn is_alive(&self, pid: u32) -> bool {
-        let pid = Pid::from_raw(pid as i32);
-        // Sending signal 0 checks if the process exists
-        signal::kill(pid, None).is_ok()
-    }
-
-    async fn wait(&self, pid: u32) -> Result<Option<i32>> {
-        use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
-
-        let pid = Pid::from_raw(pid as i32);
-        match waitpid(pid, Some(WaitPidFlag::WNOHANG)) {
-            Ok(WaitStatus::Exited(_, code)) => Ok(Some(code)),
-            Ok(WaitStatus::Signaled(_, sig, _)) => Ok(Some(-(sig as i32))),
-            Ok(WaitStatus::StillAlive) => Ok(None),
-            Ok(_) => Ok(None),
-            Err(nix::errno::Errno::ECHILD) => {
-                // Not our child or already reaped
-                if self.is_alive(pid.as_raw() as u32).await {
-                    Ok(None)
-                } else {
-                    Ok(Some(-1))
-                }
-            }
-            Err(e) => Err(SyspulseError::Process(format!(
-                "waitpid failed for {}: {}",
-                pid, e
-            ))),
-        }
-    }
-
-    async fn resource_usage(&self, pid: u32) -> Result<ResourceUsage> {
-        let sys_pid = sysinfo::Pid::from_u32(pid);
-        let mut sys = System::new_with_specifics(
-            RefreshKind::nothing()
-                .with_processes(ProcessRefreshKind::nothing().with_memory().with_cpu()),
-        );
-        sys.refresh_processes_specifics(
-            sysinfo::ProcessesToUpdate::Some(&[sys_pid]),
-            true,
-            ProcessRefreshKind::nothing().with_memory().with_cpu(),
-        );
-
-        match sys.process(sys_pid) {
-            Some(proc) => Ok(ResourceUsage {
-                memory_bytes: proc.memory(),
-                cpu_percent: proc.cpu_usage() as f64,
-            }),
-            None => Err(SyspulseError::Process(format!("Process {} not found", pid))),
+        if let Some(alive) = self.exits.is_alive(pid) {
+            return alive;
         }
+        // Not our child: sending signal 0 checks if the process exists.
+        signal::kill(Pid::from_raw(pid as i32), None).is_ok()
+    }
+
+    async fn wait(&self, pid: u32) -> Result<Option<ExitStatus>> {
+        Ok(self.exits.take_exit(pid))
+    }
+
+    fn exits(&self) -> broadcast::Receiver<ProcessExit> {
+        self.exits.subscribe()
+    }
+
+    async fn identity(&self, pid: u32) -> Option<ProcessIdentity> {
+        process_identity(pid)
+    }
+
+    async fn resource_usage(&self, pids: &[u32]) -> Result<HashMap<u32, ResourceUsage>> {
+        self.usage.sample(pids).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(user: Option<&str>, group: Option<&str>) -> DaemonSpec {
+        let mut spec: DaemonSpec =
+            serde_json::from_str(r#"{"name": "app", "command": ["true"]}"#).unwrap();
+        spec.user = user.map(String::from);
+        spec.group = group.map(String::from);
+        spec
+    }
+
+    #[test]
+    fn no_identity_needs_no_switch() {
+        assert!(resolve_run_as(&spec(None, None)).unwrap().is_none());
+    }
+
+    #[test]
+    fn current_user_needs_no_switch() {
+        let uid = Uid::effective().to_string();
+        let run_as = resolve_run_as(&spec(Some(&uid), None)).unwrap().unwrap();
+        assert!(run_as.user.is_some());
+        assert!(run_as.ids.is_none());
+    }
+
+    #[test]
+    fn unknown_user_is_an_error() {
+        let err = resolve_run_as(&spec(Some("no-such-user-syspulse"), None))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("Unknown user"));
+    }
+
+    #[tokio::test]
+    async fn sockets_are_passed_from_fd_3() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket: crate::sockets::SocketSpec =
+            serde_json::from_str(r#"{"listen": "127.0.0.1:0", "name": "http"}"#).unwrap();
+        let sockets = vec![BoundSocket::bind(&socket, "app").unwrap()];
+        let mut spec = spec(None, None);
+        spec.command = vec![
+            "sh".into(),
+            "-c".into(),
+            r#"[ "$LISTEN_PID" = $$ ] && [ -e /dev/fd/3 ] && echo "$LISTEN_FDS $LISTEN_FDNAMES""#
+                .into(),
+        ];
+
+        let driver = UnixProcessDriver::new();
+        let mut exits = driver.exits();
+        let stdout = dir.path().join("out.log");
+        let stderr = dir.path().join("err.log");
+        driver
+            .spawn("web", &spec, &stdout, &stderr, &sockets)
+            .await
+            .unwrap();
+        exits.recv().await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&stdout).unwrap(), "1 http\n");
+    }
+
+    #[tokio::test]
+    async fn missing_program_with_sockets_fails_to_spawn() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket: crate::sockets::SocketSpec =
+            serde_json::from_str(r#"{"listen": "127.0.0.1:0"}"#).unwrap();
+        let sockets = vec![BoundSocket::bind(&socket, "app").unwrap()];
+        let mut spec = spec(None, None);
+        spec.command = vec!["syspulse-no-such-program".into()];
+
+        let driver = UnixProcessDriver::new();
+        let stdout = dir.path().join("out.log");
+        let stderr = dir.path().join("err.log");
+        let err = driver
+            .spawn("web", &spec, &stdout, &stderr, &sockets)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
     }
 }
@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind};
+
+use super::{ProcessIdentity, ResourceUsage};
+use crate::error::{Result, SyspulseError};
+
+/// Samples CPU and memory of whole process trees.
+///
+/// sysinfo computes a process's CPU usage from the time elapsed since the
+/// previous full refresh of the same `System`, so one instance is kept alive
+/// and all daemons are sampled together in a single refresh.
+pub(crate) struct UsageSampler {
+    system: Arc<Mutex<System>>,
+}
+
+impl UsageSampler {
+    pub(crate) fn new() -> Self {
+        Self {
+            system: Arc::new(Mutex::new(System::new())),
+        }
+    }
+
+    /// Usage of each root PID together with its descendants. On Unix, this
+    /// also covers processes that stayed in the daemon's session after being
+    /// reparented. Roots that no longer exist are omitted.
+    ///
+    /// Reading every process's stats can take a while on a busy host, so it
+    /// runs on the blocking thread pool.
+    pub(crate) async fn sample(&self, roots: &[u32]) -> Result<HashMap<u32, ResourceUsage>> {
+        let system = Arc::clone(&self.system);
+        let roots = roots.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut sys = system.lock().unwrap_or_else(|e| e.into_inner());
+            sample_trees(&mut sys, &roots)
+        })
+        .await
+        .map_err(|e| SyspulseError::Process(format!("Failed to sample resource usage: {}", e)))
+    }
+}
+
+/// The work of [`UsageSampler::sample`], on its locked `System`.
+fn sample_trees(sys: &mut System, roots: &[u32]) -> HashMap<u32, ResourceUsage> {
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::new().with_memory().with_cpu(),
+    );
+
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    #[cfg(unix)]
+    let mut sessions: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (pid, process) in sys.processes() {
+        // Threads are listed alongside processes on Linux and would be
+        // counted twice.
+        if process.thread_kind().is_some() {
+            continue;
+        }
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(*pid);
+        }
+        #[cfg(unix)]
+        if let Some(session) = process.session_id() {
+            sessions.entry(session).or_default().push(*pid);
+        }
+    }
+
+    roots
+        .iter()
+        .filter_map(|&root| {
+            let root_pid = Pid::from_u32(root);
+            sys.process(root_pid)?;
+
+            let mut members = HashSet::from([root_pid]);
+            #[cfg(unix)]
+            members.extend(sessions.get(&root_pid).into_iter().flatten());
+            let mut stack: Vec<Pid> = members.iter().copied().collect();
+            while let Some(pid) = stack.pop() {
+                for child in children.get(&pid).into_iter().flatten() {
+                    if members.insert(*child) {
+                        stack.push(*child);
+                    }
+                }
+            }
+
+            let mut usage = ResourceUsage::default();
+            for process in members.iter().filter_map(|pid| sys.process(*pid)) {
+                usage.memory_bytes += process.memory();
+                usage.cpu_percent += process.cpu_usage() as f64;
+                usage.process_count += 1;
+            }
+            Some((root, usage))
+        })
+        .collect()
+}
+
+/// Start time and command line of `pid`, if it exists and hasn't exited
+/// (an unreaped zombie counts as gone).
+pub(crate) fn process_identity(pid: u32) -> Option<ProcessIdentity> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::new().with_cmd(UpdateKind::Always),
+    );
+    let process = sys
+        .process(pid)
+        .filter(|process| process.status() != ProcessStatus::Zombie)?;
+    Some(ProcessIdentity {
+        start_time: process.start_time(),
+        command: process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn samples_own_process() {
+        let sampler = UsageSampler::new();
+        let own = std::process::id();
+        let usage = sampler.sample(&[own]).await.unwrap();
+        let own_usage = usage.get(&own).unwrap();
+        assert!(own_usage.memory_bytes > 0);
+        assert!(own_usage.process_count >= 1);
+    }
+
+    #[test]
+    fn identifies_own_process() {
+        let identity = process_identity(std::process::id()).unwrap();
+        assert!(identity.start_time > 0);
+        assert!(!identity.command.is_empty());
+        assert_eq!(process_identity(std::process::id()), Some(identity));
+    }
+
+    #[tokio::test]
+    async fn omits_missing_processes() {
+        let sampler = UsageSampler::new();
+        assert!(sampler.sample(&[u32::MAX - 1]).await.unwrap().is_empty());
+    }
+}
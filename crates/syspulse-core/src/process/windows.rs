@@ -1,6 +1,7 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+use tokio::sync::broadcast;
 use tokio::time::{sleep, Duration};
 use windows::Win32::Foundation::{CloseHandle, HANDLE, STILL_ACTIVE, WAIT_OBJECT_0};
 use windows::Win32::System::Console::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
@@ -13,15 +14,24 @@ use windows::Win32::System::Threading::{
     CREATE_NEW_PROCESS_GROUP, PROCESS_QUERY_INFORMATION, PROCESS_TERMINATE,
 };
 
-use super::{ProcessDriver, ProcessInfo, ResourceUsage};
+use super::exits::ExitTracker;
+use super::usage::{process_identity, UsageSampler};
+use super::{ExitStatus, ProcessDriver, ProcessExit, ProcessIdentity, ProcessInfo, ResourceUsage};
 use crate::daemon::DaemonSpec;
 use crate::error::{Result, SyspulseError};
+use crate::sockets::BoundSocket;
 
-pub struct WindowsProcessDriver;
+pub struct WindowsProcessDriver {
+    usage: UsageSampler,
+    exits: ExitTracker,
+}
 
 impl WindowsProcessDriver {
     pub fn new() -> Self {
-        Self
+        Self {
+            usage: UsageSampler::new(),
+            exits: ExitTracker::new(),
+        }
     }
 
     fn open_process(&self, pid: u32, access: u32) -> std::result::Result<HANDLE, SyspulseError> {
@@ -40,9 +50,11 @@ impl WindowsProcessDriver {
 impl ProcessDriver for WindowsProcessDriver {
     async fn spawn(
         &self,
+        _name: &str,
         spec: &DaemonSpec,
         stdout_path: &PathBuf,
         stderr_path: &PathBuf,
+        sockets: &[BoundSocket],
     ) -> Result<ProcessInfo> {
         let stdout_file = std::fs::OpenOptions::new()
             .create(true)
@@ -81,8 +93,19 @@ impl ProcessDriver for WindowsProcessDriver {
             .id()
             .ok_or_else(|| SyspulseError::Process("Failed to get child PID".into()))?;
 
+        let mut warnings = Vec::new();
+        if spec.user.is_some() || spec.group.is_some() {
+            warnings.push("user and group are not supported on Windows".to_string());
+        }
+        if !sockets.is_empty() {
+            warnings.push("sockets are only passed to daemons on Unix".to_string());
+        }
+
         // Apply resource limits via Job Object if configured
-        if let Some(ref limits) = spec.resource_limits {
+        if let Some(limits) = spec.resource_limits.as_ref().filter(|l| !l.is_soft()) {
+            if limits.max_cpu_percent.is_some() {
+                warnings.push("max_cpu_percent is not enforced on Windows".to_string());
+            }
             if limits.max_memory_bytes.is_some() {
                 if let Err(e) = apply_job_limits(pid, limits) {
                     tracing::warn!(pid, "Failed to apply job object limits: {}", e);
@@ -90,10 +113,14 @@ impl ProcessDriver for WindowsProcessDriver {
             }
         }
 
-        // Detach
-        std::mem::forget(child);
+        // Keep the handle so the exit is reported as soon as it happens.
+        self.exits.track(pid, child);
 
-        Ok(ProcessInfo { pid, alive: true })
+        Ok(ProcessInfo {
+            pid,
+            alive: true,
+            warnings,
+        })
     }
 
     async fn stop(&self, pid: u32, timeout_secs: u64) -> Result<()> {
@@ -133,7 +160,14 @@ impl ProcessDriver for WindowsProcessDriver {
         Ok(())
     }
 
+    async fn signal(&self, pid: u32, _signal: &str) -> Result<()> {
+        self.kill(pid).await
+    }
+
     async fn is_alive(&self, pid: u32) -> bool {
+        if let Some(alive) = self.exits.is_alive(pid) {
+            return alive;
+        }
         let handle = match self.open_process(pid, PROCESS_QUERY_INFORMATION.0) {
             Ok(h) => h,
             Err(_) => return false,
@@ -148,10 +182,13 @@ impl ProcessDriver for WindowsProcessDriver {
         alive
     }
 
-    async fn wait(&self, pid: u32) -> Result<Option<i32>> {
+    async fn wait(&self, pid: u32) -> Result<Option<ExitStatus>> {
+        if let Some(status) = self.exits.take_exit(pid) {
+            return Ok(Some(status));
+        }
         let handle = match self.open_process(pid, PROCESS_QUERY_INFORMATION.0) {
             Ok(h) => h,
-            Err(_) => return Ok(Some(-1)),
+            Err(_) => return Ok(None),
         };
 
         let result = unsafe { WaitForSingleObject(handle, 0) };
@@ -161,7 +198,7 @@ impl ProcessDriver for WindowsProcessDriver {
                 let _ = GetExitCodeProcess(handle, &mut exit_code);
                 let _ = CloseHandle(handle);
             }
-            Ok(Some(exit_code as i32))
+            Ok(Some(ExitStatus::Code(exit_code as i32)))
         } else {
             unsafe {
                 let _ = CloseHandle(handle);
@@ -170,24 +207,16 @@ impl ProcessDriver for WindowsProcessDriver {
         }
     }
 
-    async fn resource_usage(&self, pid: u32) -> Result<ResourceUsage> {
-        let sys_pid = sysinfo::Pid::from_u32(pid);
-        let mut sys = System::new_with_specifics(
-            RefreshKind::new().with_processes(ProcessRefreshKind::new().with_memory().with_cpu()),
-        );
-        sys.refresh_processes_specifics(
-            sysinfo::ProcessesToUpdate::Some(&[sys_pid]),
-            true,
-            ProcessRefreshKind::new().with_memory().with_cpu(),
-        );
-
-        match sys.process(sys_pid) {
-            Some(proc) => Ok(ResourceUsage {
-                memory_bytes: proc.memory(),
-                cpu_percent: proc.cpu_usage() as f64,
-            }),
-            None => Err(SyspulseError::Process(format!("Process {} not found", pid))),
-        }
+    fn exits(&self) -> broadcast::Receiver<ProcessExit> {
+        self.exits.subscribe()
+    }
+
+    async fn identity(&self, pid: u32) -> Option<ProcessIdentity> {
+        process_identity(pid)
+    }
+
+    async fn resource_usage(&self, pids: &[u32]) -> Result<HashMap<u32, ResourceUsage>> {
+        self.usage.sample(pids).await
     }
 }
 
@@ -1,11 +1,34 @@
 use std::path::Path;
 
-use chrono::Utc;
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
 use rusqlite::{params, Connection};
 
-use crate::daemon::{DaemonInstance, DaemonSpec, HealthStatus};
+use crate::daemon::{replica_name, DaemonInstance, DaemonSpec, HealthStatus, SpecRevision};
 use crate::error::{Result, SyspulseError};
+use crate::events::{DaemonEvent, EventKind, EVENT_RETENTION_COUNT, EVENT_RETENTION_DAYS};
 use crate::lifecycle::LifecycleState;
+use crate::runs::{DaemonRun, RunStatus, RunTrigger, RUN_RETENTION_COUNT};
+
+/// One row per replica of each daemon.
+const STATES_TABLE: &str = "daemon_states (
+    name TEXT NOT NULL,
+    replica INTEGER NOT NULL DEFAULT 0,
+    instance_id TEXT NOT NULL,
+    state TEXT NOT NULL,
+    pid INTEGER,
+    started_at TEXT,
+    stopped_at TEXT,
+    exit_code INTEGER,
+    restart_count INTEGER DEFAULT 0,
+    health_status TEXT DEFAULT 'unknown',
+    stdout_log TEXT,
+    stderr_log TEXT,
+    exit_signal INTEGER,
+    pid_start_time INTEGER,
+    notify_socket TEXT,
+    PRIMARY KEY (name, replica),
+    FOREIGN KEY (name) REFERENCES daemon_specs(name)
+)";
 
 pub struct Registry {
     conn: Connection,
@@ -34,25 +57,98 @@ impl Registry {
                     updated_at TEXT NOT NULL
                 );
 
-                CREATE TABLE IF NOT EXISTS daemon_states (
-                    name TEXT PRIMARY KEY,
-                    instance_id TEXT NOT NULL,
-                    state TEXT NOT NULL,
+                CREATE TABLE IF NOT EXISTS daemon_events (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    timestamp TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    event_json TEXT NOT NULL,
+                    message TEXT
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_daemon_events_name ON daemon_events(name, id);
+                CREATE INDEX IF NOT EXISTS idx_daemon_events_timestamp ON daemon_events(timestamp);
+
+                CREATE TABLE IF NOT EXISTS daemon_runs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    trigger TEXT NOT NULL,
+                    status TEXT NOT NULL,
+                    started_at TEXT NOT NULL,
+                    ended_at TEXT,
                     pid INTEGER,
-                    started_at TEXT,
-                    stopped_at TEXT,
                     exit_code INTEGER,
-                    restart_count INTEGER DEFAULT 0,
-                    health_status TEXT DEFAULT 'unknown',
-                    stdout_log TEXT,
-                    stderr_log TEXT,
-                    FOREIGN KEY (name) REFERENCES daemon_specs(name)
+                    exit_signal INTEGER
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_daemon_runs_name ON daemon_runs(name, id);
+
+                CREATE TABLE IF NOT EXISTS spec_revisions (
+                    name TEXT NOT NULL,
+                    revision INTEGER NOT NULL,
+                    spec_json TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    PRIMARY KEY (name, revision)
                 );",
             )
             .map_err(|e| SyspulseError::Database(format!("Migration failed: {}", e)))?;
+        self.conn
+            .execute_batch(&format!("CREATE TABLE IF NOT EXISTS {};", STATES_TABLE))
+            .map_err(|e| SyspulseError::Database(format!("Migration failed: {}", e)))?;
+
+        self.add_column_if_missing("daemon_states", "exit_signal", "INTEGER")?;
+        self.add_column_if_missing("daemon_states", "pid_start_time", "INTEGER")?;
+        self.add_column_if_missing("daemon_specs", "revision", "INTEGER NOT NULL DEFAULT 1")?;
+        self.key_states_by_replica()?;
+        self.add_column_if_missing("daemon_states", "notify_socket", "TEXT")?;
         Ok(())
     }
 
+    fn has_column(&self, table: &str, column: &str) -> Result<bool> {
+        self.conn
+            .prepare(&format!(
+                "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1",
+                table
+            ))
+            .and_then(|mut stmt| stmt.exists(params![column]))
+            .map_err(|e| SyspulseError::Database(format!("Migration failed: {}", e)))
+    }
+
+    /// Add a column to a table created by an older version.
+    fn add_column_if_missing(&self, table: &str, column: &str, decl: &str) -> Result<()> {
+        if !self.has_column(table, column)? {
+            self.conn
+                .execute_batch(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    table, column, decl
+                ))
+                .map_err(|e| SyspulseError::Database(format!("Migration failed: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild a `daemon_states` table from before replicas, which was keyed
+    /// by name alone. SQLite cannot change a primary key in place.
+    fn key_states_by_replica(&self) -> Result<()> {
+        if self.has_column("daemon_states", "replica")? {
+            return Ok(());
+        }
+        self.conn
+            .execute_batch(&format!(
+                "BEGIN;
+                 ALTER TABLE daemon_states RENAME TO daemon_states_old;
+                 CREATE TABLE {};
+                 INSERT INTO daemon_states ({columns}) SELECT {columns} FROM daemon_states_old;
+                 DROP TABLE daemon_states_old;
+                 COMMIT;",
+                STATES_TABLE,
+                columns = "name, instance_id, state, pid, started_at, stopped_at, exit_code, \
+                           restart_count, health_status, stdout_log, stderr_log, exit_signal, \
+                           pid_start_time",
+            ))
+            .map_err(|e| SyspulseError::Database(format!("Migration failed: {}", e)))
+    }
+
     pub fn register(&self, spec: &DaemonSpec) -> Result<()> {
         let now = Utc::now().to_rfc3339();
         let json = serde_json::to_string(spec)?;
@@ -74,12 +170,155 @@ impl Registry {
         Ok(())
     }
 
+    /// Replace the spec of a registered daemon, keeping the previous one in
+    /// the revision history. If `expected_revision` is given, the update only
+    /// succeeds if the daemon is still at that revision. Returns the new
+    /// revision.
+    pub fn update_spec(&self, spec: &DaemonSpec, expected_revision: Option<u64>) -> Result<u64> {
+        let db_err =
+            |e: rusqlite::Error| SyspulseError::Database(format!("Failed to update spec: {}", e));
+        let tx = self.conn.unchecked_transaction().map_err(db_err)?;
+
+        let (revision, old_json, updated_at): (u64, String, String) = tx
+            .query_row(
+                "SELECT revision, spec_json, updated_at FROM daemon_specs WHERE name = ?1",
+                params![spec.name],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    SyspulseError::DaemonNotFound(spec.name.clone())
+                }
+                _ => db_err(e),
+            })?;
+
+        if let Some(expected) = expected_revision {
+            if expected != revision {
+                return Err(SyspulseError::RevisionConflict {
+                    name: spec.name.clone(),
+                    expected,
+                    actual: revision,
+                });
+            }
+        }
+
+        tx.execute(
+            "INSERT OR IGNORE INTO spec_revisions (name, revision, spec_json, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![spec.name, revision, old_json, updated_at],
+        )
+        .map_err(db_err)?;
+
+        let now = Utc::now().to_rfc3339();
+        let json = serde_json::to_string(spec)?;
+        tx.execute(
+            "UPDATE daemon_specs SET spec_json = ?2, updated_at = ?3, revision = ?4 WHERE name = ?1",
+            params![spec.name, json, now, revision + 1],
+        )
+        .map_err(db_err)?;
+
+        tx.commit().map_err(db_err)?;
+        Ok(revision + 1)
+    }
+
+    /// The current spec of a daemon together with its revision.
+    pub fn get_current_revision(&self, name: &str) -> Result<SpecRevision> {
+        let (revision, json, updated_at): (u64, String, String) = self
+            .conn
+            .query_row(
+                "SELECT revision, spec_json, updated_at FROM daemon_specs WHERE name = ?1",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    SyspulseError::DaemonNotFound(name.to_string())
+                }
+                _ => SyspulseError::Database(format!("Failed to get spec: {}", e)),
+            })?;
+
+        spec_revision(revision, &json, &updated_at)
+    }
+
+    /// A specific revision of a daemon's spec, current or past.
+    pub fn get_revision(&self, name: &str, revision: u64) -> Result<SpecRevision> {
+        let current = self.get_current_revision(name)?;
+        if current.revision == revision {
+            return Ok(current);
+        }
+
+        let (json, created_at): (String, String) = self
+            .conn
+            .query_row(
+                "SELECT spec_json, created_at FROM spec_revisions WHERE name = ?1 AND revision = ?2",
+                params![name, revision],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => SyspulseError::Config(format!(
+                    "Daemon '{}' has no revision {}",
+                    name, revision
+                )),
+                _ => SyspulseError::Database(format!("Failed to get revision: {}", e)),
+            })?;
+
+        spec_revision(revision, &json, &created_at)
+    }
+
+    /// Every revision of a daemon's spec, newest (current) first.
+    pub fn list_revisions(&self, name: &str) -> Result<Vec<SpecRevision>> {
+        let current = self.get_current_revision(name)?;
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT revision, spec_json, created_at FROM spec_revisions
+                 WHERE name = ?1 ORDER BY revision DESC",
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to prepare query: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![name], |row| {
+                Ok((
+                    row.get::<_, u64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| SyspulseError::Database(format!("Failed to list revisions: {}", e)))?;
+
+        let mut revisions = vec![current];
+        for row in rows {
+            let (revision, json, created_at) = row
+                .map_err(|e| SyspulseError::Database(format!("Failed to list revisions: {}", e)))?;
+            revisions.push(spec_revision(revision, &json, &created_at)?);
+        }
+        Ok(revisions)
+    }
+
     pub fn unregister(&self, name: &str) -> Result<()> {
         // Delete state first due to foreign key constraint
         self.conn
             .execute("DELETE FROM daemon_states WHERE name = ?1", params![name])
             .map_err(|e| SyspulseError::Database(format!("Failed to delete state: {}", e)))?;
 
+        self.conn
+            .execute(
+                &format!("DELETE FROM daemon_events WHERE {}", EVENTS_OF_DAEMON),
+                params![name],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to delete events: {}", e)))?;
+
+        self.conn
+            .execute(
+                &format!("DELETE FROM daemon_runs WHERE {}", EVENTS_OF_DAEMON),
+                params![name],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to delete runs: {}", e)))?;
+
+        self.conn
+            .execute("DELETE FROM spec_revisions WHERE name = ?1", params![name])
+            .map_err(|e| SyspulseError::Database(format!("Failed to delete revisions: {}", e)))?;
+
         let changes = self
             .conn
             .execute("DELETE FROM daemon_specs WHERE name = ?1", params![name])
@@ -150,12 +389,16 @@ impl Registry {
             .stderr_log
             .as_ref()
             .map(|p| p.to_string_lossy().to_string());
+        let notify_socket = instance
+            .notify_socket
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string());
 
         self.conn
             .execute(
-                "INSERT INTO daemon_states (name, instance_id, state, pid, started_at, stopped_at, exit_code, restart_count, health_status, stdout_log, stderr_log)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
-                 ON CONFLICT(name) DO UPDATE SET
+                "INSERT INTO daemon_states (name, instance_id, state, pid, started_at, stopped_at, exit_code, restart_count, health_status, stdout_log, stderr_log, exit_signal, pid_start_time, replica, notify_socket)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                 ON CONFLICT(name, replica) DO UPDATE SET
                      instance_id = excluded.instance_id,
                      state = excluded.state,
                      pid = excluded.pid,
@@ -165,7 +408,10 @@ impl Registry {
                      restart_count = excluded.restart_count,
                      health_status = excluded.health_status,
                      stdout_log = excluded.stdout_log,
-                     stderr_log = excluded.stderr_log",
+                     stderr_log = excluded.stderr_log,
+                     exit_signal = excluded.exit_signal,
+                     pid_start_time = excluded.pid_start_time,
+                     notify_socket = excluded.notify_socket",
                 params![
                     instance.spec_name,
                     instance.id,
@@ -178,6 +424,10 @@ impl Registry {
                     health_str,
                     stdout_log,
                     stderr_log,
+                    instance.exit_signal,
+                    instance.pid_start_time,
+                    instance.replica,
+                    notify_socket,
                 ],
             )
             .map_err(|e| SyspulseError::Database(format!("Failed to update state: {}", e)))?;
@@ -185,15 +435,16 @@ impl Registry {
         Ok(())
     }
 
-    pub fn get_state(&self, name: &str) -> Result<DaemonInstance> {
+    pub fn get_state(&self, name: &str, replica: u32) -> Result<DaemonInstance> {
         self.conn
             .query_row(
-                "SELECT instance_id, state, pid, started_at, stopped_at, exit_code, restart_count, health_status, stdout_log, stderr_log
-                 FROM daemon_states WHERE name = ?1",
-                params![name],
+                "SELECT instance_id, state, pid, started_at, stopped_at, exit_code, restart_count, health_status, stdout_log, stderr_log, exit_signal, pid_start_time, notify_socket
+                 FROM daemon_states WHERE name = ?1 AND replica = ?2",
+                params![name, replica],
                 |row| {
                     Ok(StateRow {
                         name: name.to_string(),
+                        replica,
                         instance_id: row.get(0)?,
                         state: row.get::<_, String>(1)?,
                         pid: row.get(2)?,
@@ -204,24 +455,38 @@ impl Registry {
                         health_status: row.get::<_, Option<String>>(7)?,
                         stdout_log: row.get::<_, Option<String>>(8)?,
                         stderr_log: row.get::<_, Option<String>>(9)?,
+                        exit_signal: row.get(10)?,
+                        pid_start_time: row.get(11)?,
+                        notify_socket: row.get::<_, Option<String>>(12)?,
                     })
                 },
             )
             .map_err(|e| match e {
                 rusqlite::Error::QueryReturnedNoRows => {
-                    SyspulseError::DaemonNotFound(name.to_string())
+                    SyspulseError::DaemonNotFound(replica_name(name, replica))
                 }
                 _ => SyspulseError::Database(format!("Failed to get state: {}", e)),
             })
             .map(|r| r.into_instance())
     }
 
+    /// Forget the state of a replica that was scaled away.
+    pub fn delete_state(&self, name: &str, replica: u32) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM daemon_states WHERE name = ?1 AND replica = ?2",
+                params![name, replica],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to delete state: {}", e)))?;
+        Ok(())
+    }
+
     pub fn list_states(&self) -> Result<Vec<DaemonInstance>> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT name, instance_id, state, pid, started_at, stopped_at, exit_code, restart_count, health_status, stdout_log, stderr_log
-                 FROM daemon_states ORDER BY name",
+                "SELECT name, instance_id, state, pid, started_at, stopped_at, exit_code, restart_count, health_status, stdout_log, stderr_log, exit_signal, pid_start_time, replica, notify_socket
+                 FROM daemon_states ORDER BY name, replica",
             )
             .map_err(|e| SyspulseError::Database(format!("Failed to prepare query: {}", e)))?;
 
@@ -239,6 +504,10 @@ impl Registry {
                     health_status: row.get::<_, Option<String>>(8)?,
                     stdout_log: row.get::<_, Option<String>>(9)?,
                     stderr_log: row.get::<_, Option<String>>(10)?,
+                    exit_signal: row.get(11)?,
+                    pid_start_time: row.get(12)?,
+                    replica: row.get(13)?,
+                    notify_socket: row.get::<_, Option<String>>(14)?,
                 })
             })
             .map_err(|e| SyspulseError::Database(format!("Failed to list states: {}", e)))?
@@ -250,14 +519,327 @@ impl Registry {
     }
 }
 
+impl Registry {
+    /// Append an event to a daemon's history and prune events beyond the
+    /// retention limits.
+    pub fn record_event(
+        &self,
+        name: &str,
+        kind: EventKind,
+        message: Option<String>,
+    ) -> Result<DaemonEvent> {
+        // Stored with microsecond precision; match it in the returned event.
+        let timestamp = Utc::now().trunc_subsecs(6);
+        let json = serde_json::to_string(&kind)?;
+
+        self.conn
+            .execute(
+                "INSERT INTO daemon_events (name, timestamp, kind, event_json, message)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    name,
+                    event_timestamp(&timestamp),
+                    kind.name(),
+                    json,
+                    message
+                ],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to record event: {}", e)))?;
+        let id = self.conn.last_insert_rowid();
+
+        let cutoff = timestamp - chrono::Duration::days(EVENT_RETENTION_DAYS);
+        self.conn
+            .execute(
+                "DELETE FROM daemon_events WHERE timestamp < ?1",
+                params![event_timestamp(&cutoff)],
+            )
+            .and_then(|_| {
+                self.conn.execute(
+                    "DELETE FROM daemon_events WHERE name = ?1 AND id <= (
+                         SELECT id FROM daemon_events WHERE name = ?1
+                         ORDER BY id DESC LIMIT 1 OFFSET ?2
+                     )",
+                    params![name, EVENT_RETENTION_COUNT as i64],
+                )
+            })
+            .map_err(|e| SyspulseError::Database(format!("Failed to prune events: {}", e)))?;
+
+        Ok(DaemonEvent {
+            id,
+            timestamp,
+            name: name.to_string(),
+            kind,
+            message,
+        })
+    }
+
+    /// The latest `limit` events, optionally of one daemon (and its replicas)
+    /// and not older than `since`, oldest first.
+    pub fn list_events(
+        &self,
+        name: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<DaemonEvent>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT id, name, timestamp, event_json, message FROM daemon_events
+                 WHERE (?1 IS NULL OR {}) AND (?2 IS NULL OR timestamp >= ?2)
+                 ORDER BY id DESC LIMIT ?3",
+                EVENTS_OF_DAEMON
+            ))
+            .map_err(|e| SyspulseError::Database(format!("Failed to prepare query: {}", e)))?;
+
+        let since = since.map(|t| event_timestamp(&t));
+        let mut events: Vec<DaemonEvent> = stmt
+            .query_map(params![name, since, limit as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })
+            .map_err(|e| SyspulseError::Database(format!("Failed to list events: {}", e)))?
+            .filter_map(|r| r.ok())
+            .filter_map(|(id, name, timestamp, json, message)| {
+                Some(DaemonEvent {
+                    id,
+                    timestamp: DateTime::parse_from_rfc3339(&timestamp)
+                        .ok()?
+                        .with_timezone(&Utc),
+                    name,
+                    kind: serde_json::from_str(&json).ok()?,
+                    message,
+                })
+            })
+            .collect();
+        events.reverse();
+
+        Ok(events)
+    }
+}
+
+impl Registry {
+    /// Record the start of a run of the instance `name` and prune runs
+    /// beyond the retention limit. A run still open from before, e.g. one
+    /// interrupted by a crash of the manager, is closed as failed.
+    pub fn start_run(&self, name: &str, trigger: RunTrigger, pid: u32) -> Result<DaemonRun> {
+        self.finish_run(name, RunStatus::Failed, None, None)?;
+        self.insert_run(name, trigger, RunStatus::Running, Some(pid))
+    }
+
+    /// Record a run that was not started because the previous one was
+    /// still going.
+    pub fn record_skipped_run(&self, name: &str, trigger: RunTrigger) -> Result<DaemonRun> {
+        self.insert_run(name, trigger, RunStatus::Skipped, None)
+    }
+
+    fn insert_run(
+        &self,
+        name: &str,
+        trigger: RunTrigger,
+        status: RunStatus,
+        pid: Option<u32>,
+    ) -> Result<DaemonRun> {
+        let started_at = Utc::now().trunc_subsecs(6);
+        let ended_at = (status != RunStatus::Running).then_some(started_at);
+        self.conn
+            .execute(
+                "INSERT INTO daemon_runs (name, trigger, status, started_at, ended_at, pid)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    name,
+                    trigger.name(),
+                    status.name(),
+                    event_timestamp(&started_at),
+                    ended_at.as_ref().map(event_timestamp),
+                    pid
+                ],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to record run: {}", e)))?;
+        let id = self.conn.last_insert_rowid();
+
+        self.conn
+            .execute(
+                "DELETE FROM daemon_runs WHERE name = ?1 AND id <= (
+                     SELECT id FROM daemon_runs WHERE name = ?1
+                     ORDER BY id DESC LIMIT 1 OFFSET ?2
+                 )",
+                params![name, RUN_RETENTION_COUNT as i64],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to prune runs: {}", e)))?;
+
+        Ok(DaemonRun {
+            id,
+            name: name.to_string(),
+            trigger,
+            status,
+            started_at,
+            ended_at,
+            pid,
+            exit_code: None,
+            exit_signal: None,
+        })
+    }
+
+    /// Decide how the open run of `name` ended before its process is gone,
+    /// e.g. when the manager kills it for running too long.
+    /// [`finish_run`](Self::finish_run) then keeps this status.
+    pub fn mark_run(&self, name: &str, status: RunStatus) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE daemon_runs SET status = ?2
+                 WHERE name = ?1 AND ended_at IS NULL AND status = 'running'",
+                params![name, status.name()],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to update run: {}", e)))?;
+        Ok(())
+    }
+
+    /// Close the open run of `name`, if there is one, with `status` unless
+    /// [`mark_run`](Self::mark_run) already set another. Returns whether a
+    /// run was closed.
+    pub fn finish_run(
+        &self,
+        name: &str,
+        status: RunStatus,
+        exit_code: Option<i32>,
+        exit_signal: Option<i32>,
+    ) -> Result<bool> {
+        let changes = self
+            .conn
+            .execute(
+                "UPDATE daemon_runs
+                 SET ended_at = ?2, exit_code = ?3, exit_signal = ?4,
+                     status = CASE WHEN status = 'running' THEN ?5 ELSE status END
+                 WHERE name = ?1 AND ended_at IS NULL",
+                params![
+                    name,
+                    event_timestamp(&Utc::now()),
+                    exit_code,
+                    exit_signal,
+                    status.name()
+                ],
+            )
+            .map_err(|e| SyspulseError::Database(format!("Failed to finish run: {}", e)))?;
+        Ok(changes > 0)
+    }
+
+    /// The latest `limit` runs of a daemon and its replicas, oldest first.
+    pub fn list_runs(&self, name: &str, limit: usize) -> Result<Vec<DaemonRun>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT id, name, trigger, status, started_at, ended_at, pid, exit_code,
+                        exit_signal
+                 FROM daemon_runs WHERE {}
+                 ORDER BY id DESC LIMIT ?2",
+                EVENTS_OF_DAEMON
+            ))
+            .map_err(|e| SyspulseError::Database(format!("Failed to prepare query: {}", e)))?;
+
+        let parse_dt = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|t| t.with_timezone(&Utc))
+        };
+        let mut runs: Vec<DaemonRun> = stmt
+            .query_map(params![name, limit as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<u32>>(6)?,
+                    row.get::<_, Option<i32>>(7)?,
+                    row.get::<_, Option<i32>>(8)?,
+                ))
+            })
+            .map_err(|e| SyspulseError::Database(format!("Failed to list runs: {}", e)))?
+            .filter_map(|r| r.ok())
+            .filter_map(
+                |(id, name, trigger, status, started_at, ended_at, pid, exit_code, exit_signal)| {
+                    Some(DaemonRun {
+                        id,
+                        name,
+                        trigger: RunTrigger::from_name(&trigger)?,
+                        status: RunStatus::from_name(&status)?,
+                        started_at: parse_dt(&started_at)?,
+                        ended_at: ended_at.as_deref().and_then(parse_dt),
+                        pid,
+                        exit_code,
+                        exit_signal,
+                    })
+                },
+            )
+            .collect();
+        runs.reverse();
+
+        Ok(runs)
+    }
+
+    /// When the latest run of a daemon or its replicas that its schedule
+    /// started (including skipped and catch-up runs) began.
+    pub fn last_scheduled_run(&self, name: &str) -> Result<Option<DateTime<Utc>>> {
+        let started_at = self.conn.query_row(
+            &format!(
+                "SELECT started_at FROM daemon_runs
+                     WHERE {} AND trigger != ?2
+                     ORDER BY id DESC LIMIT 1",
+                EVENTS_OF_DAEMON
+            ),
+            params![name, RunTrigger::Manual.name()],
+            |row| row.get::<_, String>(0),
+        );
+        match started_at {
+            Ok(s) => Ok(DateTime::parse_from_rfc3339(&s)
+                .ok()
+                .map(|t| t.with_timezone(&Utc))),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(SyspulseError::Database(format!(
+                "Failed to query runs: {}",
+                e
+            ))),
+        }
+    }
+}
+
+fn spec_revision(revision: u64, json: &str, created_at: &str) -> Result<SpecRevision> {
+    let created_at = DateTime::parse_from_rfc3339(created_at)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| SyspulseError::Database(format!("Invalid revision timestamp: {}", e)))?;
+    Ok(SpecRevision {
+        revision,
+        created_at,
+        spec: serde_json::from_str(json)?,
+    })
+}
+
+/// Matches events and runs of the daemon `?1` and of its replicas (`?1:index`).
+const EVENTS_OF_DAEMON: &str = "(name = ?1 OR substr(name, 1, length(?1) + 1) = ?1 || ':')";
+
+/// Fixed-width RFC 3339 so that timestamps compare correctly as text.
+fn event_timestamp(t: &DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
 struct StateRow {
     name: String,
+    replica: u32,
     instance_id: String,
     state: String,
     pid: Option<u32>,
     started_at: Option<String>,
     stopped_at: Option<String>,
     exit_code: Option<i32>,
+    exit_signal: Option<i32>,
+    pid_start_time: Option<u64>,
+    notify_socket: Option<String>,
     restart_count: Option<u32>,
     health_status: Option<String>,
     stdout_log: Option<String>,
@@ -273,6 +855,7 @@ impl StateRow {
             "stopping" => LifecycleState::Stopping,
             "failed" => LifecycleState::Failed,
             "scheduled" => LifecycleState::Scheduled,
+            "crash_looping" => LifecycleState::CrashLooping,
             _ => LifecycleState::Stopped,
         };
 
@@ -291,15 +874,253 @@ impl StateRow {
         DaemonInstance {
             id: self.instance_id,
             spec_name: self.name,
+            replica: self.replica,
             state,
             pid: self.pid,
             started_at: parse_dt(self.started_at),
             stopped_at: parse_dt(self.stopped_at),
             exit_code: self.exit_code,
+            exit_signal: self.exit_signal,
+            pid_start_time: self.pid_start_time,
+            notify_socket: self.notify_socket.map(std::path::PathBuf::from),
             restart_count: self.restart_count.unwrap_or(0),
             health_status,
             stdout_log: self.stdout_log.map(std::path::PathBuf::from),
             stderr_log: self.stderr_log.map(std::path::PathBuf::from),
+            warnings: Vec::new(),
+            reason: None,
+            status_message: None,
+            last_watchdog: None,
+            next_run_at: None,
+            recent_restarts: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::ExitStatus;
+
+    fn registry() -> (tempfile::TempDir, Registry) {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Registry::new(&dir.path().join("syspulse.db")).unwrap();
+        (dir, registry)
+    }
+
+    #[test]
+    fn update_spec_keeps_revisions() {
+        let (_dir, reg) = registry();
+        let mut spec: DaemonSpec =
+            serde_json::from_str(r#"{"name": "web", "command": ["web"]}"#).unwrap();
+        assert!(matches!(
+            reg.update_spec(&spec, None),
+            Err(SyspulseError::DaemonNotFound(_))
+        ));
+
+        reg.register(&spec).unwrap();
+        assert_eq!(reg.get_current_revision("web").unwrap().revision, 1);
+
+        spec.command = vec!["web".to_string(), "--port=8080".to_string()];
+        assert_eq!(reg.update_spec(&spec, Some(1)).unwrap(), 2);
+        assert_eq!(reg.get_spec("web").unwrap().command, spec.command);
+
+        let revisions = reg.list_revisions("web").unwrap();
+        assert_eq!(
+            revisions.iter().map(|r| r.revision).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(
+            reg.get_revision("web", 1).unwrap().spec.command,
+            vec!["web"]
+        );
+        assert!(reg.get_revision("web", 3).is_err());
+
+        reg.unregister("web").unwrap();
+        reg.register(&spec).unwrap();
+        assert_eq!(reg.list_revisions("web").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn stale_revision_is_rejected() {
+        let (_dir, reg) = registry();
+        let spec: DaemonSpec =
+            serde_json::from_str(r#"{"name": "web", "command": ["web"]}"#).unwrap();
+        reg.register(&spec).unwrap();
+        reg.update_spec(&spec, Some(1)).unwrap();
+
+        assert!(matches!(
+            reg.update_spec(&spec, Some(1)),
+            Err(SyspulseError::RevisionConflict {
+                expected: 1,
+                actual: 2,
+                ..
+            })
+        ));
+        assert_eq!(reg.get_current_revision("web").unwrap().revision, 2);
+    }
+
+    #[test]
+    fn events_are_listed_oldest_first_and_filtered() {
+        let (_dir, reg) = registry();
+        reg.record_event("web", EventKind::Started { pid: 10 }, None)
+            .unwrap();
+        reg.record_event("db", EventKind::Started { pid: 11 }, None)
+            .unwrap();
+        reg.record_event("web", EventKind::exited(Some(ExitStatus::Code(1))), None)
+            .unwrap();
+
+        let web = reg.list_events(Some("web"), None, 10).unwrap();
+        assert_eq!(web.len(), 2);
+        assert_eq!(web[0].kind, EventKind::Started { pid: 10 });
+        assert_eq!(web[1].kind.name(), "exited");
+
+        let latest = reg.list_events(None, None, 1).unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].name, "web");
+
+        let future = Utc::now() + chrono::Duration::hours(1);
+        assert!(reg.list_events(None, Some(future), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn events_are_pruned_per_daemon() {
+        let (_dir, reg) = registry();
+        for pid in 0..(EVENT_RETENTION_COUNT as u32 + 5) {
+            reg.record_event("web", EventKind::Started { pid }, None)
+                .unwrap();
         }
+        reg.record_event("db", EventKind::CronTriggered, None)
+            .unwrap();
+
+        let web = reg
+            .list_events(Some("web"), None, 2 * EVENT_RETENTION_COUNT)
+            .unwrap();
+        assert_eq!(web.len(), EVENT_RETENTION_COUNT);
+        assert_eq!(web[0].kind, EventKind::Started { pid: 5 });
+        assert_eq!(reg.list_events(Some("db"), None, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn runs_keep_the_status_they_were_marked_with() {
+        let (_dir, reg) = registry();
+        reg.start_run("backup", RunTrigger::Schedule, 10).unwrap();
+        assert!(reg
+            .finish_run("backup", RunStatus::Succeeded, Some(0), None)
+            .unwrap());
+        assert!(!reg
+            .finish_run("backup", RunStatus::Failed, Some(1), None)
+            .unwrap());
+
+        reg.record_skipped_run("backup", RunTrigger::Schedule)
+            .unwrap();
+        reg.start_run("backup:1", RunTrigger::Schedule, 11).unwrap();
+        reg.mark_run("backup:1", RunStatus::TimedOut).unwrap();
+        reg.finish_run("backup:1", RunStatus::Failed, None, Some(15))
+            .unwrap();
+        // Closes the run the manager lost track of.
+        reg.start_run("backup", RunTrigger::Schedule, 12).unwrap();
+        reg.start_run("backup", RunTrigger::Schedule, 13).unwrap();
+
+        let runs = reg.list_runs("backup", 10).unwrap();
+        let statuses: Vec<(&str, RunStatus)> =
+            runs.iter().map(|r| (r.name.as_str(), r.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("backup", RunStatus::Succeeded),
+                ("backup", RunStatus::Skipped),
+                ("backup:1", RunStatus::TimedOut),
+                ("backup", RunStatus::Failed),
+                ("backup", RunStatus::Running),
+            ]
+        );
+        assert_eq!(runs[0].exit_code, Some(0));
+        assert_eq!(runs[2].exit_signal, Some(15));
+        assert!(runs[1].ended_at.is_some() && runs[4].ended_at.is_none());
+        assert_eq!(reg.list_runs("backup", 2).unwrap()[1].pid, Some(13));
+
+        let last = reg.last_scheduled_run("backup").unwrap().unwrap();
+        assert_eq!(last, runs[4].started_at);
+        reg.start_run("backup", RunTrigger::Manual, 14).unwrap();
+        assert_eq!(reg.last_scheduled_run("backup").unwrap(), Some(last));
+        assert_eq!(reg.last_scheduled_run("web").unwrap(), None);
+    }
+
+    #[test]
+    fn states_and_events_are_kept_per_replica() {
+        let (_dir, reg) = registry();
+        let spec: DaemonSpec =
+            serde_json::from_str(r#"{"name": "web", "command": ["web"], "replicas": 2}"#).unwrap();
+        reg.register(&spec).unwrap();
+        let mut second = DaemonInstance::replica("web", 1);
+        second.pid = Some(11);
+        reg.update_state(&DaemonInstance::new("web")).unwrap();
+        reg.update_state(&second).unwrap();
+
+        let states = reg.list_states().unwrap();
+        assert_eq!(
+            states.iter().map(|s| s.name()).collect::<Vec<_>>(),
+            vec!["web", "web:1"]
+        );
+        assert_eq!(reg.get_state("web", 1).unwrap().pid, Some(11));
+
+        reg.record_event("web:1", EventKind::Started { pid: 11 }, None)
+            .unwrap();
+        reg.record_event("webapp", EventKind::Started { pid: 12 }, None)
+            .unwrap();
+        assert_eq!(reg.list_events(Some("web"), None, 10).unwrap().len(), 1);
+
+        reg.delete_state("web", 1).unwrap();
+        assert_eq!(reg.list_states().unwrap().len(), 1);
+        reg.unregister("web").unwrap();
+        assert!(reg.list_events(Some("web"), None, 10).unwrap().is_empty());
+        assert_eq!(reg.list_events(None, None, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn new_state_columns_are_added_to_old_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("syspulse.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE daemon_states (
+                    name TEXT PRIMARY KEY,
+                    instance_id TEXT NOT NULL,
+                    state TEXT NOT NULL,
+                    pid INTEGER,
+                    started_at TEXT,
+                    stopped_at TEXT,
+                    exit_code INTEGER,
+                    restart_count INTEGER DEFAULT 0,
+                    health_status TEXT DEFAULT 'unknown',
+                    stdout_log TEXT,
+                    stderr_log TEXT
+                );",
+            )
+            .unwrap();
+
+        let reg = Registry::new(&path).unwrap();
+        let spec: DaemonSpec =
+            serde_json::from_str(r#"{"name": "web", "command": ["web"]}"#).unwrap();
+        reg.register(&spec).unwrap();
+        let mut instance = DaemonInstance::new("web");
+        instance.exit_signal = Some(9);
+        instance.pid_start_time = Some(1_700_000_000);
+        instance.notify_socket = Some("/tmp/notify/web.1a2b3c4d.sock".into());
+        reg.update_state(&instance).unwrap();
+
+        reg.update_state(&DaemonInstance::replica("web", 1))
+            .unwrap();
+
+        let state = reg.get_state("web", 0).unwrap();
+        assert_eq!(state.exit_signal, Some(9));
+        assert_eq!(state.pid_start_time, Some(1_700_000_000));
+        assert_eq!(
+            state.notify_socket.as_deref(),
+            Some(Path::new("/tmp/notify/web.1a2b3c4d.sock"))
+        );
+        assert_eq!(reg.list_states().unwrap().len(), 2);
     }
 }
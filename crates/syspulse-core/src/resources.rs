@@ -1,8 +1,302 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+fn default_grace_period() -> u64 {
+    30
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceLimits {
     pub max_memory_bytes: Option<u64>,
     pub max_cpu_percent: Option<f64>,
     pub max_open_files: Option<u64>,
+    /// How `max_memory_bytes` and `max_cpu_percent` are enforced.
+    #[serde(default)]
+    pub mode: LimitMode,
+    /// Soft mode: how long a limit must be exceeded before `action` is taken.
+    #[serde(default = "default_grace_period")]
+    pub grace_period_secs: u64,
+    /// Soft mode: what to do once the grace period has elapsed.
+    #[serde(default)]
+    pub action: LimitAction,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitMode {
+    /// Enforced by the OS at spawn time (`RLIMIT_AS`, cgroup `cpu.max`).
+    #[default]
+    Hard,
+    /// Watched by the manager using sampled RSS and CPU usage.
+    Soft,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitAction {
+    /// Log and record the violation, leave the daemon running.
+    Warn,
+    /// Stop the daemon as a failure and let its restart policy decide.
+    #[default]
+    Restart,
+    /// Stop the daemon.
+    Stop,
+}
+
+impl ResourceLimits {
+    pub fn is_soft(&self) -> bool {
+        self.mode == LimitMode::Soft
+    }
+
+    /// Describe which soft limit `sample` exceeds, if any.
+    fn violation(&self, sample: &ResourceSample) -> Option<String> {
+        if let Some(max) = self.max_memory_bytes {
+            if sample.memory_bytes > max {
+                return Some(format!(
+                    "memory {:.1} MiB above limit of {:.1} MiB",
+                    mib(sample.memory_bytes),
+                    mib(max)
+                ));
+            }
+        }
+        if let Some(max) = self.max_cpu_percent {
+            if sample.cpu_percent > max {
+                return Some(format!(
+                    "CPU {:.1}% above limit of {:.1}%",
+                    sample.cpu_percent, max
+                ));
+            }
+        }
+        None
+    }
+}
+
+fn mib(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// One resource usage measurement of a daemon's process tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub timestamp: DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub memory_bytes: u64,
+    pub process_count: u32,
+}
+
+/// Recent resource samples of a daemon, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStats {
+    pub name: String,
+    pub pid: Option<u32>,
+    pub samples: Vec<ResourceSample>,
+}
+
+impl DaemonStats {
+    pub fn latest(&self) -> Option<&ResourceSample> {
+        self.samples.last()
+    }
+}
+
+/// Bounded in-memory history of resource samples per daemon.
+#[derive(Debug)]
+pub struct StatsHistory {
+    capacity: usize,
+    samples: HashMap<String, VecDeque<ResourceSample>>,
+}
+
+impl StatsHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Append a sample, dropping the oldest one once `capacity` is reached.
+    pub fn record(&mut self, name: &str, sample: ResourceSample) {
+        let history = self.samples.entry(name.to_string()).or_default();
+        if history.len() == self.capacity {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    /// Forget daemons for which `keep` returns false.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.samples.retain(|name, _| keep(name));
+    }
+
+    pub fn get(&self, name: &str) -> Vec<ResourceSample> {
+        self.samples
+            .get(name)
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Tracks how long each daemon has been over its soft limits.
+#[derive(Debug, Default)]
+pub struct SoftLimitTracker {
+    exceeded_since: HashMap<String, DateTime<Utc>>,
+    /// Daemons whose current violation has already been acted upon.
+    reported: HashSet<String>,
+}
+
+impl SoftLimitTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a new sample. Returns a description of the violation once a
+    /// limit has been exceeded for the whole grace period, and only once per
+    /// continuous violation.
+    pub fn observe(
+        &mut self,
+        name: &str,
+        limits: &ResourceLimits,
+        sample: &ResourceSample,
+    ) -> Option<String> {
+        let violation = match limits.violation(sample).filter(|_| limits.is_soft()) {
+            Some(violation) => violation,
+            None => {
+                self.exceeded_since.remove(name);
+                self.reported.remove(name);
+                return None;
+            }
+        };
+        if self.reported.contains(name) {
+            return None;
+        }
+
+        let since = *self
+            .exceeded_since
+            .entry(name.to_string())
+            .or_insert(sample.timestamp);
+        let elapsed = (sample.timestamp - since).num_seconds().max(0) as u64;
+        if elapsed < limits.grace_period_secs {
+            return None;
+        }
+
+        self.exceeded_since.remove(name);
+        self.reported.insert(name.to_string());
+        Some(format!("{} for {}s", violation, elapsed))
+    }
+
+    /// Forget daemons for which `keep` returns false.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.exceeded_since.retain(|name, _| keep(name));
+        self.reported.retain(|name| keep(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cpu_percent: f64) -> ResourceSample {
+        ResourceSample {
+            timestamp: Utc::now(),
+            cpu_percent,
+            memory_bytes: 0,
+            process_count: 1,
+        }
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = StatsHistory::new(3);
+        for i in 0..5 {
+            history.record("web", sample(i as f64));
+        }
+        let cpu: Vec<f64> = history.get("web").iter().map(|s| s.cpu_percent).collect();
+        assert_eq!(cpu, vec![2.0, 3.0, 4.0]);
+        assert!(history.get("db").is_empty());
+    }
+
+    #[test]
+    fn retain_drops_stale_daemons() {
+        let mut history = StatsHistory::new(3);
+        history.record("web", sample(1.0));
+        history.record("db", sample(1.0));
+        history.retain(|name| name == "web");
+        assert_eq!(history.get("web").len(), 1);
+        assert!(history.get("db").is_empty());
+    }
+
+    fn soft_limits(max_memory_bytes: u64, grace_period_secs: u64) -> ResourceLimits {
+        ResourceLimits {
+            max_memory_bytes: Some(max_memory_bytes),
+            max_cpu_percent: None,
+            max_open_files: None,
+            mode: LimitMode::Soft,
+            grace_period_secs,
+            action: LimitAction::Restart,
+        }
+    }
+
+    fn memory_sample(memory_bytes: u64, secs: i64) -> ResourceSample {
+        ResourceSample {
+            timestamp: DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap(),
+            cpu_percent: 0.0,
+            memory_bytes,
+            process_count: 1,
+        }
+    }
+
+    #[test]
+    fn soft_limit_fires_after_grace_period_once() {
+        let limits = soft_limits(100, 10);
+        let mut tracker = SoftLimitTracker::new();
+
+        assert!(tracker
+            .observe("web", &limits, &memory_sample(200, 0))
+            .is_none());
+        assert!(tracker
+            .observe("web", &limits, &memory_sample(200, 5))
+            .is_none());
+        let reason = tracker
+            .observe("web", &limits, &memory_sample(200, 10))
+            .unwrap();
+        assert!(reason.contains("memory"), "{}", reason);
+        assert!(reason.ends_with("for 10s"), "{}", reason);
+        assert!(tracker
+            .observe("web", &limits, &memory_sample(200, 20))
+            .is_none());
+    }
+
+    #[test]
+    fn soft_limit_resets_when_usage_drops() {
+        let limits = soft_limits(100, 10);
+        let mut tracker = SoftLimitTracker::new();
+
+        tracker.observe("web", &limits, &memory_sample(200, 0));
+        tracker.observe("web", &limits, &memory_sample(50, 8));
+        assert!(tracker
+            .observe("web", &limits, &memory_sample(200, 12))
+            .is_none());
+        assert!(tracker
+            .observe("web", &limits, &memory_sample(200, 22))
+            .is_some());
+    }
+
+    #[test]
+    fn hard_limits_are_not_tracked() {
+        let mut limits = soft_limits(100, 0);
+        limits.mode = LimitMode::Hard;
+        let mut tracker = SoftLimitTracker::new();
+        assert!(tracker
+            .observe("web", &limits, &memory_sample(200, 0))
+            .is_none());
+    }
+
+    #[test]
+    fn limit_mode_defaults_to_hard() {
+        let limits: ResourceLimits = serde_json::from_str(r#"{"max_memory_bytes": 1}"#).unwrap();
+        assert_eq!(limits.mode, LimitMode::Hard);
+        assert_eq!(limits.action, LimitAction::Restart);
+        assert_eq!(limits.grace_period_secs, 30);
+    }
 }
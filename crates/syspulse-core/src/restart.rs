@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -13,6 +14,10 @@ fn default_backoff_max() -> f64 {
     300.0
 }
 
+fn default_burst_window() -> u64 {
+    60
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "policy", rename_all = "snake_case")]
 pub enum RestartPolicy {
@@ -23,6 +28,12 @@ pub enum RestartPolicy {
         backoff_base_secs: f64,
         #[serde(default = "default_backoff_max")]
         backoff_max_secs: f64,
+        #[serde(default)]
+        stable_after_secs: Option<u64>,
+        #[serde(default)]
+        burst_limit: Option<u32>,
+        #[serde(default = "default_burst_window")]
+        burst_window_secs: u64,
     },
     OnFailure {
         #[serde(default = "default_max_retries")]
@@ -31,10 +42,35 @@ pub enum RestartPolicy {
         backoff_base_secs: f64,
         #[serde(default = "default_backoff_max")]
         backoff_max_secs: f64,
+        #[serde(default)]
+        stable_after_secs: Option<u64>,
+        #[serde(default)]
+        burst_limit: Option<u32>,
+        #[serde(default = "default_burst_window")]
+        burst_window_secs: u64,
     },
     Never,
 }
 
+impl RestartPolicy {
+    /// The burst limit and its window in seconds, if the policy has one.
+    pub fn burst(&self) -> Option<(u32, u64)> {
+        match self {
+            RestartPolicy::Always {
+                burst_limit: Some(limit),
+                burst_window_secs,
+                ..
+            }
+            | RestartPolicy::OnFailure {
+                burst_limit: Some(limit),
+                burst_window_secs,
+                ..
+            } => Some((*limit, *burst_window_secs)),
+            _ => None,
+        }
+    }
+}
+
 impl Default for RestartPolicy {
     fn default() -> Self {
         RestartPolicy::Never
@@ -84,6 +120,42 @@ impl RestartEvaluator {
             RestartPolicy::Never => Duration::ZERO,
         }
     }
+
+    /// Whether a process that ran for `uptime` was up long enough for its
+    /// exit to start the restart count over.
+    pub fn is_stable(policy: &RestartPolicy, uptime: Duration) -> bool {
+        match policy {
+            RestartPolicy::Always {
+                stable_after_secs: Some(secs),
+                ..
+            }
+            | RestartPolicy::OnFailure {
+                stable_after_secs: Some(secs),
+                ..
+            } => uptime >= Duration::from_secs(*secs),
+            _ => false,
+        }
+    }
+
+    /// Whether restarting now would exceed the policy's burst limit.
+    /// `recent` holds the times of earlier restarts; those outside the
+    /// window are dropped, and `now` is added if the restart may go ahead.
+    pub fn exceeds_burst(
+        policy: &RestartPolicy,
+        recent: &mut Vec<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let Some((limit, window_secs)) = policy.burst() else {
+            return false;
+        };
+        let window = chrono::Duration::seconds(window_secs as i64);
+        recent.retain(|t| now - *t < window);
+        if recent.len() >= limit as usize {
+            return true;
+        }
+        recent.push(now);
+        false
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +176,9 @@ mod tests {
             max_retries: None,
             backoff_base_secs: 1.0,
             backoff_max_secs: 300.0,
+            stable_after_secs: None,
+            burst_limit: None,
+            burst_window_secs: 60,
         };
         assert!(RestartEvaluator::should_restart(&policy, Some(0), 0));
         assert!(RestartEvaluator::should_restart(&policy, Some(1), 0));
@@ -117,6 +192,9 @@ mod tests {
             max_retries: Some(3),
             backoff_base_secs: 1.0,
             backoff_max_secs: 300.0,
+            stable_after_secs: None,
+            burst_limit: None,
+            burst_window_secs: 60,
         };
         assert!(RestartEvaluator::should_restart(&policy, Some(1), 0));
         assert!(RestartEvaluator::should_restart(&policy, Some(1), 2));
@@ -130,6 +208,9 @@ mod tests {
             max_retries: None,
             backoff_base_secs: 1.0,
             backoff_max_secs: 300.0,
+            stable_after_secs: None,
+            burst_limit: None,
+            burst_window_secs: 60,
         };
         // exit_code 0 => success => no restart
         assert!(!RestartEvaluator::should_restart(&policy, Some(0), 0));
@@ -146,6 +227,9 @@ mod tests {
             max_retries: Some(2),
             backoff_base_secs: 1.0,
             backoff_max_secs: 300.0,
+            stable_after_secs: None,
+            burst_limit: None,
+            burst_window_secs: 60,
         };
         assert!(RestartEvaluator::should_restart(&policy, Some(1), 0));
         assert!(RestartEvaluator::should_restart(&policy, Some(1), 1));
@@ -186,6 +270,81 @@ mod tests {
         assert_eq!(d, Duration::ZERO);
     }
 
+    #[test]
+    fn stable_uptime_resets_the_count() {
+        let policy = RestartPolicy::OnFailure {
+            max_retries: Some(3),
+            backoff_base_secs: 1.0,
+            backoff_max_secs: 300.0,
+            stable_after_secs: Some(600),
+            burst_limit: None,
+            burst_window_secs: 60,
+        };
+        assert!(RestartEvaluator::is_stable(
+            &policy,
+            Duration::from_secs(600)
+        ));
+        assert!(!RestartEvaluator::is_stable(
+            &policy,
+            Duration::from_secs(599)
+        ));
+        assert!(!RestartEvaluator::is_stable(
+            &RestartPolicy::Never,
+            Duration::from_secs(3600)
+        ));
+    }
+
+    #[test]
+    fn burst_limit_counts_restarts_in_the_window() {
+        let policy = RestartPolicy::Always {
+            max_retries: None,
+            backoff_base_secs: 1.0,
+            backoff_max_secs: 300.0,
+            stable_after_secs: None,
+            burst_limit: Some(3),
+            burst_window_secs: 60,
+        };
+        let start = Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        let mut recent = Vec::new();
+        assert!(!RestartEvaluator::exceeds_burst(
+            &policy,
+            &mut recent,
+            at(0)
+        ));
+        assert!(!RestartEvaluator::exceeds_burst(
+            &policy,
+            &mut recent,
+            at(10)
+        ));
+        assert!(!RestartEvaluator::exceeds_burst(
+            &policy,
+            &mut recent,
+            at(20)
+        ));
+        assert!(RestartEvaluator::exceeds_burst(
+            &policy,
+            &mut recent,
+            at(30)
+        ));
+        // The first restart has left the window.
+        assert!(!RestartEvaluator::exceeds_burst(
+            &policy,
+            &mut recent,
+            at(60)
+        ));
+        assert_eq!(recent.len(), 3);
+
+        let mut recent = Vec::new();
+        for secs in 0..100 {
+            assert!(!RestartEvaluator::exceeds_burst(
+                &RestartPolicy::Never,
+                &mut recent,
+                at(secs)
+            ));
+        }
+    }
+
     #[test]
     fn default_restart_policy_is_never() {
         match RestartPolicy::default() {
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// At most this many runs are kept per daemon.
+pub const RUN_RETENTION_COUNT: usize = 100;
+
+/// One run of a scheduled daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonRun {
+    pub id: i64,
+    /// Instance the run belongs to (`name` or `name:replica`).
+    pub name: String,
+    pub trigger: RunTrigger,
+    pub status: RunStatus,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the run is still going.
+    #[serde(default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub pid: Option<u32>,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub exit_signal: Option<i32>,
+}
+
+impl DaemonRun {
+    /// How long the run took, or has taken so far.
+    pub fn duration(&self) -> Duration {
+        self.ended_at.unwrap_or_else(Utc::now) - self.started_at
+    }
+}
+
+/// What started a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    /// The cron schedule fired.
+    Schedule,
+    /// Started with `syspulse trigger`.
+    Manual,
+    /// Started on manager startup for a run missed while it was down.
+    CatchUp,
+}
+
+impl RunTrigger {
+    pub fn name(self) -> &'static str {
+        match self {
+            RunTrigger::Schedule => "schedule",
+            RunTrigger::Manual => "manual",
+            RunTrigger::CatchUp => "catch_up",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "schedule" => Some(RunTrigger::Schedule),
+            "manual" => Some(RunTrigger::Manual),
+            "catch_up" => Some(RunTrigger::CatchUp),
+            _ => None,
+        }
+    }
+}
+
+/// How a run went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    /// The process exited with code 0.
+    Succeeded,
+    /// The process exited with another code, was killed by a signal, or was
+    /// ended by the manager, e.g. for missing its watchdog.
+    Failed,
+    /// The process was killed for exceeding `max_runtime_secs`.
+    TimedOut,
+    /// The daemon was stopped on request.
+    Stopped,
+    /// The process was stopped for the next run (`schedule_overlap = "replace"`).
+    Replaced,
+    /// Not started because the previous run was still going
+    /// (`schedule_overlap = "skip"`).
+    Skipped,
+}
+
+impl RunStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            RunStatus::Running => "running",
+            RunStatus::Succeeded => "succeeded",
+            RunStatus::Failed => "failed",
+            RunStatus::TimedOut => "timed_out",
+            RunStatus::Stopped => "stopped",
+            RunStatus::Replaced => "replaced",
+            RunStatus::Skipped => "skipped",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "running" => Some(RunStatus::Running),
+            "succeeded" => Some(RunStatus::Succeeded),
+            "failed" => Some(RunStatus::Failed),
+            "timed_out" => Some(RunStatus::TimedOut),
+            "stopped" => Some(RunStatus::Stopped),
+            "replaced" => Some(RunStatus::Replaced),
+            "skipped" => Some(RunStatus::Skipped),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_names_round_trip() {
+        for status in [
+            RunStatus::Running,
+            RunStatus::Succeeded,
+            RunStatus::Failed,
+            RunStatus::TimedOut,
+            RunStatus::Stopped,
+            RunStatus::Replaced,
+            RunStatus::Skipped,
+        ] {
+            assert_eq!(RunStatus::from_name(status.name()), Some(status));
+            assert_eq!(
+                serde_json::to_value(status).unwrap(),
+                serde_json::Value::from(status.name())
+            );
+        }
+        for trigger in [
+            RunTrigger::Schedule,
+            RunTrigger::Manual,
+            RunTrigger::CatchUp,
+        ] {
+            assert_eq!(RunTrigger::from_name(trigger.name()), Some(trigger));
+        }
+        assert_eq!(RunStatus::from_name("bogus"), None);
+    }
+}
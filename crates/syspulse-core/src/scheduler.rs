@@ -1,10 +1,149 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
 use tokio_cron_scheduler::{Job, JobScheduler};
-use tracing::info;
+use tracing::{info, warn};
+use uuid::Uuid;
 
+use crate::daemon::DaemonSpec;
 use crate::error::{Result, SyspulseError};
 
+/// When a scheduled daemon's job fires.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobSchedule {
+    /// A cron expression with seconds, evaluated in `timezone`.
+    Cron { expr: String, timezone: Tz },
+    /// A fixed interval, counted from when the job was added.
+    Every(Duration),
+}
+
+impl JobSchedule {
+    /// The schedule of `spec`, or `None` if it has none.
+    pub fn from_spec(spec: &DaemonSpec) -> Result<Option<Self>> {
+        if let Some(expr) = &spec.schedule {
+            parse_cron(expr).map_err(|e| {
+                SyspulseError::Config(format!(
+                    "Daemon '{}' has an invalid schedule '{}': {}",
+                    spec.name, expr, e
+                ))
+            })?;
+            let timezone = match &spec.schedule_timezone {
+                Some(name) => name.parse::<Tz>().map_err(|_| {
+                    SyspulseError::Config(format!(
+                        "Daemon '{}' has an unknown schedule_timezone '{}'",
+                        spec.name, name
+                    ))
+                })?,
+                None => Tz::UTC,
+            };
+            return Ok(Some(JobSchedule::Cron {
+                expr: expr.clone(),
+                timezone,
+            }));
+        }
+        match &spec.every {
+            Some(every) => Ok(Some(JobSchedule::Every(parse_duration(
+                &spec.name, "every", every,
+            )?))),
+            None => Ok(None),
+        }
+    }
+
+    /// When the schedule fires next after `after`. For a cron schedule this
+    /// follows daylight saving changes in its time zone.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            JobSchedule::Cron { expr, timezone } => parse_cron(expr)
+                .ok()?
+                .find_next_occurrence(&after.with_timezone(timezone), false)
+                .ok()
+                .map(|tick| tick.with_timezone(&Utc)),
+            JobSchedule::Every(every) => Some(after + chrono::Duration::from_std(*every).ok()?),
+        }
+    }
+
+    /// How many times the schedule fired after `since` and up to `until`,
+    /// counting at most `limit`.
+    pub fn ticks_between(&self, since: DateTime<Utc>, until: DateTime<Utc>, limit: usize) -> usize {
+        if until <= since {
+            return 0;
+        }
+        match self {
+            JobSchedule::Cron { expr, timezone } => {
+                let Ok(cron) = parse_cron(expr) else {
+                    return 0;
+                };
+                cron.iter_after(since.with_timezone(timezone))
+                    .take_while(|tick| tick.with_timezone(&Utc) <= until)
+                    .take(limit)
+                    .count()
+            }
+            JobSchedule::Every(every) => {
+                let elapsed = (until - since).to_std().unwrap_or_default();
+                let ticks = elapsed.as_nanos() / every.as_nanos();
+                ticks.min(limit as u128) as usize
+            }
+        }
+    }
+}
+
+impl fmt::Display for JobSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobSchedule::Cron { expr, timezone } => write!(f, "cron '{}' ({})", expr, timezone),
+            JobSchedule::Every(every) => {
+                write!(f, "every {}", humantime::format_duration(*every))
+            }
+        }
+    }
+}
+
+/// The most a scheduled start of `spec` is randomly delayed by.
+pub fn jitter(spec: &DaemonSpec) -> Result<Option<Duration>> {
+    spec.jitter
+        .as_deref()
+        .map(|jitter| parse_duration(&spec.name, "jitter", jitter))
+        .transpose()
+}
+
+/// Parse a cron expression the way the job scheduler does.
+fn parse_cron(expr: &str) -> std::result::Result<Cron, croner::errors::CronError> {
+    Cron::new(expr)
+        .with_seconds_required()
+        .with_dom_and_dow()
+        .parse()
+}
+
+fn parse_duration(name: &str, field: &str, value: &str) -> Result<Duration> {
+    match humantime::parse_duration(value) {
+        Ok(duration) if !duration.is_zero() => Ok(duration),
+        Ok(_) => Err(SyspulseError::Config(format!(
+            "Daemon '{}' needs a {} above 0",
+            name, field
+        ))),
+        Err(e) => Err(SyspulseError::Config(format!(
+            "Daemon '{}' has an invalid {} '{}': {}",
+            name, field, value, e
+        ))),
+    }
+}
+
+type JobFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The current job of each scheduled daemon. Shared with cron jobs, which
+/// replace themselves with the job for their next tick.
+type JobIds = Arc<Mutex<HashMap<String, Uuid>>>;
+
 pub struct Scheduler {
     scheduler: JobScheduler,
+    jobs: JobIds,
 }
 
 impl Scheduler {
@@ -12,39 +151,83 @@ impl Scheduler {
         let scheduler = JobScheduler::new()
             .await
             .map_err(|e| SyspulseError::Scheduler(e.to_string()))?;
-        Ok(Self { scheduler })
+        Ok(Self {
+            scheduler,
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+        })
     }
 
-    /// Schedule a daemon to be triggered on a cron expression.
-    /// The callback receives the daemon name and should start the daemon.
+    /// Schedule a daemon to be triggered on `schedule`, replacing its
+    /// previous schedule. The callback receives the daemon name and should
+    /// start the daemon.
     pub async fn schedule_daemon<F, Fut>(
         &mut self,
         name: &str,
-        cron_expr: &str,
+        schedule: &JobSchedule,
         callback: F,
     ) -> Result<()>
     where
         F: Fn(String) -> Fut + Send + Sync + Clone + 'static,
-        Fut: std::future::Future<Output = ()> + Send,
+        Fut: Future<Output = ()> + Send,
     {
-        let daemon_name = name.to_string();
-        let cb = callback.clone();
-        let job = Job::new_async(cron_expr, move |_uuid, _lock| {
-            let name = daemon_name.clone();
-            let cb = cb.clone();
-            Box::pin(async move {
-                info!("Cron trigger firing for daemon '{}'", name);
-                cb(name).await;
-            })
-        })
-        .map_err(|e| SyspulseError::Scheduler(e.to_string()))?;
+        let job = match schedule {
+            JobSchedule::Cron { .. } => cron_job(
+                name.to_string(),
+                schedule.clone(),
+                Utc::now(),
+                callback,
+                Arc::clone(&self.jobs),
+            )?,
+            JobSchedule::Every(every) => {
+                let daemon_name = name.to_string();
+                Job::new_repeated_async(*every, move |_id, _scheduler| {
+                    fire(daemon_name.clone(), callback.clone())
+                })
+                .map_err(|e| SyspulseError::Scheduler(e.to_string()))?
+            }
+        };
+
+        self.unschedule_daemon(name).await?;
+        self.jobs
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), job.guid());
+        if let Err(e) = self.scheduler.add(job).await {
+            self.jobs.lock().unwrap().remove(name);
+            return Err(SyspulseError::Scheduler(e.to_string()));
+        }
+        info!("Scheduled daemon '{}' with {}", name, schedule);
+        Ok(())
+    }
 
+    /// Remove a daemon's schedule. Returns whether it had one.
+    pub async fn unschedule_daemon(&mut self, name: &str) -> Result<bool> {
+        let Some(id) = self.jobs.lock().unwrap().remove(name) else {
+            return Ok(false);
+        };
         self.scheduler
-            .add(job)
+            .remove(&id)
             .await
             .map_err(|e| SyspulseError::Scheduler(e.to_string()))?;
-        info!("Scheduled daemon '{}' with cron '{}'", name, cron_expr);
-        Ok(())
+        info!("Unscheduled daemon '{}'", name);
+        Ok(true)
+    }
+
+    /// Whether a daemon has a schedule.
+    pub fn is_scheduled(&self, name: &str) -> bool {
+        self.jobs.lock().unwrap().contains_key(name)
+    }
+
+    /// When a daemon's schedule fires next. `None` if it has no schedule,
+    /// or before the scheduler has started.
+    pub async fn next_run(&mut self, name: &str) -> Result<Option<DateTime<Utc>>> {
+        let Some(id) = self.jobs.lock().unwrap().get(name).copied() else {
+            return Ok(None);
+        };
+        self.scheduler
+            .next_tick_for_job(id)
+            .await
+            .map_err(|e| SyspulseError::Scheduler(e.to_string()))
     }
 
     /// Start the scheduler so jobs begin firing.
@@ -67,3 +250,249 @@ impl Scheduler {
         Ok(())
     }
 }
+
+fn fire<F, Fut>(name: String, callback: F) -> JobFuture
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    Box::pin(async move {
+        info!("Cron trigger firing for daemon '{}'", name);
+        callback(name).await;
+    })
+}
+
+/// A one-shot job firing at the first tick of the cron `schedule` after
+/// `after`, which adds the job for the following tick when it fires. Unlike
+/// a repeating cron job, whose UTC offset is fixed when it is added, each
+/// tick is computed in the schedule's time zone, so daylight saving changes
+/// are followed.
+fn cron_job<F, Fut>(
+    name: String,
+    schedule: JobSchedule,
+    after: DateTime<Utc>,
+    callback: F,
+    jobs: JobIds,
+) -> Result<Job>
+where
+    F: Fn(String) -> Fut + Send + Sync + Clone + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let tick = schedule.next_after(after).ok_or_else(|| {
+        SyspulseError::Scheduler(format!("{} never fires after {}", schedule, after))
+    })?;
+    // The job scheduler keeps ticks in whole seconds, as cron ticks are.
+    let delay = (tick.timestamp() - Utc::now().timestamp()).max(0) as u64;
+    Job::new_one_shot_async(Duration::from_secs(delay), move |id, scheduler| {
+        let (name, schedule, callback, jobs) = (
+            name.clone(),
+            schedule.clone(),
+            callback.clone(),
+            Arc::clone(&jobs),
+        );
+        Box::pin(async move {
+            // Unscheduled or replaced since it was added.
+            if jobs.lock().unwrap().get(&name) != Some(&id) {
+                return;
+            }
+            match cron_job(
+                name.clone(),
+                schedule,
+                tick.max(Utc::now()),
+                callback.clone(),
+                Arc::clone(&jobs),
+            ) {
+                Ok(next) => {
+                    let next_id = next.guid();
+                    let current = {
+                        let mut jobs = jobs.lock().unwrap();
+                        let current = jobs.get(&name) == Some(&id);
+                        if current {
+                            jobs.insert(name.clone(), next_id);
+                        }
+                        current
+                    };
+                    if current {
+                        if let Err(e) = scheduler.add(next).await {
+                            warn!("Failed to schedule the next run of '{}': {}", name, e);
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to schedule the next run of '{}': {}", name, e),
+            }
+            fire(name, callback).await;
+        }) as JobFuture
+    })
+    .map_err(|e| SyspulseError::Scheduler(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn cron(expr: &str) -> JobSchedule {
+        JobSchedule::Cron {
+            expr: expr.to_string(),
+            timezone: Tz::UTC,
+        }
+    }
+
+    fn spec(json: &str) -> DaemonSpec {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn add_replace_and_remove_jobs() {
+        let mut scheduler = Scheduler::new().await.unwrap();
+        let noop = |_name: String| async {};
+
+        scheduler
+            .schedule_daemon("backup", &cron("0 0 * * * *"), noop)
+            .await
+            .unwrap();
+        let first = scheduler.jobs.lock().unwrap()["backup"];
+        scheduler
+            .schedule_daemon("backup", &cron("0 30 * * * *"), noop)
+            .await
+            .unwrap();
+        assert_ne!(scheduler.jobs.lock().unwrap()["backup"], first);
+        assert_eq!(scheduler.jobs.lock().unwrap().len(), 1);
+        scheduler
+            .schedule_daemon("poll", &JobSchedule::Every(Duration::from_secs(60)), noop)
+            .await
+            .unwrap();
+
+        assert!(scheduler
+            .schedule_daemon("broken", &cron("not a cron"), noop)
+            .await
+            .is_err());
+        assert!(!scheduler.is_scheduled("broken"));
+
+        scheduler.start().await.unwrap();
+        let next = scheduler.next_run("backup").await.unwrap().unwrap();
+        assert!(next > Utc::now() && next <= Utc::now() + chrono::Duration::hours(1));
+        assert!(scheduler.next_run("poll").await.unwrap().is_some());
+        assert_eq!(scheduler.next_run("broken").await.unwrap(), None);
+
+        assert!(scheduler.unschedule_daemon("backup").await.unwrap());
+        assert!(!scheduler.is_scheduled("backup"));
+        assert!(!scheduler.unschedule_daemon("backup").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn cron_jobs_schedule_their_next_tick() {
+        let mut scheduler = Scheduler::new().await.unwrap();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fired);
+        let count = move |_name: String| {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+        scheduler
+            .schedule_daemon("tick", &cron("* * * * * *"), count)
+            .await
+            .unwrap();
+        let first = scheduler.jobs.lock().unwrap()["tick"];
+        scheduler.start().await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(3500)).await;
+        assert!(fired.load(Ordering::SeqCst) >= 2);
+        assert_ne!(scheduler.jobs.lock().unwrap()["tick"], first);
+
+        assert!(scheduler.unschedule_daemon("tick").await.unwrap());
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let unscheduled = fired.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(2000)).await;
+        assert_eq!(fired.load(Ordering::SeqCst), unscheduled);
+        scheduler.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn next_tick_follows_daylight_saving() {
+        let berlin = JobSchedule::Cron {
+            expr: "0 0 3 * * *".to_string(),
+            timezone: chrono_tz::Europe::Berlin,
+        };
+        // 03:00 in Berlin is 02:00 UTC in winter and 01:00 UTC in summer.
+        let before = Utc.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap();
+        let winter = berlin.next_after(before).unwrap();
+        assert_eq!(winter, Utc.with_ymd_and_hms(2024, 3, 31, 1, 0, 0).unwrap());
+        let summer = berlin.next_after(winter).unwrap();
+        assert_eq!(summer, Utc.with_ymd_and_hms(2024, 4, 1, 1, 0, 0).unwrap());
+        let autumn = Utc.with_ymd_and_hms(2024, 10, 27, 12, 0, 0).unwrap();
+        assert_eq!(
+            berlin.next_after(autumn).unwrap(),
+            Utc.with_ymd_and_hms(2024, 10, 28, 2, 0, 0).unwrap()
+        );
+
+        let hourly = JobSchedule::Every(Duration::from_secs(3600));
+        assert_eq!(
+            hourly.next_after(before).unwrap(),
+            before + chrono::Duration::hours(1)
+        );
+    }
+
+    #[test]
+    fn schedule_from_spec() {
+        let berlin = spec(
+            r#"{"name": "backup", "command": ["backup"], "schedule": "0 0 3 * * *",
+                "schedule_timezone": "Europe/Berlin"}"#,
+        );
+        assert_eq!(
+            JobSchedule::from_spec(&berlin).unwrap(),
+            Some(JobSchedule::Cron {
+                expr: "0 0 3 * * *".to_string(),
+                timezone: chrono_tz::Europe::Berlin,
+            })
+        );
+
+        let poll =
+            spec(r#"{"name": "poll", "command": ["poll"], "every": "15m", "jitter": "30s"}"#);
+        assert_eq!(
+            JobSchedule::from_spec(&poll).unwrap(),
+            Some(JobSchedule::Every(Duration::from_secs(900)))
+        );
+        assert_eq!(jitter(&poll).unwrap(), Some(Duration::from_secs(30)));
+
+        let plain = spec(r#"{"name": "web", "command": ["web"]}"#);
+        assert_eq!(JobSchedule::from_spec(&plain).unwrap(), None);
+
+        for bad in [
+            r#""schedule": "0 0 * * *""#,
+            r#""schedule": "0 0 3 * * *", "schedule_timezone": "Mars/Olympus""#,
+            r#""every": "0s""#,
+            r#""every": "soon""#,
+        ] {
+            let bad = spec(&format!(r#"{{"name": "x", "command": ["x"], {}}}"#, bad));
+            assert!(JobSchedule::from_spec(&bad).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn count_missed_ticks() {
+        let since = Utc.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 4, 2, 12, 0, 0).unwrap();
+
+        let daily = cron("0 0 3 * * *");
+        assert_eq!(daily.ticks_between(since, until, 100), 3);
+        assert_eq!(daily.ticks_between(since, until, 1), 1);
+        assert_eq!(daily.ticks_between(until, since, 100), 0);
+
+        // Berlin switches to summer time on March 31, so 03:00 there is
+        // 01:00 UTC from then on.
+        let berlin = JobSchedule::Cron {
+            expr: "0 0 3 * * *".to_string(),
+            timezone: chrono_tz::Europe::Berlin,
+        };
+        let after_switch = Utc.with_ymd_and_hms(2024, 4, 1, 1, 30, 0).unwrap();
+        assert_eq!(berlin.ticks_between(since, after_switch, 100), 2);
+
+        let hourly = JobSchedule::Every(Duration::from_secs(3600));
+        assert_eq!(hourly.ticks_between(since, until, 100), 72);
+        assert_eq!(hourly.ticks_between(since, until, 10), 10);
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::daemon::DaemonSpec;
+use crate::error::{Result, SyspulseError};
+
+/// First file descriptor passed sockets occupy in the daemon, as in systemd's
+/// `SD_LISTEN_FDS_START`.
+pub const LISTEN_FDS_START: i32 = 3;
+
+/// A socket the manager listens on for a daemon and passes to it on spawn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocketSpec {
+    /// `host:port` for TCP, or an absolute path for a Unix socket.
+    pub listen: String,
+    /// Name reported in `LISTEN_FDNAMES`. Defaults to the daemon's name.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl SocketSpec {
+    fn unix_path(&self) -> Option<&Path> {
+        let path = Path::new(&self.listen);
+        path.is_absolute().then_some(path)
+    }
+}
+
+/// A listening socket held open by the manager, so that connections queue up
+/// while the daemon is restarting or not started yet.
+#[derive(Debug)]
+pub struct BoundSocket {
+    pub name: String,
+    listener: Listener,
+}
+
+#[derive(Debug)]
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: std::os::unix::net::UnixListener,
+        path: std::path::PathBuf,
+    },
+}
+
+impl BoundSocket {
+    /// Bind `spec` for the daemon `daemon_name`.
+    pub fn bind(spec: &SocketSpec, daemon_name: &str) -> Result<Self> {
+        let name = spec.name.clone().unwrap_or_else(|| daemon_name.to_string());
+        let listener = match spec.unix_path() {
+            Some(path) => bind_unix(path)?,
+            None => TcpListener::bind(&spec.listen)
+                .map(Listener::Tcp)
+                .map_err(|e| {
+                    SyspulseError::Process(format!(
+                        "Failed to listen on '{}' for daemon '{}': {}",
+                        spec.listen, daemon_name, e
+                    ))
+                })?,
+        };
+        Ok(Self { name, listener })
+    }
+
+    /// Whether a connection is waiting to be accepted. The connection is
+    /// left in the backlog for the daemon.
+    #[cfg(unix)]
+    pub fn has_pending_connection(&self) -> bool {
+        use std::os::fd::AsRawFd;
+
+        let mut pollfd = libc::pollfd {
+            fd: self.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready = unsafe { libc::poll(&mut pollfd, 1, 0) };
+        ready > 0 && pollfd.revents & libc::POLLIN != 0
+    }
+
+    #[cfg(not(unix))]
+    pub fn has_pending_connection(&self) -> bool {
+        false
+    }
+
+    /// Remove the socket file of a Unix socket. The descriptor itself stays
+    /// open until the last holder drops it.
+    fn remove_file(&self) {
+        #[cfg(unix)]
+        if let Listener::Unix { path, .. } = &self.listener {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::AsRawFd for BoundSocket {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.as_raw_fd(),
+            Listener::Unix { listener, .. } => listener.as_raw_fd(),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn bind_unix(path: &Path) -> Result<Listener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    // A socket file left behind by an earlier manager would make bind fail.
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if metadata.file_type().is_socket() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    let listener = std::os::unix::net::UnixListener::bind(path).map_err(|e| {
+        SyspulseError::Process(format!("Failed to listen on '{}': {}", path.display(), e))
+    })?;
+    Ok(Listener::Unix {
+        listener,
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(not(unix))]
+fn bind_unix(path: &Path) -> Result<Listener> {
+    Err(SyspulseError::Config(format!(
+        "Unix socket '{}' is only supported on Unix",
+        path.display()
+    )))
+}
+
+/// The sockets bound for each daemon.
+#[derive(Default)]
+pub struct SocketTable {
+    daemons: HashMap<String, BoundDaemon>,
+}
+
+struct BoundDaemon {
+    specs: Vec<SocketSpec>,
+    lazy_start: bool,
+    sockets: Arc<Vec<BoundSocket>>,
+}
+
+impl SocketTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make the sockets bound for `spec`'s daemon match its `sockets`.
+    /// Sockets that are already bound as specified are kept open.
+    pub fn bind(&mut self, spec: &DaemonSpec) -> Result<()> {
+        if spec.sockets.is_empty() {
+            self.unbind(&spec.name);
+            return Ok(());
+        }
+        if let Some(bound) = self.daemons.get_mut(&spec.name) {
+            if bound.specs == spec.sockets {
+                bound.lazy_start = spec.lazy_start;
+                return Ok(());
+            }
+        }
+
+        // Release the old sockets first: the new ones may reuse an address.
+        self.unbind(&spec.name);
+        let sockets = spec
+            .sockets
+            .iter()
+            .map(|s| BoundSocket::bind(s, &spec.name))
+            .collect::<Result<Vec<_>>>()?;
+        self.daemons.insert(
+            spec.name.clone(),
+            BoundDaemon {
+                specs: spec.sockets.clone(),
+                lazy_start: spec.lazy_start,
+                sockets: Arc::new(sockets),
+            },
+        );
+        Ok(())
+    }
+
+    /// Close the sockets of a daemon.
+    pub fn unbind(&mut self, name: &str) {
+        if let Some(bound) = self.daemons.remove(name) {
+            for socket in bound.sockets.iter() {
+                socket.remove_file();
+            }
+        }
+    }
+
+    /// The sockets to pass to a process of the daemon `name`, if bound.
+    pub fn get(&self, name: &str) -> Option<Arc<Vec<BoundSocket>>> {
+        self.daemons.get(name).map(|b| Arc::clone(&b.sockets))
+    }
+
+    /// Lazily started daemons with a connection waiting on one of their
+    /// sockets.
+    pub fn pending(&self) -> Vec<String> {
+        self.daemons
+            .iter()
+            .filter(|(_, b)| b.lazy_start && b.sockets.iter().any(|s| s.has_pending_connection()))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(toml: &str) -> DaemonSpec {
+        crate::config::parse_config(toml).unwrap().remove(0)
+    }
+
+    #[test]
+    fn tcp_socket_reports_pending_connections() {
+        let mut table = SocketTable::new();
+        table
+            .bind(&spec(
+                "[daemon]\nname = 'web'\ncommand = ['web']\nlazy_start = true\n\
+                 [[daemon.sockets]]\nlisten = '127.0.0.1:0'\n",
+            ))
+            .unwrap();
+        let sockets = table.get("web").unwrap();
+        assert_eq!(sockets[0].name, "web");
+        assert!(table.pending().is_empty());
+
+        let Listener::Tcp(ref listener) = sockets[0].listener else {
+            panic!("expected a TCP socket");
+        };
+        let _client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        assert_eq!(table.pending(), vec!["web"]);
+
+        // Still queued: the manager never accepts.
+        assert_eq!(table.pending(), vec!["web"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_is_rebound_and_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("web.sock");
+        let config = format!(
+            "[daemon]\nname = 'web'\ncommand = ['web']\n\
+             [[daemon.sockets]]\nname = 'http'\nlisten = '{}'\n",
+            path.display()
+        );
+
+        // A stale socket file is replaced.
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        let mut table = SocketTable::new();
+        table.bind(&spec(&config)).unwrap();
+        assert_eq!(table.get("web").unwrap()[0].name, "http");
+        std::os::unix::net::UnixStream::connect(&path).unwrap();
+
+        table.unbind("web");
+        assert!(!path.exists());
+        assert!(table.get("web").is_none());
+    }
+
+    #[test]
+    fn unchanged_sockets_stay_bound() {
+        let mut table = SocketTable::new();
+        let mut web = spec(
+            "[daemon]\nname = 'web'\ncommand = ['web']\n\
+             [[daemon.sockets]]\nlisten = '127.0.0.1:0'\n",
+        );
+        table.bind(&web).unwrap();
+        let before = table.get("web").unwrap();
+        table.bind(&web).unwrap();
+        assert!(Arc::ptr_eq(&before, &table.get("web").unwrap()));
+
+        web.sockets.clear();
+        table.bind(&web).unwrap();
+        assert!(table.get("web").is_none());
+    }
+}
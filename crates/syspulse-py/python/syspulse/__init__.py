@@ -6,6 +6,9 @@ from syspulse._syspulse import (
     Daemon,
     DaemonInstance,
     SyspulseClient,
+    LogStream,
+    DaemonEvent,
+    EventStream,
     # Config classes
     HealthCheck,
     ResourceLimits,
@@ -14,7 +17,10 @@ from syspulse._syspulse import (
     DaemonStatus,
     HealthStatus,
     HealthCheckType,
+    UnhealthyAction,
     RestartPolicyType,
+    LimitMode,
+    LimitAction,
     # Exceptions
     SyspulseError,
     DaemonNotFoundError,
@@ -27,7 +33,11 @@ from syspulse._syspulse import (
     socket_path,
 )
 
-from syspulse.async_client import AsyncSyspulseClient
+from syspulse.async_client import (
+    AsyncEventStream,
+    AsyncLogStream,
+    AsyncSyspulseClient,
+)
 
 __all__ = [
     # Version
@@ -37,6 +47,11 @@ __all__ = [
     "DaemonInstance",
     "SyspulseClient",
     "AsyncSyspulseClient",
+    "LogStream",
+    "AsyncLogStream",
+    "DaemonEvent",
+    "EventStream",
+    "AsyncEventStream",
     # Config classes
     "HealthCheck",
     "ResourceLimits",
@@ -45,7 +60,10 @@ __all__ = [
     "DaemonStatus",
     "HealthStatus",
     "HealthCheckType",
+    "UnhealthyAction",
     "RestartPolicyType",
+    "LimitMode",
+    "LimitAction",
     # Exceptions
     "SyspulseError",
     "DaemonNotFoundError",
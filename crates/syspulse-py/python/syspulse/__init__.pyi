@@ -2,7 +2,7 @@
 
 from enum import IntEnum
 from types import TracebackType
-from typing import Dict, List, Optional, Type
+from typing import Dict, Iterator, List, Literal, Optional, Type, overload
 
 __version__: str
 
@@ -17,6 +17,7 @@ class DaemonStatus(IntEnum):
     Stopping = 3
     Failed = 4
     Scheduled = 5
+    CrashLooping = 6
 
 class HealthStatus(IntEnum):
     Unknown = 0
@@ -29,11 +30,25 @@ class HealthCheckType(IntEnum):
     Tcp = 1
     Command = 2
 
+class UnhealthyAction(IntEnum):
+    Ignore = 0
+    Restart = 1
+    Stop = 2
+
 class RestartPolicyType(IntEnum):
     Always = 0
     OnFailure = 1
     Never = 2
 
+class LimitMode(IntEnum):
+    Hard = 0
+    Soft = 1
+
+class LimitAction(IntEnum):
+    Warn = 0
+    Restart = 1
+    Stop = 2
+
 # ---------------------------------------------------------------------------
 # Config classes
 # ---------------------------------------------------------------------------
@@ -48,6 +63,7 @@ class HealthCheck:
         timeout: int = 5,
         retries: int = 3,
         start_period: int = 0,
+        on_unhealthy: UnhealthyAction = UnhealthyAction.Ignore,
     ) -> None: ...
     @property
     def check_type(self) -> HealthCheckType: ...
@@ -61,6 +77,8 @@ class HealthCheck:
     def retries(self) -> int: ...
     @property
     def start_period(self) -> int: ...
+    @property
+    def on_unhealthy(self) -> UnhealthyAction: ...
     def __repr__(self) -> str: ...
 
 class ResourceLimits:
@@ -70,6 +88,9 @@ class ResourceLimits:
         max_memory_bytes: Optional[int] = None,
         max_cpu_percent: Optional[float] = None,
         max_open_files: Optional[int] = None,
+        mode: LimitMode = LimitMode.Hard,
+        grace_period: int = 30,
+        action: LimitAction = LimitAction.Restart,
     ) -> None: ...
     @property
     def max_memory_bytes(self) -> Optional[int]: ...
@@ -77,6 +98,12 @@ class ResourceLimits:
     def max_cpu_percent(self) -> Optional[float]: ...
     @property
     def max_open_files(self) -> Optional[int]: ...
+    @property
+    def mode(self) -> LimitMode: ...
+    @property
+    def grace_period(self) -> int: ...
+    @property
+    def action(self) -> LimitAction: ...
     def __repr__(self) -> str: ...
 
 class LogConfig:
@@ -112,9 +139,12 @@ class Daemon:
         stop_timeout: int = 30,
         description: Optional[str] = None,
         user: Optional[str] = None,
+        group: Optional[str] = None,
+        depends_on: Optional[List[str]] = None,
         health_check: Optional[HealthCheck] = None,
         resource_limits: Optional[ResourceLimits] = None,
         log_config: Optional[LogConfig] = None,
+        replicas: int = 1,
     ) -> None: ...
     @property
     def name(self) -> str: ...
@@ -131,10 +161,16 @@ class Daemon:
     @property
     def stop_timeout(self) -> int: ...
     @property
+    def replicas(self) -> int: ...
+    @property
     def description(self) -> Optional[str]: ...
     @property
     def user(self) -> Optional[str]: ...
     @property
+    def group(self) -> Optional[str]: ...
+    @property
+    def depends_on(self) -> List[str]: ...
+    @property
     def restart_policy(self) -> RestartPolicyType: ...
     @property
     def health_check(self) -> Optional[HealthCheck]: ...
@@ -160,6 +196,15 @@ class Daemon:
         max_retries: Optional[int] = None,
         backoff_base: Optional[float] = None,
         backoff_max: Optional[float] = None,
+        stable_after: Optional[int] = None,
+        burst_limit: Optional[int] = None,
+        burst_window: Optional[int] = None,
+    ) -> Daemon: ...
+    def with_dependency(
+        self,
+        name: str,
+        *,
+        healthy: Optional[bool] = None,
     ) -> Daemon: ...
     def with_resource_limits(self, limits: ResourceLimits) -> Daemon: ...
     def with_log_config(self, config: LogConfig) -> Daemon: ...
@@ -182,8 +227,12 @@ class DaemonInstance:
     @property
     def stopped_at(self) -> Optional[str]: ...
     @property
+    def next_run_at(self) -> Optional[str]: ...
+    @property
     def exit_code(self) -> Optional[int]: ...
     @property
+    def exit_signal(self) -> Optional[int]: ...
+    @property
     def restart_count(self) -> int: ...
     @property
     def health(self) -> HealthStatus: ...
@@ -191,8 +240,103 @@ class DaemonInstance:
     def stdout_log(self) -> Optional[str]: ...
     @property
     def stderr_log(self) -> Optional[str]: ...
+    @property
+    def warnings(self) -> List[str]: ...
+    @property
+    def reason(self) -> Optional[str]: ...
+    @property
+    def status_message(self) -> Optional[str]: ...
+    def __repr__(self) -> str: ...
+
+# ---------------------------------------------------------------------------
+# Log streams
+# ---------------------------------------------------------------------------
+
+class LogStream(Iterator[str]):
+    def __iter__(self) -> LogStream: ...
+    def __next__(self) -> str: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> LogStream: ...
+    def __exit__(
+        self,
+        exc_type: Optional[Type[BaseException]],
+        exc_val: Optional[BaseException],
+        exc_tb: Optional[TracebackType],
+    ) -> bool: ...
+
+class AsyncLogStream:
+    def __init__(self, stream: LogStream) -> None: ...
+    def __aiter__(self) -> AsyncLogStream: ...
+    async def __anext__(self) -> str: ...
+    async def aclose(self) -> None: ...
+    async def __aenter__(self) -> AsyncLogStream: ...
+    async def __aexit__(
+        self,
+        exc_type: Optional[Type[BaseException]],
+        exc_val: Optional[BaseException],
+        exc_tb: Optional[TracebackType],
+    ) -> bool: ...
+
+# ---------------------------------------------------------------------------
+# Events
+# ---------------------------------------------------------------------------
+
+class DaemonEvent:
+    @property
+    def id(self) -> int: ...
+    @property
+    def timestamp(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def kind(self) -> str: ...
+    @property
+    def message(self) -> Optional[str]: ...
+    @property
+    def pid(self) -> Optional[int]: ...
+    @property
+    def exit_code(self) -> Optional[int]: ...
+    @property
+    def signal(self) -> Optional[int]: ...
+    @property
+    def attempt(self) -> Optional[int]: ...
+    @property
+    def backoff_ms(self) -> Optional[int]: ...
+    @property
+    def health(self) -> Optional[HealthStatus]: ...
+    @property
+    def cpu_percent(self) -> Optional[float]: ...
+    @property
+    def memory_bytes(self) -> Optional[int]: ...
+    @property
+    def process_count(self) -> Optional[int]: ...
     def __repr__(self) -> str: ...
 
+class EventStream(Iterator[DaemonEvent]):
+    def __iter__(self) -> EventStream: ...
+    def __next__(self) -> DaemonEvent: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> EventStream: ...
+    def __exit__(
+        self,
+        exc_type: Optional[Type[BaseException]],
+        exc_val: Optional[BaseException],
+        exc_tb: Optional[TracebackType],
+    ) -> bool: ...
+
+class AsyncEventStream:
+    def __init__(self, stream: EventStream) -> None: ...
+    def __aiter__(self) -> AsyncEventStream: ...
+    async def __anext__(self) -> DaemonEvent: ...
+    async def aclose(self) -> None: ...
+    async def __aenter__(self) -> AsyncEventStream: ...
+    async def __aexit__(
+        self,
+        exc_type: Optional[Type[BaseException]],
+        exc_val: Optional[BaseException],
+        exc_tb: Optional[TracebackType],
+    ) -> bool: ...
+
 # ---------------------------------------------------------------------------
 # Sync client
 # ---------------------------------------------------------------------------
@@ -212,6 +356,7 @@ class SyspulseClient:
         *,
         wait: Optional[bool] = None,
         timeout: Optional[int] = None,
+        with_deps: Optional[bool] = None,
     ) -> str: ...
     def stop(
         self,
@@ -226,16 +371,37 @@ class SyspulseClient:
         *,
         force: Optional[bool] = None,
         wait: Optional[bool] = None,
+        timeout: Optional[int] = None,
+        rolling: Optional[bool] = None,
     ) -> str: ...
+    def trigger(self, name: str) -> str: ...
+    def reset_failed(self, name: str) -> str: ...
     def status(self, name: str) -> DaemonInstance: ...
     def list(self) -> List[DaemonInstance]: ...
+    @overload
     def logs(
         self,
         name: str,
         *,
         lines: Optional[int] = None,
         stderr: Optional[bool] = None,
+        follow: Literal[False, None] = None,
     ) -> List[str]: ...
+    @overload
+    def logs(
+        self,
+        name: str,
+        *,
+        lines: Optional[int] = None,
+        stderr: Optional[bool] = None,
+        follow: Literal[True],
+    ) -> LogStream: ...
+    def subscribe(
+        self,
+        names: Optional[List[str]] = None,
+        *,
+        kinds: Optional[List[str]] = None,
+    ) -> EventStream: ...
     def add(self, daemon: Daemon) -> str: ...
     def remove(
         self,
@@ -266,6 +432,7 @@ class AsyncSyspulseClient:
         *,
         wait: Optional[bool] = None,
         timeout: Optional[int] = None,
+        with_deps: Optional[bool] = None,
     ) -> str: ...
     async def stop(
         self,
@@ -280,16 +447,37 @@ class AsyncSyspulseClient:
         *,
         force: Optional[bool] = None,
         wait: Optional[bool] = None,
+        timeout: Optional[int] = None,
+        rolling: Optional[bool] = None,
     ) -> str: ...
+    async def trigger(self, name: str) -> str: ...
+    async def reset_failed(self, name: str) -> str: ...
     async def status(self, name: str) -> DaemonInstance: ...
     async def list(self) -> List[DaemonInstance]: ...
+    @overload
     async def logs(
         self,
         name: str,
         *,
         lines: Optional[int] = None,
         stderr: Optional[bool] = None,
+        follow: Literal[False, None] = None,
     ) -> List[str]: ...
+    @overload
+    async def logs(
+        self,
+        name: str,
+        *,
+        lines: Optional[int] = None,
+        stderr: Optional[bool] = None,
+        follow: Literal[True],
+    ) -> AsyncLogStream: ...
+    async def subscribe(
+        self,
+        names: Optional[List[str]] = None,
+        *,
+        kinds: Optional[List[str]] = None,
+    ) -> AsyncEventStream: ...
     async def add(self, daemon: Daemon) -> str: ...
     async def remove(
         self,
@@ -8,12 +8,101 @@ event loop free.
 from __future__ import annotations
 
 import asyncio
-from typing import TYPE_CHECKING, List, Optional
+from typing import (
+    TYPE_CHECKING,
+    Generic,
+    Iterator,
+    List,
+    Optional,
+    TypeVar,
+    Union,
+)
 
 from syspulse._syspulse import SyspulseClient
 
 if TYPE_CHECKING:
-    from syspulse._syspulse import Daemon, DaemonInstance
+    from syspulse._syspulse import (
+        Daemon,
+        DaemonEvent,
+        DaemonInstance,
+        EventStream,
+        LogStream,
+    )
+
+_END = object()
+
+T = TypeVar("T")
+S = TypeVar("S", bound="_AsyncStream")
+
+
+class _AsyncStream(Generic[T]):
+    """Async iterator over a blocking stream, running each wait for the next
+    item in a thread-pool worker.
+
+    Closing is safe while a cancelled wait is still running in its worker;
+    the worker notices the close and returns.
+    """
+
+    def __init__(self, stream: "Iterator[T]") -> None:
+        self._stream = stream
+
+    def __aiter__(self: S) -> S:
+        return self
+
+    async def __anext__(self) -> T:
+        item = await asyncio.to_thread(next, self._stream, _END)
+        if item is _END:
+            raise StopAsyncIteration
+        return item
+
+    async def aclose(self) -> None:
+        self._stream.close()
+
+    async def __aenter__(self: S) -> S:
+        return self
+
+    async def __aexit__(
+        self,
+        exc_type: object,
+        exc_val: object,
+        exc_tb: object,
+    ) -> bool:
+        self._stream.close()
+        return False
+
+
+class AsyncLogStream(_AsyncStream[str]):
+    """Async iterator over a followed daemon log.
+
+    Wraps the blocking :class:`LogStream` so each wait for new output runs
+    in a thread-pool worker.
+
+    Example::
+
+        async with await client.logs("web", follow=True) as stream:
+            async for line in stream:
+                print(line)
+    """
+
+    def __init__(self, stream: "LogStream") -> None:
+        super().__init__(stream)
+
+
+class AsyncEventStream(_AsyncStream["DaemonEvent"]):
+    """Async iterator over events pushed by the manager.
+
+    Wraps the blocking :class:`EventStream` so each wait for the next event
+    runs in a thread-pool worker.
+
+    Example::
+
+        async with await client.subscribe(["web"], kinds=["exited"]) as events:
+            async for event in events:
+                print(event.name, event.kind, event.exit_code)
+    """
+
+    def __init__(self, stream: "EventStream") -> None:
+        super().__init__(stream)
 
 
 class AsyncSyspulseClient:
@@ -53,9 +142,14 @@ class AsyncSyspulseClient:
         *,
         wait: Optional[bool] = None,
         timeout: Optional[int] = None,
+        with_deps: Optional[bool] = None,
     ) -> str:
         return await asyncio.to_thread(
-            self._client.start, name, wait=wait, timeout=timeout
+            self._client.start,
+            name,
+            wait=wait,
+            timeout=timeout,
+            with_deps=with_deps,
         )
 
     async def stop(
@@ -75,11 +169,24 @@ class AsyncSyspulseClient:
         *,
         force: Optional[bool] = None,
         wait: Optional[bool] = None,
+        timeout: Optional[int] = None,
+        rolling: Optional[bool] = None,
     ) -> str:
         return await asyncio.to_thread(
-            self._client.restart, name, force=force, wait=wait
+            self._client.restart,
+            name,
+            force=force,
+            wait=wait,
+            timeout=timeout,
+            rolling=rolling,
         )
 
+    async def trigger(self, name: str) -> str:
+        return await asyncio.to_thread(self._client.trigger, name)
+
+    async def reset_failed(self, name: str) -> str:
+        return await asyncio.to_thread(self._client.reset_failed, name)
+
     # -- queries --------------------------------------------------------------
 
     async def status(self, name: str) -> "DaemonInstance":
@@ -94,10 +201,25 @@ class AsyncSyspulseClient:
         *,
         lines: Optional[int] = None,
         stderr: Optional[bool] = None,
-    ) -> List[str]:
-        return await asyncio.to_thread(
-            self._client.logs, name, lines=lines, stderr=stderr
+        follow: Optional[bool] = None,
+    ) -> Union[List[str], AsyncLogStream]:
+        result = await asyncio.to_thread(
+            self._client.logs, name, lines=lines, stderr=stderr, follow=follow
+        )
+        if follow:
+            return AsyncLogStream(result)
+        return result
+
+    async def subscribe(
+        self,
+        names: Optional[List[str]] = None,
+        *,
+        kinds: Optional[List[str]] = None,
+    ) -> AsyncEventStream:
+        stream = await asyncio.to_thread(
+            self._client.subscribe, names, kinds=kinds
         )
+        return AsyncEventStream(stream)
 
     # -- management -----------------------------------------------------------
 
@@ -2,6 +2,8 @@ use pyo3::prelude::*;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use syspulse_core::daemon::{CatchUp, Dependency, DependencyCondition, ReadyMode, ScheduleOverlap};
+
 use crate::health::HealthCheck;
 use crate::log_config::LogConfig;
 use crate::resources::ResourceLimits;
@@ -16,7 +18,7 @@ pub struct Daemon {
 #[pymethods]
 impl Daemon {
     #[new]
-    #[pyo3(signature = (name, command, *, working_dir=None, env=None, schedule=None, tags=None, stop_timeout=30, description=None, user=None, health_check=None, resource_limits=None, log_config=None))]
+    #[pyo3(signature = (name, command, *, working_dir=None, env=None, schedule=None, tags=None, stop_timeout=30, description=None, user=None, group=None, depends_on=None, health_check=None, resource_limits=None, log_config=None, replicas=1))]
     fn new(
         name: String,
         command: Vec<String>,
@@ -27,9 +29,12 @@ impl Daemon {
         stop_timeout: u64,
         description: Option<String>,
         user: Option<String>,
+        group: Option<String>,
+        depends_on: Option<Vec<String>>,
         health_check: Option<HealthCheck>,
         resource_limits: Option<ResourceLimits>,
         log_config: Option<LogConfig>,
+        replicas: u32,
     ) -> Self {
         Daemon {
             inner: syspulse_core::daemon::DaemonSpec {
@@ -41,11 +46,34 @@ impl Daemon {
                 restart_policy: Default::default(),
                 resource_limits: resource_limits.map(|r| r.inner),
                 schedule,
+                schedule_timezone: None,
+                every: None,
+                jitter: None,
+                catch_up: CatchUp::None,
+                schedule_overlap: ScheduleOverlap::Skip,
+                max_runtime_secs: None,
                 tags: tags.unwrap_or_default(),
                 stop_timeout_secs: stop_timeout,
                 log_config: log_config.map(|l| l.inner),
                 description,
                 user,
+                group,
+                depends_on: depends_on
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|name| Dependency {
+                        name,
+                        condition: DependencyCondition::Started,
+                    })
+                    .collect(),
+                replicas,
+                sockets: Vec::new(),
+                lazy_start: false,
+                ready_mode: ReadyMode::Immediate,
+                start_timeout_secs: 90,
+                watchdog_secs: None,
+                heartbeat_file: None,
+                watchdog_signal: "SIGABRT".to_string(),
             },
         }
     }
@@ -88,6 +116,11 @@ impl Daemon {
         self.inner.stop_timeout_secs
     }
 
+    #[getter]
+    fn replicas(&self) -> u32 {
+        self.inner.replicas
+    }
+
     #[getter]
     fn description(&self) -> Option<String> {
         self.inner.description.clone()
@@ -98,6 +131,20 @@ impl Daemon {
         self.inner.user.clone()
     }
 
+    #[getter]
+    fn group(&self) -> Option<String> {
+        self.inner.group.clone()
+    }
+
+    #[getter]
+    fn depends_on(&self) -> Vec<String> {
+        self.inner
+            .depends_on
+            .iter()
+            .map(|d| d.name.clone())
+            .collect()
+    }
+
     #[getter]
     fn restart_policy(&self) -> RestartPolicyType {
         RestartPolicyType::from(&self.inner.restart_policy)
@@ -157,18 +204,23 @@ impl Daemon {
             timeout_secs: timeout.unwrap_or(5),
             retries: retries.unwrap_or(3),
             start_period_secs: start_period.unwrap_or(0),
+            on_unhealthy: Default::default(),
         });
         d
     }
 
     /// Builder: set a restart policy.
-    #[pyo3(signature = (policy, *, max_retries=None, backoff_base=None, backoff_max=None))]
+    #[pyo3(signature = (policy, *, max_retries=None, backoff_base=None, backoff_max=None, stable_after=None, burst_limit=None, burst_window=None))]
+    #[allow(clippy::too_many_arguments)]
     fn with_restart_policy(
         &self,
         policy: &str,
         max_retries: Option<u32>,
         backoff_base: Option<f64>,
         backoff_max: Option<f64>,
+        stable_after: Option<u64>,
+        burst_limit: Option<u32>,
+        burst_window: Option<u64>,
     ) -> Self {
         let mut d = self.clone();
         d.inner.restart_policy = match policy {
@@ -176,17 +228,38 @@ impl Daemon {
                 max_retries,
                 backoff_base_secs: backoff_base.unwrap_or(1.0),
                 backoff_max_secs: backoff_max.unwrap_or(300.0),
+                stable_after_secs: stable_after,
+                burst_limit,
+                burst_window_secs: burst_window.unwrap_or(60),
             },
             "on_failure" => syspulse_core::restart::RestartPolicy::OnFailure {
                 max_retries,
                 backoff_base_secs: backoff_base.unwrap_or(1.0),
                 backoff_max_secs: backoff_max.unwrap_or(300.0),
+                stable_after_secs: stable_after,
+                burst_limit,
+                burst_window_secs: burst_window.unwrap_or(60),
             },
             _ => syspulse_core::restart::RestartPolicy::Never,
         };
         d
     }
 
+    /// Builder: add a dependency, optionally waiting for it to be healthy.
+    #[pyo3(signature = (name, *, healthy=None))]
+    fn with_dependency(&self, name: &str, healthy: Option<bool>) -> Self {
+        let mut d = self.clone();
+        d.inner.depends_on.push(Dependency {
+            name: name.to_string(),
+            condition: if healthy.unwrap_or(false) {
+                DependencyCondition::Healthy
+            } else {
+                DependencyCondition::Started
+            },
+        });
+        d
+    }
+
     /// Builder: set resource limits.
     fn with_resource_limits(&self, limits: ResourceLimits) -> Self {
         let mut d = self.clone();
@@ -21,6 +21,9 @@ pub fn to_py_err(err: syspulse_core::error::SyspulseError) -> PyErr {
                 from, to
             ))
         }
+        err @ syspulse_core::error::SyspulseError::CrashLooping(_) => {
+            InvalidStateError::new_err(err.to_string())
+        }
         syspulse_core::error::SyspulseError::Ipc(msg) => {
             PyConnectionError::new_err(format!("IPC error: {}", msg))
         }
@@ -0,0 +1,131 @@
+use pyo3::prelude::*;
+
+use syspulse_core::events::EventKind;
+
+use crate::types::HealthStatus;
+
+/// A lifecycle, health or resource event pushed by the manager. Fields that
+/// don't apply to the event's ``kind`` are ``None``.
+#[pyclass(frozen)]
+#[derive(Clone)]
+pub struct DaemonEvent {
+    inner: syspulse_core::events::DaemonEvent,
+}
+
+impl DaemonEvent {
+    pub fn from_core(event: syspulse_core::events::DaemonEvent) -> Self {
+        Self { inner: event }
+    }
+}
+
+#[pymethods]
+impl DaemonEvent {
+    /// History id; 0 for resource samples, which are not recorded.
+    #[getter]
+    fn id(&self) -> i64 {
+        self.inner.id
+    }
+
+    #[getter]
+    fn timestamp(&self) -> String {
+        self.inner.timestamp.to_rfc3339()
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// ``"started"``, ``"stopped"``, ``"exited"``, ``"restarting"``,
+    /// ``"health"``, ``"cron_triggered"`` or ``"resources"``.
+    #[getter]
+    fn kind(&self) -> &'static str {
+        self.inner.kind.name()
+    }
+
+    #[getter]
+    fn message(&self) -> Option<String> {
+        self.inner.message.clone()
+    }
+
+    #[getter]
+    fn pid(&self) -> Option<u32> {
+        match self.inner.kind {
+            EventKind::Started { pid } => Some(pid),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn exit_code(&self) -> Option<i32> {
+        match self.inner.kind {
+            EventKind::Stopped { exit_code, .. } | EventKind::Exited { exit_code, .. } => exit_code,
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn signal(&self) -> Option<i32> {
+        match self.inner.kind {
+            EventKind::Stopped { signal, .. } | EventKind::Exited { signal, .. } => signal,
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn attempt(&self) -> Option<u32> {
+        match self.inner.kind {
+            EventKind::Restarting { attempt, .. } => Some(attempt),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn backoff_ms(&self) -> Option<u64> {
+        match self.inner.kind {
+            EventKind::Restarting { backoff_ms, .. } => Some(backoff_ms),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn health(&self) -> Option<HealthStatus> {
+        match &self.inner.kind {
+            EventKind::Health { status } => Some(HealthStatus::from(status.clone())),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn cpu_percent(&self) -> Option<f64> {
+        match self.inner.kind {
+            EventKind::Resources { cpu_percent, .. } => Some(cpu_percent),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn memory_bytes(&self) -> Option<u64> {
+        match self.inner.kind {
+            EventKind::Resources { memory_bytes, .. } => Some(memory_bytes),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn process_count(&self) -> Option<u32> {
+        match self.inner.kind {
+            EventKind::Resources { process_count, .. } => Some(process_count),
+            _ => None,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DaemonEvent(name='{}', kind='{}', timestamp='{}')",
+            self.inner.name,
+            self.inner.kind.name(),
+            self.inner.timestamp.to_rfc3339()
+        )
+    }
+}
@@ -29,6 +29,36 @@ impl From<&HealthCheckType> for syspulse_core::daemon::HealthCheckType {
     }
 }
 
+/// What to do once a daemon is marked unhealthy.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum UnhealthyAction {
+    /// Only report the daemon as unhealthy (`none` in config files).
+    Ignore = 0,
+    Restart = 1,
+    Stop = 2,
+}
+
+impl From<syspulse_core::daemon::UnhealthyAction> for UnhealthyAction {
+    fn from(action: syspulse_core::daemon::UnhealthyAction) -> Self {
+        match action {
+            syspulse_core::daemon::UnhealthyAction::None => UnhealthyAction::Ignore,
+            syspulse_core::daemon::UnhealthyAction::Restart => UnhealthyAction::Restart,
+            syspulse_core::daemon::UnhealthyAction::Stop => UnhealthyAction::Stop,
+        }
+    }
+}
+
+impl From<&UnhealthyAction> for syspulse_core::daemon::UnhealthyAction {
+    fn from(action: &UnhealthyAction) -> Self {
+        match action {
+            UnhealthyAction::Ignore => syspulse_core::daemon::UnhealthyAction::None,
+            UnhealthyAction::Restart => syspulse_core::daemon::UnhealthyAction::Restart,
+            UnhealthyAction::Stop => syspulse_core::daemon::UnhealthyAction::Stop,
+        }
+    }
+}
+
 /// Health check configuration for a daemon.
 #[pyclass]
 #[derive(Clone)]
@@ -39,7 +69,7 @@ pub struct HealthCheck {
 #[pymethods]
 impl HealthCheck {
     #[new]
-    #[pyo3(signature = (check_type, target, *, interval=30, timeout=5, retries=3, start_period=0))]
+    #[pyo3(signature = (check_type, target, *, interval=30, timeout=5, retries=3, start_period=0, on_unhealthy=UnhealthyAction::Ignore))]
     fn new(
         check_type: &HealthCheckType,
         target: String,
@@ -47,6 +77,7 @@ impl HealthCheck {
         timeout: u64,
         retries: u32,
         start_period: u64,
+        on_unhealthy: UnhealthyAction,
     ) -> Self {
         HealthCheck {
             inner: syspulse_core::daemon::HealthCheckSpec {
@@ -56,6 +87,7 @@ impl HealthCheck {
                 timeout_secs: timeout,
                 retries,
                 start_period_secs: start_period,
+                on_unhealthy: (&on_unhealthy).into(),
             },
         }
     }
@@ -90,6 +122,11 @@ impl HealthCheck {
         self.inner.start_period_secs
     }
 
+    #[getter]
+    fn on_unhealthy(&self) -> UnhealthyAction {
+        self.inner.on_unhealthy.into()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "HealthCheck(check_type={:?}, target='{}', interval={})",
@@ -47,11 +47,23 @@ impl DaemonInstance {
         self.inner.stopped_at.map(|t| t.to_rfc3339())
     }
 
+    /// When the daemon's schedule fires next, if it has one.
+    #[getter]
+    fn next_run_at(&self) -> Option<String> {
+        self.inner.next_run_at.map(|t| t.to_rfc3339())
+    }
+
     #[getter]
     fn exit_code(&self) -> Option<i32> {
         self.inner.exit_code
     }
 
+    /// Signal that terminated the last process, if it didn't exit by itself.
+    #[getter]
+    fn exit_signal(&self) -> Option<i32> {
+        self.inner.exit_signal
+    }
+
     #[getter]
     fn restart_count(&self) -> u32 {
         self.inner.restart_count
@@ -78,6 +90,21 @@ impl DaemonInstance {
             .map(|p| p.display().to_string())
     }
 
+    #[getter]
+    fn warnings(&self) -> Vec<String> {
+        self.inner.warnings.clone()
+    }
+
+    #[getter]
+    fn reason(&self) -> Option<String> {
+        self.inner.reason.clone()
+    }
+
+    #[getter]
+    fn status_message(&self) -> Option<String> {
+        self.inner.status_message.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "DaemonInstance(name='{}', state={:?}, pid={:?})",
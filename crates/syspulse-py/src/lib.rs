@@ -3,6 +3,7 @@ use pyo3::prelude::*;
 mod config;
 mod daemon;
 mod errors;
+mod events;
 mod health;
 mod instance;
 mod log_config;
@@ -20,6 +21,9 @@ fn _syspulse(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<daemon::Daemon>()?;
     m.add_class::<instance::DaemonInstance>()?;
     m.add_class::<manager::SyspulseClient>()?;
+    m.add_class::<manager::LogStream>()?;
+    m.add_class::<manager::EventStream>()?;
+    m.add_class::<events::DaemonEvent>()?;
 
     // Config classes
     m.add_class::<health::HealthCheck>()?;
@@ -31,6 +35,9 @@ fn _syspulse(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<types::HealthStatus>()?;
     m.add_class::<types::RestartPolicyType>()?;
     m.add_class::<health::HealthCheckType>()?;
+    m.add_class::<health::UnhealthyAction>()?;
+    m.add_class::<resources::LimitMode>()?;
+    m.add_class::<resources::LimitAction>()?;
 
     // Exceptions
     m.add(
@@ -1,16 +1,24 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
 use pyo3::prelude::*;
+use pyo3::IntoPyObjectExt;
+use tokio::sync::mpsc;
 
-use syspulse_core::ipc::client::IpcClient;
+use syspulse_core::ipc::client::{IpcClient, ResponseStream};
 use syspulse_core::ipc::protocol::{Request, Response};
 
 use crate::daemon::Daemon;
 use crate::errors::to_py_err;
+use crate::events::DaemonEvent;
 use crate::instance::DaemonInstance;
 
 #[pyclass]
 pub struct SyspulseClient {
     client: IpcClient,
-    runtime: tokio::runtime::Runtime,
+    runtime: Arc<tokio::runtime::Runtime>,
 }
 
 #[pymethods]
@@ -25,7 +33,7 @@ impl SyspulseClient {
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
         Ok(Self {
             client: IpcClient::new(path),
-            runtime,
+            runtime: Arc::new(runtime),
         })
     }
 
@@ -44,12 +52,19 @@ impl SyspulseClient {
         false
     }
 
-    #[pyo3(signature = (name, *, wait=None, timeout=None))]
-    fn start(&self, name: &str, wait: Option<bool>, timeout: Option<u64>) -> PyResult<String> {
+    #[pyo3(signature = (name, *, wait=None, timeout=None, with_deps=None))]
+    fn start(
+        &self,
+        name: &str,
+        wait: Option<bool>,
+        timeout: Option<u64>,
+        with_deps: Option<bool>,
+    ) -> PyResult<String> {
         let req = Request::Start {
             name: name.to_string(),
             wait: wait.unwrap_or(false),
             timeout_secs: timeout,
+            with_deps: with_deps.unwrap_or(false),
         };
         let resp = self
             .runtime
@@ -72,12 +87,45 @@ impl SyspulseClient {
         handle_ok_response(resp)
     }
 
-    #[pyo3(signature = (name, *, force=None, wait=None))]
-    fn restart(&self, name: &str, force: Option<bool>, wait: Option<bool>) -> PyResult<String> {
+    #[pyo3(signature = (name, *, force=None, wait=None, timeout=None, rolling=None))]
+    fn restart(
+        &self,
+        name: &str,
+        force: Option<bool>,
+        wait: Option<bool>,
+        timeout: Option<u64>,
+        rolling: Option<bool>,
+    ) -> PyResult<String> {
         let req = Request::Restart {
             name: name.to_string(),
             force: force.unwrap_or(false),
             wait: wait.unwrap_or(false),
+            timeout_secs: timeout,
+            rolling: rolling.unwrap_or(false),
+        };
+        let resp = self
+            .runtime
+            .block_on(self.client.send(req))
+            .map_err(to_py_err)?;
+        handle_ok_response(resp)
+    }
+
+    /// Run a scheduled daemon now, as if its schedule had fired.
+    fn trigger(&self, name: &str) -> PyResult<String> {
+        let req = Request::Trigger {
+            name: name.to_string(),
+        };
+        let resp = self
+            .runtime
+            .block_on(self.client.send(req))
+            .map_err(to_py_err)?;
+        handle_ok_response(resp)
+    }
+
+    /// Clear a failed or crash-looping daemon's state and restart count.
+    fn reset_failed(&self, name: &str) -> PyResult<String> {
+        let req = Request::ResetFailed {
+            name: name.to_string(),
         };
         let resp = self
             .runtime
@@ -125,24 +173,39 @@ impl SyspulseClient {
         }
     }
 
-    #[pyo3(signature = (name, *, lines=None, stderr=None))]
+    /// Return the last lines of a daemon's log. With ``follow=True``, return a
+    /// :class:`LogStream` that yields those lines and then every new line.
+    #[pyo3(signature = (name, *, lines=None, stderr=None, follow=None))]
     fn logs(
         &self,
+        py: Python<'_>,
         name: &str,
         lines: Option<usize>,
         stderr: Option<bool>,
-    ) -> PyResult<Vec<String>> {
+        follow: Option<bool>,
+    ) -> PyResult<PyObject> {
+        let follow = follow.unwrap_or(false);
         let req = Request::Logs {
             name: name.to_string(),
             lines: lines.unwrap_or(100),
             stderr: stderr.unwrap_or(false),
+            follow,
         };
+
+        if follow {
+            let stream = self
+                .runtime
+                .block_on(self.client.stream(req))
+                .map_err(to_py_err)?;
+            return LogStream::spawn(Arc::clone(&self.runtime), stream).into_py_any(py);
+        }
+
         let resp = self
             .runtime
             .block_on(self.client.send(req))
             .map_err(to_py_err)?;
         match resp {
-            Response::Logs { lines } => Ok(lines),
+            Response::Logs { lines } => lines.into_py_any(py),
             Response::Error { code, message } => Err(pyo3::exceptions::PyRuntimeError::new_err(
                 format!("Error {}: {}", code, message),
             )),
@@ -152,6 +215,25 @@ impl SyspulseClient {
         }
     }
 
+    /// Subscribe to lifecycle, health and resource events, optionally only
+    /// for some daemons or event kinds. Returns an :class:`EventStream`.
+    #[pyo3(signature = (names=None, *, kinds=None))]
+    fn subscribe(
+        &self,
+        names: Option<Vec<String>>,
+        kinds: Option<Vec<String>>,
+    ) -> PyResult<EventStream> {
+        let req = Request::Subscribe {
+            names: names.unwrap_or_default(),
+            kinds: kinds.unwrap_or_default(),
+        };
+        let stream = self
+            .runtime
+            .block_on(self.client.stream(req))
+            .map_err(to_py_err)?;
+        Ok(EventStream::spawn(Arc::clone(&self.runtime), stream))
+    }
+
     fn add(&self, daemon: &Daemon) -> PyResult<String> {
         let req = Request::Add {
             spec: daemon.inner.clone(),
@@ -199,9 +281,225 @@ impl SyspulseClient {
     }
 }
 
+/// Iterator over a followed daemon log. Blocks until new lines arrive; call
+/// :meth:`close` (or leave a ``with`` block) to stop following.
+#[pyclass(frozen)]
+pub struct LogStream {
+    stream: StreamReceiver,
+    pending: Mutex<VecDeque<String>>,
+}
+
+impl LogStream {
+    fn spawn(runtime: Arc<tokio::runtime::Runtime>, stream: ResponseStream) -> Self {
+        Self {
+            stream: StreamReceiver::spawn(runtime, stream),
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn pending(&self) -> MutexGuard<'_, VecDeque<String>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[pymethods]
+impl LogStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        loop {
+            if let Some(line) = self.pending().pop_front() {
+                return Ok(Some(line));
+            }
+            match self.stream.next(py)? {
+                Some(Response::Logs { lines }) => self.pending().extend(lines),
+                Some(_) => {
+                    self.stream.close();
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                        "Unexpected response",
+                    ));
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Stop following and close the connection to the manager.
+    fn close(&self) {
+        self.stream.close();
+        self.pending().clear();
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    fn __exit__(
+        &self,
+        _exc_type: Option<PyObject>,
+        _exc_val: Option<PyObject>,
+        _exc_tb: Option<PyObject>,
+    ) -> bool {
+        self.close();
+        false
+    }
+}
+
+/// Iterator over events pushed by the manager. Blocks until the next event
+/// arrives; call :meth:`close` (or leave a ``with`` block) to unsubscribe.
+#[pyclass(frozen)]
+pub struct EventStream {
+    stream: StreamReceiver,
+}
+
+impl EventStream {
+    fn spawn(runtime: Arc<tokio::runtime::Runtime>, stream: ResponseStream) -> Self {
+        Self {
+            stream: StreamReceiver::spawn(runtime, stream),
+        }
+    }
+}
+
+#[pymethods]
+impl EventStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<DaemonEvent>> {
+        match self.stream.next(py)? {
+            Some(Response::Event { event }) => Ok(Some(DaemonEvent::from_core(event))),
+            Some(_) => {
+                self.stream.close();
+                Err(pyo3::exceptions::PyRuntimeError::new_err(
+                    "Unexpected response",
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Unsubscribe and close the connection to the manager.
+    fn close(&self) {
+        self.stream.close();
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    fn __exit__(
+        &self,
+        _exc_type: Option<PyObject>,
+        _exc_val: Option<PyObject>,
+        _exc_tb: Option<PyObject>,
+    ) -> bool {
+        self.close();
+        false
+    }
+}
+
+/// How long to block waiting for streamed output before checking for Ctrl+C
+/// or a close from another thread.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The receiving end of a streamed request. The stream classes are frozen
+/// and this is shared, so `close` works from one thread while another is
+/// blocked in `next`, e.g. a cancelled ``asyncio.to_thread`` worker.
+struct StreamReceiver {
+    rx: Mutex<Option<mpsc::Receiver<syspulse_core::Result<Response>>>>,
+    closed: AtomicBool,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl StreamReceiver {
+    /// Read `stream` on a runtime task so waiting for output can be
+    /// interrupted without dropping half-read messages.
+    fn spawn(runtime: Arc<tokio::runtime::Runtime>, mut stream: ResponseStream) -> Self {
+        let (tx, rx) = mpsc::channel(16);
+        runtime.spawn(async move {
+            loop {
+                let next = tokio::select! {
+                    next = stream.next() => next,
+                    _ = tx.closed() => break,
+                };
+                let item = match next {
+                    Ok(Some(response)) => Ok(response),
+                    Ok(None) => break,
+                    Err(e) => Err(e),
+                };
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self {
+            rx: Mutex::new(Some(rx)),
+            closed: AtomicBool::new(false),
+            runtime,
+        }
+    }
+
+    /// Wait for the next streamed response without holding the GIL. Returns
+    /// `None` once the stream has ended or been closed; errors end the
+    /// stream as well.
+    fn next(&self, py: Python<'_>) -> PyResult<Option<Response>> {
+        loop {
+            // The lock is only taken with the GIL released, so a thread
+            // waiting for it never blocks one that needs the GIL back.
+            let next = py.allow_threads(|| {
+                let mut rx = self.rx.lock().unwrap_or_else(PoisonError::into_inner);
+                if self.closed.load(Ordering::Acquire) {
+                    *rx = None;
+                }
+                let receiver = rx.as_mut()?;
+                Some(self.runtime.block_on(async {
+                    tokio::time::timeout(FOLLOW_POLL_INTERVAL, receiver.recv()).await
+                }))
+            });
+
+            match next {
+                None => return Ok(None),
+                // Nothing yet: give Python a chance to raise KeyboardInterrupt.
+                Some(Err(_)) => py.check_signals()?,
+                Some(Ok(Some(Ok(Response::Error { code, message })))) => {
+                    self.close();
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Error {}: {}",
+                        code, message
+                    )));
+                }
+                Some(Ok(Some(Ok(response)))) => return Ok(Some(response)),
+                Some(Ok(Some(Err(e)))) => {
+                    self.close();
+                    return Err(to_py_err(e));
+                }
+                Some(Ok(None)) => self.close(),
+            }
+        }
+    }
+
+    /// End the stream. If another thread is waiting in `next`, it drops the
+    /// receiver, closing the connection, at its next poll instead.
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        if let Ok(mut rx) = self.rx.try_lock() {
+            *rx = None;
+        }
+    }
+}
+
 fn handle_ok_response(resp: Response) -> PyResult<String> {
     match resp {
         Response::Ok { message } => Ok(message),
+        Response::Error { code: 504, message } => {
+            Err(pyo3::exceptions::PyTimeoutError::new_err(message))
+        }
         Response::Error { code, message } => Err(pyo3::exceptions::PyRuntimeError::new_err(
             format!("Error {}: {}", code, message),
         )),
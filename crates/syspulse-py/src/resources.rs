@@ -1,5 +1,60 @@
 use pyo3::prelude::*;
 
+/// How resource limits are enforced.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum LimitMode {
+    Hard = 0,
+    Soft = 1,
+}
+
+impl From<syspulse_core::resources::LimitMode> for LimitMode {
+    fn from(mode: syspulse_core::resources::LimitMode) -> Self {
+        match mode {
+            syspulse_core::resources::LimitMode::Hard => LimitMode::Hard,
+            syspulse_core::resources::LimitMode::Soft => LimitMode::Soft,
+        }
+    }
+}
+
+impl From<&LimitMode> for syspulse_core::resources::LimitMode {
+    fn from(mode: &LimitMode) -> Self {
+        match mode {
+            LimitMode::Hard => syspulse_core::resources::LimitMode::Hard,
+            LimitMode::Soft => syspulse_core::resources::LimitMode::Soft,
+        }
+    }
+}
+
+/// What the manager does when a soft limit is exceeded.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum LimitAction {
+    Warn = 0,
+    Restart = 1,
+    Stop = 2,
+}
+
+impl From<syspulse_core::resources::LimitAction> for LimitAction {
+    fn from(action: syspulse_core::resources::LimitAction) -> Self {
+        match action {
+            syspulse_core::resources::LimitAction::Warn => LimitAction::Warn,
+            syspulse_core::resources::LimitAction::Restart => LimitAction::Restart,
+            syspulse_core::resources::LimitAction::Stop => LimitAction::Stop,
+        }
+    }
+}
+
+impl From<&LimitAction> for syspulse_core::resources::LimitAction {
+    fn from(action: &LimitAction) -> Self {
+        match action {
+            LimitAction::Warn => syspulse_core::resources::LimitAction::Warn,
+            LimitAction::Restart => syspulse_core::resources::LimitAction::Restart,
+            LimitAction::Stop => syspulse_core::resources::LimitAction::Stop,
+        }
+    }
+}
+
 /// Resource limits for a daemon process.
 #[pyclass]
 #[derive(Clone)]
@@ -10,17 +65,23 @@ pub struct ResourceLimits {
 #[pymethods]
 impl ResourceLimits {
     #[new]
-    #[pyo3(signature = (*, max_memory_bytes=None, max_cpu_percent=None, max_open_files=None))]
+    #[pyo3(signature = (*, max_memory_bytes=None, max_cpu_percent=None, max_open_files=None, mode=LimitMode::Hard, grace_period=30, action=LimitAction::Restart))]
     fn new(
         max_memory_bytes: Option<u64>,
         max_cpu_percent: Option<f64>,
         max_open_files: Option<u64>,
+        mode: LimitMode,
+        grace_period: u64,
+        action: LimitAction,
     ) -> Self {
         ResourceLimits {
             inner: syspulse_core::resources::ResourceLimits {
                 max_memory_bytes,
                 max_cpu_percent,
                 max_open_files,
+                mode: (&mode).into(),
+                grace_period_secs: grace_period,
+                action: (&action).into(),
             },
         }
     }
@@ -40,10 +101,28 @@ impl ResourceLimits {
         self.inner.max_open_files
     }
 
+    #[getter]
+    fn mode(&self) -> LimitMode {
+        self.inner.mode.into()
+    }
+
+    #[getter]
+    fn grace_period(&self) -> u64 {
+        self.inner.grace_period_secs
+    }
+
+    #[getter]
+    fn action(&self) -> LimitAction {
+        self.inner.action.into()
+    }
+
     fn __repr__(&self) -> String {
         format!(
-            "ResourceLimits(max_memory_bytes={:?}, max_cpu_percent={:?}, max_open_files={:?})",
-            self.inner.max_memory_bytes, self.inner.max_cpu_percent, self.inner.max_open_files
+            "ResourceLimits(max_memory_bytes={:?}, max_cpu_percent={:?}, max_open_files={:?}, mode={:?})",
+            self.inner.max_memory_bytes,
+            self.inner.max_cpu_percent,
+            self.inner.max_open_files,
+            self.inner.mode
         )
     }
 }
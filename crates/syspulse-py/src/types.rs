@@ -9,6 +9,7 @@ pub enum DaemonStatus {
     Stopping = 3,
     Failed = 4,
     Scheduled = 5,
+    CrashLooping = 6,
 }
 
 #[pyclass(eq, eq_int)]
@@ -37,6 +38,7 @@ impl From<syspulse_core::lifecycle::LifecycleState> for DaemonStatus {
             syspulse_core::lifecycle::LifecycleState::Stopping => DaemonStatus::Stopping,
             syspulse_core::lifecycle::LifecycleState::Failed => DaemonStatus::Failed,
             syspulse_core::lifecycle::LifecycleState::Scheduled => DaemonStatus::Scheduled,
+            syspulse_core::lifecycle::LifecycleState::CrashLooping => DaemonStatus::CrashLooping,
         }
     }
 }
@@ -0,0 +1,57 @@
+"""Tests for the async stream wrappers.
+
+Needs the extension module built, e.g. with ``maturin develop``:
+
+    python -m unittest discover crates/syspulse-py/tests
+"""
+
+import asyncio
+import os
+import socket
+import tempfile
+import unittest
+
+from syspulse import AsyncSyspulseClient
+
+
+@unittest.skipUnless(hasattr(socket, "AF_UNIX"), "needs Unix sockets")
+class CancelledStreamTest(unittest.IsolatedAsyncioTestCase):
+    """A stream whose pending ``__anext__`` was cancelled can still be
+    closed, and its worker thread returns."""
+
+    def setUp(self) -> None:
+        self._dir = tempfile.TemporaryDirectory()
+        path = os.path.join(self._dir.name, "syspulse.sock")
+        # A manager that accepts requests and never answers, like a quiet
+        # subscription.
+        self._server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        self._server.bind(path)
+        self._server.listen()
+        self.client = AsyncSyspulseClient(path)
+
+    def tearDown(self) -> None:
+        self._server.close()
+        self._dir.cleanup()
+
+    async def check_close_after_cancel(self, stream) -> None:
+        with self.assertRaises(asyncio.TimeoutError):
+            await asyncio.wait_for(stream.__anext__(), 0.5)
+        async with stream:
+            pass
+        with self.assertRaises(StopAsyncIteration):
+            await asyncio.wait_for(stream.__anext__(), 5)
+        # The worker of the cancelled __anext__ has returned as well.
+        loop = asyncio.get_running_loop()
+        await asyncio.wait_for(loop.shutdown_default_executor(), 5)
+
+    async def test_event_stream(self) -> None:
+        stream = await self.client.subscribe(["web"])
+        await self.check_close_after_cancel(stream)
+
+    async def test_log_stream(self) -> None:
+        stream = await self.client.logs("web", follow=True)
+        await self.check_close_after_cancel(stream)
+
+
+if __name__ == "__main__":
+    unittest.main()